@@ -22,9 +22,27 @@ pub(crate) fn run_app() {
             get_window_label,
             get_all_window_labels,
             list_project_entries,
+            list_directory,
+            stream_project_entries,
+            fuzzy_find_files,
+            search_project,
+            cancel_project_search,
+            replace_in_files,
             read_text_file,
+            reopen_with_encoding,
+            read_text_range,
+            search_text_file,
+            read_binary_range,
             write_text_file,
             format_structured_text,
+            validate_structured_text,
+            read_table,
+            update_table_cell,
+            markdown_outline,
+            check_markdown_links,
+            read_task_list,
+            csv_to_markdown_table,
+            markdown_table_to_csv,
             set_window_title,
             set_menu_state,
             close_current_window,
@@ -37,6 +55,8 @@ pub(crate) fn run_app() {
             watch_project_folder,
             clear_project_folder_watch,
             watch_project_files,
+            follow_file,
+            stop_following_file,
             open_paths_in_new_window,
             set_theme,
             report_drag_position,
@@ -46,15 +66,28 @@ pub(crate) fn run_app() {
             get_drag_preview_content,
             create_window_from_drag,
             trash_file,
+            create_file,
+            create_folder,
+            rename_path,
+            move_path,
+            duplicate_path,
+            undo_file_operation,
+            list_file_history,
+            diff_file_history,
+            restore_file_history,
             show_sidebar_context_menu,
             show_tab_context_menu,
             set_show_hidden_files_checked,
+            set_show_ignored_files_checked,
             set_show_modified_only_checked,
             add_recent_file,
             add_recent_folder,
             git_status,
             git_diff,
             git_diff_all,
+            diff_paths,
+            semantic_diff_paths,
+            git_semantic_diff,
             get_folder_icon
         ])
         .build(tauri::generate_context!())
@@ -121,6 +154,14 @@ fn setup_app(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     app.manage(FileWatchRegistry {
         by_window: Mutex::new(HashMap::new()),
     });
+    app.manage(project_search::SearchRegistry::new());
+    app.manage(log_follow::FollowRegistry::new());
+    app.manage(table_view::TableCache::new());
+    app.manage(large_file::LineIndexCache::new());
+    app.manage(markdown_outline::OutlineCache::new());
+    app.manage(task_list::TaskProgressCache::new());
+    app.manage(file_journal::FileJournal::new());
+    app.manage(local_history::LocalHistory::for_app(app.handle()));
     app.manage(CrossWindowDragRegistry::new());
     app.manage(TabDragPreviewState::new());
 
@@ -188,6 +229,14 @@ fn handle_window_event(window: &tauri::Window, event: &tauri::WindowEvent) {
     if let tauri::WindowEvent::Destroyed = event {
         clear_project_folder_watch_for_label(window.app_handle(), window.label());
         clear_project_file_watch_for_label(window.app_handle(), window.label());
+        window
+            .app_handle()
+            .state::<project_search::SearchRegistry>()
+            .cancel(window.label());
+        window
+            .app_handle()
+            .state::<log_follow::FollowRegistry>()
+            .clear_for_label(window.label());
         cleanup_drag_entries_for_window(window.app_handle(), window.label());
     }
 }
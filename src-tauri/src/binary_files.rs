@@ -0,0 +1,267 @@
+use super::*;
+use crate::text_encoding::guess_bomless_utf16;
+use encoding_rs::Encoding;
+use std::io::{Read, Seek, SeekFrom};
+
+/// How many leading bytes are inspected when sniffing file content.
+const SNIFF_LEN: usize = 8192;
+const HEX_ROW_BYTES: usize = 16;
+const DEFAULT_RANGE_BYTES: usize = 4096;
+const MAX_RANGE_BYTES: usize = 64 * 1024;
+
+/// Leading byte signatures, checked in order; the first match names the type.
+const MAGIC_NUMBERS: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "png"),
+    (b"\xFF\xD8\xFF", "jpeg"),
+    (b"GIF87a", "gif"),
+    (b"GIF89a", "gif"),
+    (b"%PDF-", "pdf"),
+    (b"PK\x03\x04", "zip"),
+    (b"PK\x05\x06", "zip"),
+    (b"\x1F\x8B", "gzip"),
+    (b"BZh", "bzip2"),
+    (b"\xFD7zXZ\x00", "xz"),
+    (b"7z\xBC\xAF\x27\x1C", "7z"),
+    (b"\x28\xB5\x2F\xFD", "zstd"),
+    (b"Rar!\x1A\x07", "rar"),
+    (b"\x7FELF", "elf"),
+    (b"MZ", "exe"),
+    (b"\xCF\xFA\xED\xFE", "mach-o"),
+    (b"\xCE\xFA\xED\xFE", "mach-o"),
+    (b"\xCA\xFE\xBA\xBE", "class"),
+    (b"\x00asm", "wasm"),
+    (b"SQLite format 3\x00", "sqlite"),
+    (b"BM", "bmp"),
+    (b"\x00\x00\x01\x00", "ico"),
+    (b"II*\x00", "tiff"),
+    (b"MM\x00*", "tiff"),
+    (b"ID3", "mp3"),
+    (b"OggS", "ogg"),
+    (b"fLaC", "flac"),
+    (b"\x1A\x45\xDF\xA3", "webm"),
+    (b"wOFF", "woff"),
+    (b"wOF2", "woff2"),
+    (b"\x00\x01\x00\x00\x00", "ttf"),
+    (b"OTTO", "otf"),
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HexRow {
+    pub(crate) offset: u64,
+    /// Space-separated byte values, e.g. `"89 50 4e 47"`.
+    pub(crate) hex: String,
+    /// Printable ASCII with `.` in place of other bytes.
+    pub(crate) ascii: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BinaryRange {
+    pub(crate) offset: u64,
+    pub(crate) rows: Vec<HexRow>,
+    pub(crate) total_size: u64,
+    pub(crate) has_more: bool,
+    pub(crate) binary_type: Option<String>,
+}
+
+fn riff_type(sample: &[u8]) -> Option<&'static str> {
+    if sample.len() < 12 || &sample[..4] != b"RIFF" {
+        return None;
+    }
+    match &sample[8..12] {
+        b"WEBP" => Some("webp"),
+        b"WAVE" => Some("wav"),
+        b"AVI " => Some("avi"),
+        _ => None,
+    }
+}
+
+fn iso_media_type(sample: &[u8]) -> Option<&'static str> {
+    if sample.len() < 12 || &sample[4..8] != b"ftyp" {
+        return None;
+    }
+    match &sample[8..12] {
+        b"heic" | b"heix" | b"mif1" => Some("heic"),
+        b"avif" => Some("avif"),
+        b"qt  " => Some("mov"),
+        _ => Some("mp4"),
+    }
+}
+
+fn is_plain_utf8(sample: &[u8]) -> bool {
+    if sample.contains(&0) {
+        return false;
+    }
+    match std::str::from_utf8(sample) {
+        Ok(_) => true,
+        // The sample may end in the middle of a multi-byte character.
+        Err(e) => e.error_len().is_none(),
+    }
+}
+
+/// Whether the sample has bytes no single-byte text encoding would
+/// produce: NUL, C0 controls other than whitespace and escape, or the
+/// positions Windows-1252 leaves undefined.
+fn has_undecodable_bytes(sample: &[u8]) -> bool {
+    sample.iter().any(|&byte| match byte {
+        b'\t' | b'\n' | b'\r' | 0x0C | 0x1B => false,
+        0x00..=0x1F | 0x7F => true,
+        0x81 | 0x8D | 0x8F | 0x90 | 0x9D => true,
+        _ => false,
+    })
+}
+
+/// Classify leading file bytes. Returns the badge for binary content, or
+/// `None` for text. Valid UTF-8 without NUL bytes is always text, and
+/// signatures shorter than four bytes (`MZ`, `BM`, `ID3`, ...) only count
+/// when the sample also has undecodable bytes, so Latin-1 prose that
+/// starts with them stays text. Known signatures are named; anything else
+/// containing a NUL byte (outside UTF-16 text) is reported as plain
+/// `"binary"`.
+pub(crate) fn binary_type_of(sample: &[u8]) -> Option<&'static str> {
+    let sample = &sample[..sample.len().min(SNIFF_LEN)];
+    if is_plain_utf8(sample) {
+        return None;
+    }
+    if let Some(found) = riff_type(sample).or_else(|| iso_media_type(sample)) {
+        return Some(found);
+    }
+    if let Some((magic, name)) = MAGIC_NUMBERS
+        .iter()
+        .find(|(magic, _)| sample.starts_with(magic))
+    {
+        if magic.len() >= 4 || has_undecodable_bytes(sample) {
+            return Some(name);
+        }
+    }
+    if !sample.contains(&0) {
+        return None;
+    }
+    if Encoding::for_bom(sample).is_some() || guess_bomless_utf16(sample).is_some() {
+        return None;
+    }
+    Some("binary")
+}
+
+/// Badge for a file whose extension names a binary format, without
+/// reading it. Unknown extensions return `None`.
+pub(crate) fn binary_type_for_extension(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    Some(match extension.as_str() {
+        "png" => "png",
+        "jpg" | "jpeg" => "jpeg",
+        "gif" => "gif",
+        "bmp" => "bmp",
+        "ico" => "ico",
+        "tif" | "tiff" => "tiff",
+        "webp" => "webp",
+        "heic" => "heic",
+        "avif" => "avif",
+        "pdf" => "pdf",
+        "zip" | "jar" | "docx" | "xlsx" | "pptx" => "zip",
+        "gz" | "tgz" => "gzip",
+        "bz2" => "bzip2",
+        "xz" => "xz",
+        "7z" => "7z",
+        "zst" => "zstd",
+        "rar" => "rar",
+        "so" | "o" => "elf",
+        "exe" | "dll" => "exe",
+        "dylib" => "mach-o",
+        "class" => "class",
+        "wasm" => "wasm",
+        "db" | "sqlite" | "sqlite3" => "sqlite",
+        "mp3" => "mp3",
+        "ogg" => "ogg",
+        "flac" => "flac",
+        "wav" => "wav",
+        "webm" => "webm",
+        "mp4" | "m4a" | "m4v" => "mp4",
+        "mov" => "mov",
+        "avi" => "avi",
+        "woff" => "woff",
+        "woff2" => "woff2",
+        "ttf" => "ttf",
+        "otf" => "otf",
+        _ => return None,
+    })
+}
+
+/// Read the start of `path` and classify it with `binary_type_of`.
+/// Unreadable files are treated as text so they stay openable.
+pub(crate) fn sniff_binary_type(path: &Path) -> Option<&'static str> {
+    let file = fs::File::open(path).ok()?;
+    let mut sample = Vec::with_capacity(SNIFF_LEN);
+    file.take(SNIFF_LEN as u64).read_to_end(&mut sample).ok()?;
+    binary_type_of(&sample)
+}
+
+fn hex_rows(bytes: &[u8], start_offset: u64) -> Vec<HexRow> {
+    bytes
+        .chunks(HEX_ROW_BYTES)
+        .enumerate()
+        .map(|(i, chunk)| HexRow {
+            offset: start_offset + (i * HEX_ROW_BYTES) as u64,
+            hex: chunk
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect::<Vec<_>>()
+                .join(" "),
+            ascii: chunk
+                .iter()
+                .map(|byte| {
+                    if byte.is_ascii_graphic() || *byte == b' ' {
+                        *byte as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Read `length` bytes at `offset` as hex/ASCII rows of 16 bytes for a
+/// paged hex view. The offset is rounded down to a row boundary and the
+/// length is capped at 64 KiB per call.
+#[tauri::command]
+pub(crate) fn read_binary_range(
+    path: String,
+    offset: u64,
+    length: Option<usize>,
+) -> Result<BinaryRange, String> {
+    let path_buf = PathBuf::from(&path);
+    if !path_buf.is_file() {
+        return Err("File was not found".to_string());
+    }
+    let mut file = fs::File::open(&path_buf).map_err(|e| format!("Unable to read file: {e}"))?;
+    let total_size = file
+        .metadata()
+        .map_err(|e| format!("Unable to read file metadata: {e}"))?
+        .len();
+
+    let mut sample = Vec::with_capacity(SNIFF_LEN);
+    (&mut file)
+        .take(SNIFF_LEN as u64)
+        .read_to_end(&mut sample)
+        .map_err(|e| format!("Unable to read file: {e}"))?;
+    let binary_type = binary_type_of(&sample).map(str::to_string);
+
+    let offset = (offset - offset % HEX_ROW_BYTES as u64).min(total_size);
+    let length = length.unwrap_or(DEFAULT_RANGE_BYTES).min(MAX_RANGE_BYTES);
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|e| format!("Unable to read file: {e}"))?;
+    let mut bytes = Vec::with_capacity(length);
+    file.take(length as u64)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Unable to read file: {e}"))?;
+
+    Ok(BinaryRange {
+        offset,
+        rows: hex_rows(&bytes, offset),
+        total_size,
+        has_more: offset + (bytes.len() as u64) < total_size,
+        binary_type,
+    })
+}
@@ -8,14 +8,25 @@ pub(crate) const EVENT_OPEN_FOLDER_SELECTED: &str = "teex://open-folder-selected
 pub(crate) const EVENT_OS_OPEN_PATHS: &str = "teex://os-open-paths";
 pub(crate) const EVENT_PROJECT_FOLDER_CHANGED: &str = "teex://project-folder-changed";
 pub(crate) const EVENT_PROJECT_FILE_CHANGED: &str = "teex://project-file-changed";
+pub(crate) const EVENT_FILE_APPENDED: &str = "teex://file-appended";
+pub(crate) const EVENT_MARKDOWN_OUTLINE_CHANGED: &str = "teex://markdown-outline-changed";
+pub(crate) const EVENT_TASK_PROGRESS: &str = "teex://task-progress";
+pub(crate) const EVENT_PROJECT_ENTRIES_CHUNK: &str = "teex://project-entries-chunk";
+pub(crate) const EVENT_PROJECT_SEARCH_RESULTS: &str = "teex://project-search-results";
 pub(crate) const EVENT_TOGGLE_SIDEBAR: &str = "teex://toggle-sidebar";
 pub(crate) const EVENT_TOGGLE_MARKDOWN_MODE: &str = "teex://toggle-markdown-mode";
+pub(crate) const EVENT_TOGGLE_TABLE_VIEW: &str = "teex://toggle-table-view";
 pub(crate) const EVENT_CLOSE_ACTIVE_FILE: &str = "teex://close-active-file";
 pub(crate) const EVENT_NEW_TAB: &str = "teex://new-tab";
 pub(crate) const EVENT_REQUEST_EXPORT_ALL_TABS: &str = "teex://request-export-all-tabs";
 pub(crate) const EVENT_RECEIVE_TRANSFERRED_TABS: &str = "teex://receive-transferred-tabs";
 pub(crate) const EVENT_TAB_TRANSFER_RESULT: &str = "teex://tab-transfer-result";
 pub(crate) const EVENT_CONTEXT_MENU_DELETE: &str = "teex://context-menu-delete";
+pub(crate) const EVENT_CONTEXT_MENU_NEW_FILE: &str = "teex://context-menu-new-file";
+pub(crate) const EVENT_CONTEXT_MENU_NEW_FOLDER: &str = "teex://context-menu-new-folder";
+pub(crate) const EVENT_CONTEXT_MENU_RENAME: &str = "teex://context-menu-rename";
+pub(crate) const EVENT_CONTEXT_MENU_DUPLICATE: &str = "teex://context-menu-duplicate";
+pub(crate) const EVENT_PATH_RENAMED: &str = "teex://path-renamed";
 pub(crate) const EVENT_TAB_CONTEXT_MENU_CLOSE: &str = "teex://tab-context-menu-close";
 pub(crate) const EVENT_TAB_CONTEXT_MENU_CLOSE_OTHERS: &str = "teex://tab-context-menu-close-others";
 pub(crate) const EVENT_CROSS_WINDOW_DRAG_ENTER: &str = "teex://cross-window-drag-enter";
@@ -26,10 +37,14 @@ pub(crate) const EVENT_MOUSE_NAV_BACK: &str = "teex://mouse-nav-back";
 pub(crate) const EVENT_MOUSE_NAV_FORWARD: &str = "teex://mouse-nav-forward";
 pub(crate) const EVENT_TOGGLE_STATUS_BAR: &str = "teex://toggle-status-bar";
 pub(crate) const EVENT_TOGGLE_HIDDEN_FILES: &str = "teex://toggle-hidden-files";
+pub(crate) const EVENT_TOGGLE_IGNORED_FILES: &str = "teex://toggle-ignored-files";
 pub(crate) const EVENT_TOGGLE_MODIFIED_ONLY: &str = "teex://toggle-modified-only";
 pub(crate) const EVENT_TOGGLE_COLLAPSE_ALL_FOLDERS: &str = "teex://toggle-collapse-all-folders";
 pub(crate) const EVENT_FIND: &str = "teex://find";
+pub(crate) const EVENT_REOPEN_WITH_ENCODING: &str = "teex://reopen-with-encoding";
 pub(crate) const EVENT_FORMAT_FILE: &str = "teex://format-file";
+pub(crate) const EVENT_COPY_TABLE: &str = "teex://copy-table";
+pub(crate) const EVENT_UNDO_FILE_OPERATION: &str = "teex://undo-file-operation";
 pub(crate) const EVENT_TOGGLE_UNIFIED_DIFF: &str = "teex://toggle-unified-diff";
 pub(crate) const EVENT_SET_THEME: &str = "teex://set-theme";
 pub(crate) const EVENT_RESTORE_SESSION: &str = "teex://restore-session";
@@ -49,17 +64,25 @@ pub(crate) const MENU_CLOSE_WINDOW: &str = "close_window";
 pub(crate) const MENU_TOGGLE_SIDEBAR: &str = "toggle_sidebar";
 pub(crate) const MENU_NEW_TAB: &str = "new_tab";
 pub(crate) const MENU_TOGGLE_MARKDOWN_MODE: &str = "toggle_markdown_mode";
+pub(crate) const MENU_TOGGLE_TABLE_VIEW: &str = "toggle_table_view";
 pub(crate) const MENU_TOGGLE_STATUS_BAR: &str = "toggle_status_bar";
 pub(crate) const MENU_SHOW_HIDDEN_FILES: &str = "show_hidden_files";
+pub(crate) const MENU_SHOW_IGNORED_FILES: &str = "show_ignored_files";
 pub(crate) const MENU_SHOW_MODIFIED_ONLY: &str = "show_modified_only";
 pub(crate) const MENU_TOGGLE_COLLAPSE_ALL_FOLDERS: &str = "toggle_collapse_all_folders";
 pub(crate) const MENU_FIND: &str = "find";
 pub(crate) const MENU_FORMAT_FILE: &str = "format_file";
+pub(crate) const MENU_FORMAT_FILE_SORT_KEYS: &str = "format_file_sort_keys";
+pub(crate) const MENU_MINIFY_FILE: &str = "minify_file";
+pub(crate) const MENU_COPY_AS_MARKDOWN_TABLE: &str = "copy_as_markdown_table";
+pub(crate) const MENU_COPY_TABLE_AS_CSV: &str = "copy_table_as_csv";
+pub(crate) const MENU_UNDO_FILE_OPERATION: &str = "undo_file_operation";
 pub(crate) const MENU_UNIFIED_DIFF: &str = "unified_diff";
 pub(crate) const MENU_THEME_SYSTEM: &str = "theme_system";
 pub(crate) const MENU_THEME_LIGHT: &str = "theme_light";
 pub(crate) const MENU_THEME_DARK: &str = "theme_dark";
 pub(crate) const MENU_RESTORE_SESSION: &str = "restore_session";
+pub(crate) const MENU_REOPEN_WITH_ENCODING: &str = "reopen_with_encoding";
 pub(crate) const MENU_CLEAR_RECENTS: &str = "clear_recents";
 pub(crate) const MENU_RECENT_FILE_PREFIX: &str = "recent_file:";
 pub(crate) const MENU_RECENT_FOLDER_PREFIX: &str = "recent_folder:";
@@ -67,6 +90,9 @@ pub(crate) const MENU_RECENT_FOLDER_PREFIX: &str = "recent_folder:";
 // Global counters
 pub(crate) static NEXT_WINDOW_ID: AtomicUsize = AtomicUsize::new(1);
 pub(crate) static NEXT_TRANSFER_REQUEST_ID: AtomicUsize = AtomicUsize::new(1);
+pub(crate) static NEXT_LISTING_REQUEST_ID: AtomicUsize = AtomicUsize::new(1);
+pub(crate) static NEXT_SEARCH_REQUEST_ID: AtomicUsize = AtomicUsize::new(1);
+pub(crate) static NEXT_TEMP_FILE_ID: AtomicUsize = AtomicUsize::new(1);
 
 // Timing
 pub(crate) const FOLDER_WATCH_DEBOUNCE: Duration = Duration::from_millis(250);
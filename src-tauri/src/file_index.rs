@@ -0,0 +1,456 @@
+use super::*;
+use crate::project_ignore::{is_ignore_file, project_walk_builder, ProjectIgnore};
+use crate::recent_files::RecentState;
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+const DEFAULT_FUZZY_LIMIT: usize = 50;
+const RECENT_FILE_BOOST: i64 = 12;
+
+const SCORE_MATCH: i64 = 16;
+const BONUS_BOUNDARY: i64 = 10;
+const BONUS_CAMEL: i64 = 8;
+const BONUS_CONSECUTIVE: i64 = 6;
+const BONUS_BASENAME: i64 = 24;
+const PENALTY_GAP: i64 = 1;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FuzzyFileMatch {
+    pub(crate) path: String,
+    pub(crate) rel_path: String,
+    pub(crate) score: i64,
+    /// Half-open `[start, end)` character ranges of `rel_path` to highlight.
+    pub(crate) ranges: Vec<(usize, usize)>,
+}
+
+struct IndexedFile {
+    path: String,
+    chars: Vec<char>,
+    basename_start: usize,
+}
+
+type RankedMatch<'a> = (i64, &'a String, &'a IndexedFile, Vec<usize>);
+
+impl IndexedFile {
+    fn new(path: &Path, rel_path: &str) -> Self {
+        let chars: Vec<char> = rel_path.chars().collect();
+        let basename_start = chars
+            .iter()
+            .rposition(|c| *c == '/' || *c == '\\')
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        Self {
+            path: path_to_string(path),
+            chars,
+            basename_start,
+        }
+    }
+}
+
+/// In-memory list of the files under a window's project root, kept current
+/// by the folder watcher and queried by `fuzzy_find_files`.
+///
+/// Until a crawl finishes, changed paths are also queued in `pending` so
+/// they can be replayed onto the freshly built index.
+pub(crate) struct ProjectFileIndex {
+    root: PathBuf,
+    show_hidden: bool,
+    show_ignored: bool,
+    ignore: Option<ProjectIgnore>,
+    files: BTreeMap<String, IndexedFile>,
+    ready: bool,
+    pending: Vec<PathBuf>,
+    /// Bumped when a rebuild starts; only the newest one is swapped in.
+    generation: u64,
+}
+
+pub(crate) type SharedFileIndex = Arc<RwLock<ProjectFileIndex>>;
+
+impl ProjectFileIndex {
+    pub(crate) fn new(root: &Path, show_hidden: bool, show_ignored: bool) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            show_hidden,
+            show_ignored,
+            ignore: (!show_ignored).then(|| ProjectIgnore::new(root)),
+            files: BTreeMap::new(),
+            ready: false,
+            pending: Vec::new(),
+            generation: 0,
+        }
+    }
+
+    fn rel_path(&self, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(&self.root).ok()?;
+        if relative.as_os_str().is_empty() {
+            return None;
+        }
+        Some(relative.to_string_lossy().to_string())
+    }
+
+    fn insert_file(&mut self, path: &Path) {
+        if !is_visible_project_file(path, self.show_hidden) {
+            return;
+        }
+        let Some(rel_path) = self.rel_path(path) else {
+            return;
+        };
+        let file = IndexedFile::new(path, &rel_path);
+        self.files.insert(rel_path, file);
+    }
+
+    fn remove_tree(&mut self, path: &Path) {
+        let Some(rel_path) = self.rel_path(path) else {
+            return;
+        };
+        self.files.remove(&rel_path);
+
+        let prefix = format!("{rel_path}{}", std::path::MAIN_SEPARATOR);
+        let nested: Vec<String> = self
+            .files
+            .range(prefix.clone()..)
+            .take_while(|(key, _)| key.starts_with(&prefix))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in nested {
+            self.files.remove(&key);
+        }
+    }
+
+    fn insert_tree(&mut self, dir: &Path) {
+        // The walker only sees ignore files at or below `dir` (plus its
+        // repository parents), so subtrees get re-checked against the rules
+        // collected from the project root.
+        let check_ignore = dir != self.root;
+        for entry in project_walk_builder(dir, self.show_hidden, self.show_ignored).build() {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            let path = entry.path();
+            if !path.is_file() || (check_ignore && self.is_ignored(path, false)) {
+                continue;
+            }
+            self.insert_file(path);
+        }
+    }
+
+    fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        self.ignore
+            .as_ref()
+            .is_some_and(|ignore| ignore.is_ignored(path, is_dir))
+    }
+
+    /// Whether `path` is outside the root, inside `.git`, or inside a
+    /// dotfile or dot-folder while hidden files are not shown.
+    fn is_hidden(&self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return true;
+        };
+        relative.components().any(|component| {
+            component.as_os_str().to_str().is_none_or(|name| {
+                is_always_blocked_dir(name) || (!self.show_hidden && name.starts_with('.'))
+            })
+        })
+    }
+
+    /// Whether any of `paths` can change the index. Checked under the read
+    /// lock so churn in `.git`, hidden or ignored folders never waits for
+    /// the write lock.
+    fn is_affected_by(&self, paths: &[PathBuf]) -> bool {
+        paths.iter().any(|path| {
+            is_ignore_file(path) || !(self.is_hidden(path) || self.is_ignored(path, path.is_dir()))
+        })
+    }
+
+    /// Replace the index contents with a fresh walk of the root.
+    pub(crate) fn rebuild(&mut self) {
+        self.files.clear();
+        let root = self.root.clone();
+        self.insert_tree(&root);
+        self.ready = true;
+    }
+
+    /// Re-sync the given paths with what is currently on disk. A change to
+    /// an ignore file can hide or reveal whole subtrees, so it needs a full
+    /// rebuild instead: the index starts queueing changes again and `true`
+    /// is returned for the caller to rebuild, see `spawn_rebuild`.
+    pub(crate) fn apply_changes(&mut self, paths: &[PathBuf]) -> bool {
+        if !self.ready {
+            self.pending.extend(paths.iter().cloned());
+        }
+
+        let ignore_files: Vec<&PathBuf> = paths.iter().filter(|p| is_ignore_file(p)).collect();
+        if !ignore_files.is_empty() {
+            if let Some(ignore) = &self.ignore {
+                for path in ignore_files {
+                    ignore.invalidate(path);
+                }
+            }
+            if !self.ready {
+                return false;
+            }
+            self.ready = false;
+            self.generation += 1;
+            return true;
+        }
+
+        for path in paths {
+            if self.is_hidden(path) {
+                continue;
+            }
+            self.remove_tree(path);
+            if !path.exists() {
+                continue;
+            }
+            if path.is_dir() {
+                if !self.is_ignored(path, true) {
+                    self.insert_tree(path);
+                }
+            } else if !self.is_ignored(path, false) {
+                self.insert_file(path);
+            }
+        }
+        false
+    }
+
+    pub(crate) fn find(
+        &self,
+        query: &str,
+        limit: usize,
+        recent_files: &[String],
+    ) -> Vec<FuzzyFileMatch> {
+        let needle: Vec<char> = query
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        let recent_boost = |path: &str| -> i64 {
+            recent_files
+                .iter()
+                .position(|recent| recent == path)
+                .map(|rank| (recent_files.len() - rank) as i64 * RECENT_FILE_BOOST)
+                .unwrap_or(0)
+        };
+
+        let mut matches: Vec<RankedMatch> = self
+            .files
+            .iter()
+            .filter_map(|(rel_path, file)| {
+                let (score, positions) = fuzzy_score(&needle, file)?;
+                Some((score + recent_boost(&file.path), rel_path, file, positions))
+            })
+            .collect();
+
+        let by_rank = |a: &RankedMatch, b: &RankedMatch| {
+            b.0.cmp(&a.0)
+                .then_with(|| a.2.chars.len().cmp(&b.2.chars.len()))
+                .then_with(|| a.1.cmp(b.1))
+        };
+        if matches.len() > limit {
+            matches.select_nth_unstable_by(limit, by_rank);
+            matches.truncate(limit);
+        }
+        matches.sort_by(by_rank);
+
+        matches
+            .into_iter()
+            .map(|(score, rel_path, file, positions)| FuzzyFileMatch {
+                path: file.path.clone(),
+                rel_path: rel_path.clone(),
+                score,
+                ranges: positions_to_ranges(&positions),
+            })
+            .collect()
+    }
+}
+
+fn is_word_boundary(prev: char) -> bool {
+    matches!(prev, '/' | '\\' | '_' | '-' | '.' | ' ')
+}
+
+/// Score `needle` against the span of `file` starting at `from`, returning
+/// `None` when the needle is not a subsequence of that span.
+///
+/// The greedy forward pass finds the earliest end of a match; walking back
+/// from there finds the tightest start, and the final forward pass over that
+/// window collects positions for scoring and highlighting.
+fn score_span(needle: &[char], file: &IndexedFile, from: usize) -> Option<(i64, Vec<usize>)> {
+    let haystack = &file.chars[from..];
+    if needle.is_empty() {
+        return Some((0, Vec::new()));
+    }
+
+    let mut qi = 0;
+    let mut end = None;
+    for (i, c) in haystack.iter().enumerate() {
+        if c.to_ascii_lowercase() == needle[qi] {
+            qi += 1;
+            if qi == needle.len() {
+                end = Some(i);
+                break;
+            }
+        }
+    }
+    let end = end?;
+
+    let mut qi = needle.len();
+    let mut start = end;
+    for i in (0..=end).rev() {
+        if haystack[i].to_ascii_lowercase() == needle[qi - 1] {
+            qi -= 1;
+            if qi == 0 {
+                start = i;
+                break;
+            }
+        }
+    }
+
+    let mut positions = Vec::with_capacity(needle.len());
+    let mut score = 0;
+    let mut qi = 0;
+    let mut previous: Option<usize> = None;
+    for (i, c) in haystack.iter().enumerate().take(end + 1).skip(start) {
+        if qi == needle.len() || c.to_ascii_lowercase() != needle[qi] {
+            continue;
+        }
+        let absolute = from + i;
+        score += SCORE_MATCH;
+        if absolute == 0 || is_word_boundary(file.chars[absolute - 1]) {
+            score += BONUS_BOUNDARY;
+        } else if file.chars[absolute].is_uppercase() && file.chars[absolute - 1].is_lowercase() {
+            score += BONUS_CAMEL;
+        }
+        match previous {
+            Some(prev) if prev + 1 == absolute => score += BONUS_CONSECUTIVE,
+            Some(prev) => score -= PENALTY_GAP * (absolute - prev - 1).min(8) as i64,
+            None => {}
+        }
+        positions.push(absolute);
+        previous = Some(absolute);
+        qi += 1;
+    }
+
+    Some((score, positions))
+}
+
+fn fuzzy_score(needle: &[char], file: &IndexedFile) -> Option<(i64, Vec<usize>)> {
+    let full = score_span(needle, file, 0)?;
+    let best = match score_span(needle, file, file.basename_start) {
+        Some((score, positions)) if score + BONUS_BASENAME >= full.0 => {
+            (score + BONUS_BASENAME, positions)
+        }
+        _ => full,
+    };
+    // Prefer shorter paths when everything else is equal.
+    Some((best.0 - (file.chars.len() as i64 / 16), best.1))
+}
+
+fn positions_to_ranges(positions: &[usize]) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &position in positions {
+        match ranges.last_mut() {
+            Some((_, end)) if *end == position => *end += 1,
+            _ => ranges.push((position, position + 1)),
+        }
+    }
+    ranges
+}
+
+/// Walk the root into a scratch index on a background thread so queries
+/// and watcher updates are not blocked during the crawl, then swap it in
+/// and replay the changes queued meanwhile.
+fn spawn_rebuild(index: &SharedFileIndex) {
+    let Ok(current) = index.read() else {
+        return;
+    };
+    let root = current.root.clone();
+    let (show_hidden, show_ignored) = (current.show_hidden, current.show_ignored);
+    let generation = current.generation;
+    drop(current);
+
+    let building = Arc::clone(index);
+    std::thread::spawn(move || {
+        let mut fresh = ProjectFileIndex::new(&root, show_hidden, show_ignored);
+        fresh.rebuild();
+        fresh.generation = generation;
+        let rebuild_again = {
+            let Ok(mut index) = building.write() else {
+                return;
+            };
+            if index.generation != generation {
+                return;
+            }
+            let pending = std::mem::take(&mut index.pending);
+            let rebuild_again = fresh.apply_changes(&pending);
+            *index = fresh;
+            rebuild_again
+        };
+        if rebuild_again {
+            spawn_rebuild(&building);
+        }
+    });
+}
+
+pub(crate) fn build_shared_index(
+    root: &Path,
+    show_hidden: bool,
+    show_ignored: bool,
+) -> SharedFileIndex {
+    let index = Arc::new(RwLock::new(ProjectFileIndex::new(
+        root,
+        show_hidden,
+        show_ignored,
+    )));
+    spawn_rebuild(&index);
+    index
+}
+
+/// Apply a watcher event to a shared index. Only events that can change it
+/// take the write lock, and ignore-file changes rebuild in the background.
+pub(crate) fn update_shared_index(index: &SharedFileIndex, paths: &[PathBuf]) {
+    let affected = index.read().is_ok_and(|index| index.is_affected_by(paths));
+    if !affected {
+        return;
+    }
+    let rebuild = index
+        .write()
+        .is_ok_and(|mut index| index.apply_changes(paths));
+    if rebuild {
+        spawn_rebuild(index);
+    }
+}
+
+#[tauri::command]
+pub(crate) fn fuzzy_find_files(
+    window: tauri::Window,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<FuzzyFileMatch>, String> {
+    let app = window.app_handle();
+    let index = {
+        let registry = app.state::<FolderWatchRegistry>();
+        let watches = registry
+            .by_window
+            .lock()
+            .map_err(|_| "Unable to read folder watcher registry".to_string())?;
+        let Some(watch) = watches.get(window.label()) else {
+            return Ok(Vec::new());
+        };
+        Arc::clone(&watch.index)
+    };
+
+    let recent_files = app
+        .try_state::<RecentState>()
+        .and_then(|state| state.files.lock().ok().map(|files| files.clone()))
+        .unwrap_or_default();
+
+    let index = index
+        .read()
+        .map_err(|_| "Unable to read file index".to_string())?;
+    Ok(index.find(
+        &query,
+        limit.unwrap_or(DEFAULT_FUZZY_LIMIT).max(1),
+        &recent_files,
+    ))
+}
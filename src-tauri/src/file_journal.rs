@@ -0,0 +1,271 @@
+use super::*;
+use crate::file_ops::{emit_path_renamed, is_same_entry, move_or_copy};
+use std::ffi::OsString;
+
+/// Oldest operations are dropped once the journal holds this many.
+const MAX_JOURNAL_ENTRIES: usize = 100;
+
+/// A destructive file operation that can be reversed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum FileOperation {
+    /// `path` was moved to the OS trash. `trash_id` names the trashed item
+    /// on platforms that can list the trash (the `.trashinfo` path on Linux).
+    Trashed {
+        path: PathBuf,
+        trash_id: Option<OsString>,
+    },
+    /// A rename or move from `from` to `to`.
+    Moved { from: PathBuf, to: PathBuf },
+    /// Operations done as one action, oldest first, such as trashing the
+    /// destination a move replaced and then the move itself.
+    Steps(Vec<FileOperation>),
+}
+
+impl FileOperation {
+    /// Group `steps` into one journal entry.
+    pub(crate) fn steps(mut steps: Vec<FileOperation>) -> Option<Self> {
+        match steps.len() {
+            0 => None,
+            1 => steps.pop(),
+            _ => Some(Self::Steps(steps)),
+        }
+    }
+}
+
+/// Why a step could not be undone.
+enum UndoFailure {
+    /// Something is in the way; the undo can be retried once it is cleared.
+    Conflict(String),
+    /// The step can never be undone: its item is gone, or the platform
+    /// cannot restore from the trash.
+    Gone(String),
+}
+
+/// App-wide history of file operations, newest last. Shared by every
+/// window since they all act on the same file system.
+pub(crate) struct FileJournal {
+    entries: Mutex<Vec<FileOperation>>,
+}
+
+impl FileJournal {
+    pub(crate) fn new() -> Self {
+        Self {
+            entries: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub(crate) fn record(&self, operation: FileOperation) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.push(operation);
+            if entries.len() > MAX_JOURNAL_ENTRIES {
+                let excess = entries.len() - MAX_JOURNAL_ENTRIES;
+                entries.drain(..excess);
+            }
+        }
+    }
+
+    pub(crate) fn pop(&self) -> Option<FileOperation> {
+        self.entries.lock().ok()?.pop()
+    }
+
+    /// Undo the newest entry, newest step first, calling `on_undone` for
+    /// every step that was reversed. Steps that can never be undone are
+    /// dropped so they do not block older entries; on a conflict the steps
+    /// not yet undone go back on the journal to be retried.
+    pub(crate) fn undo_newest(
+        &self,
+        mut on_undone: impl FnMut(&FileOperation),
+    ) -> Result<Option<UndoneOperation>, String> {
+        let Some(operation) = self.pop() else {
+            return Ok(None);
+        };
+        let mut steps = match operation {
+            FileOperation::Steps(steps) => steps,
+            operation => vec![operation],
+        };
+        let mut undone = None;
+        let mut dropped = None;
+        while let Some(step) = steps.pop() {
+            match undo_step(&step) {
+                Ok(result) => {
+                    on_undone(&step);
+                    undone.get_or_insert(result);
+                }
+                Err(UndoFailure::Gone(message)) => {
+                    dropped.get_or_insert(message);
+                }
+                Err(UndoFailure::Conflict(message)) => {
+                    steps.push(step);
+                    if let Some(remaining) = FileOperation::steps(steps) {
+                        self.record(remaining);
+                    }
+                    return Err(message);
+                }
+            }
+        }
+        match (undone, dropped) {
+            (Some(undone), _) => Ok(Some(undone)),
+            (None, Some(message)) => Err(message),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UndoneOperation {
+    /// `"restore"` for items brought back from the trash, `"move"` for
+    /// renames and moves that were reversed.
+    pub(crate) kind: &'static str,
+    /// Where the item is now.
+    pub(crate) path: String,
+    /// Where the item was before the undo, for moves.
+    pub(crate) from: Option<String>,
+}
+
+/// The path `trash::delete` records for `path`: canonical parent plus the
+/// entry name, so symlinks are trashed rather than their targets.
+fn trash_original_path(path: &Path) -> Option<PathBuf> {
+    let parent = fs::canonicalize(path.parent()?).ok()?;
+    Some(parent.join(path.file_name()?))
+}
+
+/// The trash can only be listed on Linux (freedesktop `.trashinfo` files)
+/// and Windows.
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+fn newest_trashed_item(original: &Path) -> Option<trash::TrashItem> {
+    trash::os_limited::list()
+        .ok()?
+        .into_iter()
+        .filter(|item| item.original_path() == original)
+        .max_by_key(|item| item.time_deleted)
+}
+
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+fn trashed_item_id(original: &Path) -> Option<OsString> {
+    newest_trashed_item(original).map(|item| item.id)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn trashed_item_id(_original: &Path) -> Option<OsString> {
+    None
+}
+
+/// Move `path` to the OS trash and describe how to bring it back.
+pub(crate) fn move_to_trash(path: &Path) -> Result<FileOperation, String> {
+    if fs::symlink_metadata(path).is_err() {
+        return Err("File not found".to_string());
+    }
+    let original = trash_original_path(path);
+    trash::delete(path).map_err(|e| format!("Unable to move file to trash: {e}"))?;
+
+    let trash_id = original.as_deref().and_then(trashed_item_id);
+
+    Ok(FileOperation::Trashed {
+        path: original.unwrap_or_else(|| path.to_path_buf()),
+        trash_id,
+    })
+}
+
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+fn restore_from_trash(path: &Path, trash_id: Option<&OsString>) -> Result<(), UndoFailure> {
+    let item = match trash_id {
+        Some(id) => trash::os_limited::list()
+            .map_err(|e| UndoFailure::Conflict(format!("Unable to read the trash: {e}")))?
+            .into_iter()
+            .find(|item| &item.id == id),
+        None => newest_trashed_item(path),
+    }
+    .ok_or_else(|| UndoFailure::Gone("The item is no longer in the trash".to_string()))?;
+
+    trash::os_limited::restore_all([item]).map_err(|e| {
+        UndoFailure::Conflict(match e {
+            trash::Error::RestoreCollision { path, .. } => format!(
+                "Unable to restore: \"{}\" already exists",
+                path.file_name().unwrap_or_default().to_string_lossy()
+            ),
+            e => format!("Unable to restore from trash: {e}"),
+        })
+    })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn restore_from_trash(_path: &Path, _trash_id: Option<&OsString>) -> Result<(), UndoFailure> {
+    Err(UndoFailure::Gone(
+        "Restoring from the trash is not supported on this platform".to_string(),
+    ))
+}
+
+fn undo_move(from: &Path, to: &Path) -> Result<(), UndoFailure> {
+    let name = |path: &Path| {
+        path.file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string()
+    };
+    if fs::symlink_metadata(to).is_err() {
+        return Err(UndoFailure::Gone(format!(
+            "\"{}\" no longer exists",
+            name(to)
+        )));
+    }
+    if fs::symlink_metadata(from).is_ok() && !is_same_entry(from, to) {
+        return Err(UndoFailure::Conflict(format!(
+            "\"{}\" already exists",
+            name(from)
+        )));
+    }
+    move_or_copy(to, from).map_err(UndoFailure::Conflict)
+}
+
+/// Reverse `operation` on disk, newest step first.
+fn undo_step(operation: &FileOperation) -> Result<UndoneOperation, UndoFailure> {
+    match operation {
+        FileOperation::Trashed { path, trash_id } => {
+            restore_from_trash(path, trash_id.as_ref())?;
+            Ok(UndoneOperation {
+                kind: "restore",
+                path: path_to_string(path),
+                from: None,
+            })
+        }
+        FileOperation::Moved { from, to } => {
+            undo_move(from, to)?;
+            Ok(UndoneOperation {
+                kind: "move",
+                path: path_to_string(from),
+                from: Some(path_to_string(to)),
+            })
+        }
+        FileOperation::Steps(steps) => {
+            let mut undone = None;
+            for step in steps.iter().rev() {
+                let result = undo_step(step)?;
+                undone.get_or_insert(result);
+            }
+            undone.ok_or_else(|| UndoFailure::Gone("Nothing to undo".to_string()))
+        }
+    }
+}
+
+/// Put back an entry that `move_to_trash` trashed, as when the operation
+/// it made room for failed.
+pub(crate) fn restore_trashed(operation: &FileOperation) -> Result<(), String> {
+    undo_step(operation)
+        .map(|_| ())
+        .map_err(|(UndoFailure::Conflict(message) | UndoFailure::Gone(message))| message)
+}
+
+/// Undo the newest journaled file operation. Returns `None` when there is
+/// nothing to undo. An undo blocked by a conflict stays in the journal so
+/// it can be retried; one that can never succeed is dropped.
+#[tauri::command]
+pub(crate) fn undo_file_operation(
+    app: tauri::AppHandle,
+) -> Result<Option<UndoneOperation>, String> {
+    app.state::<FileJournal>().undo_newest(|step| {
+        if let FileOperation::Moved { from, to } = step {
+            emit_path_renamed(&app, to, from);
+        }
+    })
+}
@@ -0,0 +1,422 @@
+use super::*;
+use crate::file_journal::{move_to_trash, restore_trashed, FileJournal, FileOperation};
+
+/// What to do when the destination of a file operation already exists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ConflictPolicy {
+    /// Refuse the operation.
+    #[default]
+    Fail,
+    /// Pick a free name such as `notes 2.md`.
+    KeepBoth,
+    /// Move the existing destination to the trash first.
+    Replace,
+}
+
+/// Sent to every window after a file or folder moved, so open tabs and
+/// file watches can follow it. For folders, paths under `from` moved to
+/// the same relative place under `to`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PathRenamed {
+    pub(crate) from: String,
+    pub(crate) to: String,
+    pub(crate) is_dir: bool,
+}
+
+/// Where a created, renamed or moved entry ended up, and how the entry
+/// `ConflictPolicy::Replace` moved out of its way was trashed.
+#[derive(Debug)]
+pub(crate) struct Placement {
+    pub(crate) path: PathBuf,
+    pub(crate) replaced: Option<FileOperation>,
+}
+
+fn canonical_root(root: &Path) -> Result<PathBuf, String> {
+    if !root.is_dir() {
+        return Err("Selected path is not a folder".to_string());
+    }
+    fs::canonicalize(root).map_err(|e| format!("Unable to resolve project folder: {e}"))
+}
+
+/// Require an existing file or folder to be strictly inside the project.
+/// Symlinks are judged by where they live, not where they point.
+fn ensure_entry_in_root(root: &Path, path: &Path) -> Result<(), String> {
+    if fs::symlink_metadata(path).is_err() {
+        return Err("File not found".to_string());
+    }
+    let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+        return Err("Path is outside the project root".to_string());
+    };
+    let resolved = fs::canonicalize(parent)
+        .map_err(|e| format!("Unable to resolve path: {e}"))?
+        .join(name);
+    if resolved == root || !resolved.starts_with(root) {
+        return Err("Path is outside the project root".to_string());
+    }
+    Ok(())
+}
+
+/// Require a folder to be the project root or inside it.
+fn ensure_folder_in_root(root: &Path, dir: &Path) -> Result<(), String> {
+    if !dir.is_dir() {
+        return Err("Selected path is not a folder".to_string());
+    }
+    let resolved = fs::canonicalize(dir).map_err(|e| format!("Unable to resolve path: {e}"))?;
+    if !resolved.starts_with(root) {
+        return Err("Path is outside the project root".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+pub(crate) fn is_same_entry(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (fs::symlink_metadata(a), fs::symlink_metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn is_same_entry(a: &Path, b: &Path) -> bool {
+    matches!((fs::canonicalize(a), fs::canonicalize(b)), (Ok(a), Ok(b)) if a == b)
+}
+
+/// Accept a single path component; nested names and `..` are rejected so
+/// a name can never escape its folder.
+fn validate_name(name: &str) -> Result<&str, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Name cannot be empty".to_string());
+    }
+    if name == "." || name == ".." || name.contains(['/', '\\']) || name.contains('\0') {
+        return Err(format!("Invalid name: {name}"));
+    }
+    Ok(name)
+}
+
+/// `notes.md` becomes `notes{suffix}.md`; dotfiles and folders keep the
+/// whole name as the stem.
+fn name_with_suffix(name: &str, suffix: &str, is_dir: bool) -> String {
+    match name.rfind('.') {
+        Some(dot) if dot > 0 && !is_dir => format!("{}{suffix}{}", &name[..dot], &name[dot..]),
+        _ => format!("{name}{suffix}"),
+    }
+}
+
+fn first_free_path(dir: &Path, name: &str, is_dir: bool, base_suffix: &str) -> PathBuf {
+    let mut candidate = dir.join(name_with_suffix(name, base_suffix, is_dir));
+    let mut counter = 2;
+    while fs::symlink_metadata(&candidate).is_ok() {
+        let suffix = if base_suffix.is_empty() {
+            format!(" {counter}")
+        } else {
+            format!("{base_suffix} {counter}")
+        };
+        candidate = dir.join(name_with_suffix(name, &suffix, is_dir));
+        counter += 1;
+    }
+    candidate
+}
+
+/// Apply `policy` to a destination that may already exist and return the
+/// path to use. A destination that is `source` itself, as with a
+/// case-only rename on a case-insensitive file system, is not a conflict.
+fn resolve_destination(
+    dir: &Path,
+    name: &str,
+    is_dir: bool,
+    source: Option<&Path>,
+    policy: ConflictPolicy,
+) -> Result<Placement, String> {
+    let destination = dir.join(name);
+    if fs::symlink_metadata(&destination).is_err()
+        || source.is_some_and(|source| is_same_entry(source, &destination))
+    {
+        return Ok(Placement {
+            path: destination,
+            replaced: None,
+        });
+    }
+    match policy {
+        ConflictPolicy::Fail => Err(format!("\"{name}\" already exists")),
+        ConflictPolicy::KeepBoth => Ok(Placement {
+            path: first_free_path(dir, name, is_dir, ""),
+            replaced: None,
+        }),
+        ConflictPolicy::Replace => {
+            let trashed = move_to_trash(&destination)
+                .map_err(|e| format!("Unable to move \"{name}\" to trash: {e}"))?;
+            Ok(Placement {
+                path: destination,
+                replaced: Some(trashed),
+            })
+        }
+    }
+}
+
+/// Run `operation` to create the entry at `placement.path`. When it fails,
+/// the entry `ConflictPolicy::Replace` trashed for it is put back.
+fn place(
+    placement: Placement,
+    operation: impl FnOnce(&Path) -> Result<(), String>,
+) -> Result<Placement, String> {
+    let Err(message) = operation(&placement.path) else {
+        return Ok(placement);
+    };
+    match placement.replaced.as_ref().map(restore_trashed) {
+        Some(Err(restore_error)) => Err(format!(
+            "{message}. The replaced item is still in the trash: {restore_error}"
+        )),
+        _ => Err(message),
+    }
+}
+
+fn copy_recursively(from: &Path, to: &Path) -> Result<(), String> {
+    let metadata = fs::symlink_metadata(from).map_err(|e| format!("Unable to copy: {e}"))?;
+    if metadata.is_dir() {
+        fs::create_dir(to).map_err(|e| format!("Unable to create folder: {e}"))?;
+        let entries = fs::read_dir(from).map_err(|e| format!("Unable to read folder: {e}"))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Unable to read folder: {e}"))?;
+            copy_recursively(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(from, to)
+            .map(|_| ())
+            .map_err(|e| format!("Unable to copy file: {e}"))
+    }
+}
+
+pub(crate) fn move_or_copy(from: &Path, to: &Path) -> Result<(), String> {
+    match fs::rename(from, to) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+            copy_recursively(from, to)?;
+            if from.is_dir() {
+                fs::remove_dir_all(from)
+            } else {
+                fs::remove_file(from)
+            }
+            .map_err(|e| format!("Unable to remove original after copy: {e}"))
+        }
+        Err(e) => Err(format!("Unable to move: {e}")),
+    }
+}
+
+/// Create an empty file named `name` in `dir`.
+pub(crate) fn create_file_in_root(
+    root: &Path,
+    dir: &Path,
+    name: &str,
+    policy: ConflictPolicy,
+) -> Result<Placement, String> {
+    let root = canonical_root(root)?;
+    ensure_folder_in_root(&root, dir)?;
+    let name = validate_name(name)?;
+    let placement = resolve_destination(dir, name, false, None, policy)?;
+    place(placement, |path| {
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .map(|_| ())
+            .map_err(|e| format!("Unable to create file: {e}"))
+    })
+}
+
+/// Create a folder named `name` in `dir`.
+pub(crate) fn create_folder_in_root(
+    root: &Path,
+    dir: &Path,
+    name: &str,
+    policy: ConflictPolicy,
+) -> Result<Placement, String> {
+    let root = canonical_root(root)?;
+    ensure_folder_in_root(&root, dir)?;
+    let name = validate_name(name)?;
+    let placement = resolve_destination(dir, name, true, None, policy)?;
+    place(placement, |path| {
+        fs::create_dir(path).map_err(|e| format!("Unable to create folder: {e}"))
+    })
+}
+
+/// Give `path` a new name in the same folder.
+pub(crate) fn rename_in_root(
+    root: &Path,
+    path: &Path,
+    new_name: &str,
+    policy: ConflictPolicy,
+) -> Result<Placement, String> {
+    let root = canonical_root(root)?;
+    ensure_entry_in_root(&root, path)?;
+    let new_name = validate_name(new_name)?;
+    if path.file_name() == Some(std::ffi::OsStr::new(new_name)) {
+        return Ok(Placement {
+            path: path.to_path_buf(),
+            replaced: None,
+        });
+    }
+    let parent = path.parent().unwrap_or(&root);
+    let placement = resolve_destination(parent, new_name, path.is_dir(), Some(path), policy)?;
+    place(placement, |destination| {
+        fs::rename(path, destination).map_err(|e| format!("Unable to rename: {e}"))
+    })
+}
+
+/// Move `path` into `target_dir`, keeping its name.
+pub(crate) fn move_in_root(
+    root: &Path,
+    path: &Path,
+    target_dir: &Path,
+    policy: ConflictPolicy,
+) -> Result<Placement, String> {
+    let root = canonical_root(root)?;
+    ensure_entry_in_root(&root, path)?;
+    ensure_folder_in_root(&root, target_dir)?;
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    if path
+        .parent()
+        .is_some_and(|parent| is_same_entry(parent, target_dir))
+    {
+        return Ok(Placement {
+            path: path.to_path_buf(),
+            replaced: None,
+        });
+    }
+    if path.is_dir()
+        && fs::canonicalize(target_dir)
+            .ok()
+            .zip(fs::canonicalize(path).ok())
+            .is_some_and(|(target, source)| target.starts_with(source))
+    {
+        return Err("Cannot move a folder into itself".to_string());
+    }
+
+    let placement = resolve_destination(target_dir, &name, path.is_dir(), None, policy)?;
+    place(placement, |destination| move_or_copy(path, destination))
+}
+
+/// Copy `path` next to itself as `name copy`, `name copy 2`, ...
+pub(crate) fn duplicate_in_root(root: &Path, path: &Path) -> Result<PathBuf, String> {
+    let root = canonical_root(root)?;
+    ensure_entry_in_root(&root, path)?;
+    let parent = path.parent().unwrap_or(&root);
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let destination = first_free_path(parent, &name, path.is_dir(), " copy");
+    copy_recursively(path, &destination)?;
+    Ok(destination)
+}
+
+pub(crate) fn emit_path_renamed(app: &tauri::AppHandle, from: &Path, to: &Path) {
+    if from == to {
+        return;
+    }
+    let payload = PathRenamed {
+        from: path_to_string(from),
+        to: path_to_string(to),
+        is_dir: to.is_dir(),
+    };
+    for label in app.webview_windows().keys() {
+        emit_to_window(app, label, EVENT_PATH_RENAMED, payload.clone());
+    }
+}
+
+/// Journal a completed operation for undo, together with the trashing of
+/// the entry it replaced, and tell windows about a rename or move.
+fn record_placement(app: &tauri::AppHandle, from: Option<&Path>, placement: Placement) -> String {
+    let mut steps: Vec<FileOperation> = placement.replaced.into_iter().collect();
+    if let Some(from) = from.filter(|from| *from != placement.path) {
+        steps.push(FileOperation::Moved {
+            from: from.to_path_buf(),
+            to: placement.path.clone(),
+        });
+        emit_path_renamed(app, from, &placement.path);
+    }
+    if let Some(operation) = FileOperation::steps(steps) {
+        app.state::<FileJournal>().record(operation);
+    }
+    path_to_string(&placement.path)
+}
+
+#[tauri::command]
+pub(crate) fn create_file(
+    app: tauri::AppHandle,
+    root: String,
+    dir: String,
+    name: String,
+    on_conflict: Option<ConflictPolicy>,
+) -> Result<String, String> {
+    create_file_in_root(
+        Path::new(&root),
+        Path::new(&dir),
+        &name,
+        on_conflict.unwrap_or_default(),
+    )
+    .map(|placement| record_placement(&app, None, placement))
+}
+
+#[tauri::command]
+pub(crate) fn create_folder(
+    app: tauri::AppHandle,
+    root: String,
+    dir: String,
+    name: String,
+    on_conflict: Option<ConflictPolicy>,
+) -> Result<String, String> {
+    create_folder_in_root(
+        Path::new(&root),
+        Path::new(&dir),
+        &name,
+        on_conflict.unwrap_or_default(),
+    )
+    .map(|placement| record_placement(&app, None, placement))
+}
+
+/// Rename a file or folder within its folder. Returns the new path and
+/// notifies every window with `PathRenamed`.
+#[tauri::command]
+pub(crate) fn rename_path(
+    app: tauri::AppHandle,
+    root: String,
+    path: String,
+    new_name: String,
+    on_conflict: Option<ConflictPolicy>,
+) -> Result<String, String> {
+    let original = PathBuf::from(&path);
+    let placement = rename_in_root(
+        Path::new(&root),
+        &original,
+        &new_name,
+        on_conflict.unwrap_or_default(),
+    )?;
+    Ok(record_placement(&app, Some(&original), placement))
+}
+
+/// Move a file or folder into `target_dir`, e.g. for sidebar drag and
+/// drop. Returns the new path and notifies every window with `PathRenamed`.
+#[tauri::command]
+pub(crate) fn move_path(
+    app: tauri::AppHandle,
+    root: String,
+    path: String,
+    target_dir: String,
+    on_conflict: Option<ConflictPolicy>,
+) -> Result<String, String> {
+    let original = PathBuf::from(&path);
+    let placement = move_in_root(
+        Path::new(&root),
+        &original,
+        Path::new(&target_dir),
+        on_conflict.unwrap_or_default(),
+    )?;
+    Ok(record_placement(&app, Some(&original), placement))
+}
+
+#[tauri::command]
+pub(crate) fn duplicate_path(root: String, path: String) -> Result<String, String> {
+    duplicate_in_root(Path::new(&root), Path::new(&path)).map(|path| path_to_string(&path))
+}
@@ -1,20 +1,71 @@
 use super::*;
+use crate::file_journal::{move_to_trash, FileJournal};
+use crate::json_format::{format_json_document, format_json_lines, JsonStyle};
+use crate::languages::detect_language;
+use crate::large_file::{is_large_file, large_file_preview, LargeFileInfo, LineIndexCache};
+use crate::local_history::{HistorySource, LocalHistory};
+use crate::markdown_table::format_markdown_tables;
+use crate::task_list::emit_task_progress;
+use crate::text_encoding::{decode_text, encode_text, encoding_for_label, DecodedText};
+use crate::yaml_layout::normalize_yaml_layout;
+use encoding_rs::{Encoding, UTF_8};
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct ProjectEntry {
+pub(crate) struct FilePayload {
     pub(crate) path: String,
-    pub(crate) rel_path: String,
-    pub(crate) is_dir: bool,
+    pub(crate) content: String,
+    pub(crate) kind: String,
+    /// Language id from name, extension, shebang or modeline, e.g.
+    /// `"python"`, `"dockerfile"` or `"plaintext"`.
+    pub(crate) language: String,
+    pub(crate) writable: bool,
+    /// Dominant line ending on disk: `"lf"`, `"crlf"` or `"mixed"`.
+    pub(crate) line_ending: String,
+    /// Whether the file started with a byte order mark, which is stripped
+    /// from `content`.
+    pub(crate) has_bom: bool,
+    /// Encoding `content` was decoded from, e.g. `"UTF-8"`, `"windows-1252"`
+    /// or `"UTF-16LE"`. Saves re-encode to it.
+    pub(crate) encoding: String,
+    /// Set when the file is above the large-file threshold. `content` then
+    /// only holds the first lines, the file is read-only, and the rest is
+    /// served by `read_text_range`.
+    pub(crate) large_file: Option<LargeFileInfo>,
+    /// Opaque mtime+hash token; pass it back to `write_text_file` as
+    /// `expected_version` to refuse overwriting an external change.
+    pub(crate) version: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct FilePayload {
+pub(crate) struct WriteTextFileResult {
+    pub(crate) version: String,
+}
+
+/// Both sides of a save that was refused because the file changed on disk
+/// since `expected_version` was read.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WriteConflict {
+    pub(crate) message: String,
     pub(crate) path: String,
-    pub(crate) content: String,
-    pub(crate) kind: String,
-    pub(crate) writable: bool,
+    pub(crate) expected_version: String,
+    pub(crate) current_version: String,
+    /// The content we tried to save.
+    pub(crate) ours: String,
+    /// The content currently on disk.
+    pub(crate) theirs: String,
+}
+
+/// Error returned by `write_text_file`. Serialized with a `kind` tag so the
+/// frontend can tell a conflict apart from an ordinary failure; both carry a
+/// human-readable `message`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum WriteFileError {
+    Io { message: String },
+    Conflict(Box<WriteConflict>),
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -23,27 +74,146 @@ pub(crate) struct FormatStructuredTextResult {
     pub(crate) formatted: String,
     pub(crate) detected_kind: Option<String>,
     pub(crate) changed: bool,
+    /// 1-based lines of a JSONL file that are not valid JSON.
+    pub(crate) invalid_lines: Vec<usize>,
+}
+
+/// Whether `formatted` differs from `original` only in whitespace, so no
+/// comment or value text was dropped or rewritten.
+fn only_whitespace_changed(original: &str, formatted: &str) -> bool {
+    let non_whitespace = |text: &str| {
+        text.chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+    };
+    non_whitespace(original) == non_whitespace(formatted)
 }
 
-fn format_json(content: &str) -> Option<String> {
-    let value: serde_json::Value = serde_json::from_str(content).ok()?;
-    serde_json::to_string_pretty(&value).ok()
+/// Every document of a YAML stream, or `None` if any fails to parse.
+fn yaml_documents(content: &str) -> Option<Vec<serde_yml::Value>> {
+    serde_yml::Deserializer::from_str(content)
+        .map(|document| serde_yml::Value::deserialize(document).ok())
+        .collect()
 }
 
+/// Re-indent block YAML to two spaces per level and tidy spacing, keeping
+/// comments, quoting and flow collections as written. Refuses (`None`) when
+/// the result would parse differently or lose anything but whitespace.
 fn format_yaml(content: &str) -> Option<String> {
-    let value: serde_yml::Value = serde_yml::from_str(content).ok()?;
-    match &value {
-        serde_yml::Value::Mapping(_) | serde_yml::Value::Sequence(_) => {}
-        _ => return None,
+    let documents = yaml_documents(content)?;
+    let is_collection = |value: &serde_yml::Value| {
+        matches!(
+            value,
+            serde_yml::Value::Mapping(_) | serde_yml::Value::Sequence(_)
+        )
+    };
+    // Empty documents between `---` markers are fine; scalars are not YAML
+    // worth formatting.
+    if !documents.iter().any(is_collection)
+        || !documents
+            .iter()
+            .all(|value| value.is_null() || is_collection(value))
+    {
+        return None;
     }
-    let formatted = serde_yml::to_string(&value).ok()?;
-    Some(formatted.trim_end_matches('\n').to_string())
+    let formatted = normalize_yaml_layout(content)?;
+    (yaml_documents(&formatted)? == documents && only_whitespace_changed(content, &formatted))
+        .then(|| formatted.trim_end_matches('\n').to_string())
 }
 
+/// Trim indentation inside the whitespace and comment lines before a TOML
+/// key or table header, keeping at most one blank line in a row.
+fn normalize_toml_prefix(prefix: &str) -> String {
+    // Every line but the last ends in a newline; the last is the
+    // indentation in front of the key itself.
+    let mut lines: Vec<&str> = prefix.split('\n').map(str::trim).collect();
+    lines.pop();
+    let mut out = String::new();
+    let mut previous_blank = false;
+    for line in lines {
+        if line.is_empty() && previous_blank {
+            continue;
+        }
+        previous_blank = line.is_empty();
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// A trailing comment after a value or header, separated by one space.
+fn normalize_toml_suffix(suffix: &str) -> String {
+    let comment = suffix.trim();
+    if comment.is_empty() {
+        String::new()
+    } else {
+        format!(" {comment}")
+    }
+}
+
+fn normalize_toml_decor(decor: &mut toml_edit::Decor, header: bool) {
+    let prefix = decor
+        .prefix()
+        .and_then(|raw| raw.as_str())
+        .map(normalize_toml_prefix)
+        .unwrap_or_default();
+    let prefix = if header && !prefix.starts_with('\n') {
+        format!("\n{prefix}")
+    } else {
+        prefix
+    };
+    let suffix = decor
+        .suffix()
+        .and_then(|raw| raw.as_str())
+        .map(normalize_toml_suffix)
+        .unwrap_or_default();
+    decor.set_prefix(prefix);
+    decor.set_suffix(suffix);
+}
+
+fn normalize_toml_table(table: &mut toml_edit::Table) {
+    if !table.is_implicit() {
+        normalize_toml_decor(table.decor_mut(), true);
+    }
+    for (mut key, item) in table.iter_mut() {
+        match item {
+            toml_edit::Item::Value(value) => {
+                let decor = key.leaf_decor_mut();
+                let prefix = decor
+                    .prefix()
+                    .and_then(|raw| raw.as_str())
+                    .map(normalize_toml_prefix)
+                    .unwrap_or_default();
+                decor.set_prefix(prefix);
+                decor.set_suffix(" ");
+                let suffix = value
+                    .decor()
+                    .suffix()
+                    .and_then(|raw| raw.as_str())
+                    .map(normalize_toml_suffix)
+                    .unwrap_or_default();
+                value.decor_mut().set_prefix(" ");
+                value.decor_mut().set_suffix(suffix);
+            }
+            toml_edit::Item::Table(table) if !table.is_dotted() => normalize_toml_table(table),
+            toml_edit::Item::ArrayOfTables(tables) => {
+                tables.iter_mut().for_each(normalize_toml_table);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Normalize key spacing, indentation and blank lines around TOML entries
+/// through `toml_edit`, which keeps comments, key order and value layout.
 fn format_toml(content: &str) -> Option<String> {
-    let value: toml::Value = toml::from_str(content).ok()?;
-    let formatted = toml::to_string_pretty(&value).ok()?;
-    Some(formatted.trim_end_matches('\n').to_string())
+    let mut document: toml_edit::DocumentMut = content.parse().ok()?;
+    normalize_toml_table(document.as_table_mut());
+    let formatted = document.to_string();
+    let formatted = formatted.trim_start_matches('\n').trim_end_matches('\n');
+    let same_values = toml::from_str::<toml::Value>(formatted).ok()?
+        == toml::from_str::<toml::Value>(content).ok()?;
+    (same_values && only_whitespace_changed(content, formatted)).then(|| formatted.to_string())
 }
 
 fn format_xml(content: &str) -> Option<String> {
@@ -73,11 +243,13 @@ fn format_xml(content: &str) -> Option<String> {
 }
 
 fn format_csv(content: &str) -> Option<String> {
-    let delimiter = if content.contains('\t') { b'\t' } else { b',' };
+    let dialect = crate::table_view::detect_dialect(content, Path::new(""));
+    let delimiter = dialect.delimiter;
 
     let mut reader = csv::ReaderBuilder::new()
         .has_headers(false)
         .delimiter(delimiter)
+        .quote(dialect.quote)
         .flexible(true)
         .from_reader(content.as_bytes());
 
@@ -103,7 +275,7 @@ fn format_csv(content: &str) -> Option<String> {
         }
     }
 
-    let sep = if delimiter == b'\t' { "\t" } else { "," };
+    let sep = (delimiter as char).to_string();
     let formatted: Vec<String> = rows
         .iter()
         .map(|row| {
@@ -119,177 +291,374 @@ fn format_csv(content: &str) -> Option<String> {
                     }
                 })
                 .collect();
-            padded.join(sep)
+            padded.join(&sep)
         })
         .collect();
 
     Some(formatted.join("\n"))
 }
 
+/// A successful format: the text, the kind that parsed and, for JSONL,
+/// the 1-based numbers of lines that are not valid JSON.
+struct StructuredFormat {
+    formatted: String,
+    kind: &'static str,
+    invalid_lines: Vec<usize>,
+}
+
 fn format_structured(
     content: &str,
     preferred_kind: Option<&str>,
-) -> (Option<String>, Option<String>) {
+    style: JsonStyle,
+) -> Option<StructuredFormat> {
     let preferred = preferred_kind.map(|value| value.trim().to_ascii_lowercase());
-    let candidates: &[&str] = match preferred.as_deref() {
-        Some("json") => &["json", "yaml"],
+    let candidates: &[&'static str] = match preferred.as_deref() {
+        // `tsconfig.json` and VS Code settings are JSONC in `.json` files.
+        Some("json") => &["json", "jsonc", "yaml"],
+        Some("jsonc") => &["jsonc"],
+        Some("json5") => &["json5"],
+        Some("jsonl") => &["jsonl"],
         Some("yaml") => &["yaml", "json"],
         Some("toml") => &["toml"],
         Some("xml") => &["xml"],
         Some("csv") => &["csv"],
+        Some("markdown" | "md") => &["markdown"],
         _ => &["json", "yaml"],
     };
 
-    for candidate in candidates {
-        let formatted = match *candidate {
-            "json" => format_json(content),
+    candidates.iter().find_map(|&kind| {
+        let mut invalid_lines = Vec::new();
+        let formatted = match kind {
+            "json" => format_json_document(content, style, true),
+            "jsonc" | "json5" => format_json_document(content, style, false),
+            "jsonl" => format_json_lines(content, style).map(|(formatted, invalid)| {
+                invalid_lines = invalid;
+                formatted
+            }),
+            // Sorting keys and minifying only apply to JSON.
+            _ if style != JsonStyle::Pretty => None,
             "yaml" => format_yaml(content),
             "toml" => format_toml(content),
             "xml" => format_xml(content),
             "csv" => format_csv(content),
+            "markdown" => format_markdown_tables(content),
             _ => None,
-        };
+        }?;
+        Some(StructuredFormat {
+            formatted,
+            kind,
+            invalid_lines,
+        })
+    })
+}
 
-        if let Some(formatted) = formatted {
-            return (Some(formatted), Some((*candidate).to_string()));
-        }
+const UTF8_BOM: &str = "\u{feff}";
+const LINE_ENDING_LF: &str = "lf";
+const LINE_ENDING_CRLF: &str = "crlf";
+const LINE_ENDING_MIXED: &str = "mixed";
+
+/// Classify the line endings used in `content`. Files without any line
+/// break count as LF; files mixing both styles are reported as mixed so a
+/// save leaves them exactly as the editor hands them back.
+pub(crate) fn detect_line_ending(content: &str) -> &'static str {
+    let crlf = content.matches("\r\n").count();
+    let lf = content.matches('\n').count() - crlf;
+    match (lf, crlf) {
+        (_, 0) => LINE_ENDING_LF,
+        (0, _) => LINE_ENDING_CRLF,
+        _ => LINE_ENDING_MIXED,
     }
-
-    (None, None)
 }
 
-#[tauri::command]
-pub(crate) fn list_project_entries(
-    root: String,
-    show_hidden: bool,
-) -> Result<Vec<ProjectEntry>, String> {
-    let root_path = PathBuf::from(root);
-
-    if !root_path.is_dir() {
-        return Err("Selected path is not a folder".to_string());
+/// Convert `content` to the given line-ending style. Mixed or unknown
+/// styles leave the content untouched.
+pub(crate) fn apply_line_ending(content: &str, line_ending: &str) -> String {
+    match line_ending {
+        LINE_ENDING_LF => content.replace("\r\n", "\n"),
+        LINE_ENDING_CRLF => content.replace("\r\n", "\n").replace('\n', "\r\n"),
+        _ => content.to_string(),
     }
+}
 
-    let mut entries = Vec::new();
-    let mut all_dirs: Vec<PathBuf> = Vec::new();
-
-    for entry in WalkDir::new(&root_path)
-        .follow_links(false)
-        .into_iter()
-        .filter_entry(|e| should_traverse_with_hidden(e, show_hidden))
+/// Replace `path` with `bytes` by writing a sibling temp file and renaming it
+/// over the original, so a crash mid-write never leaves a truncated file.
+///
+/// Symlinks are followed so the link itself stays in place and the real
+/// target is replaced. The target's permission bits are carried over, and a
+/// read-only target is refused just like a plain write would be.
+pub(crate) fn write_file_atomically(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let target = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let existing = fs::metadata(&target).ok();
+    if existing
+        .as_ref()
+        .is_some_and(|metadata| metadata.permissions().readonly())
     {
-        let entry = match entry {
-            Ok(item) => item,
-            Err(_) => continue,
-        };
-
-        let path = entry.path();
-
-        if path.is_dir() {
-            if path != root_path {
-                all_dirs.push(path.to_path_buf());
-            }
-            continue;
-        }
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            "file is read-only",
+        ));
+    }
 
-        let visible = is_text_like(path) || (show_hidden && is_dotfile_config(path));
-        if !visible {
-            continue;
-        }
-        if !show_hidden {
-            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                if name.starts_with('.') {
-                    continue;
-                }
-            }
+    let dir = target.parent().unwrap_or_else(|| Path::new("."));
+    let name = target.file_name().unwrap_or_default().to_string_lossy();
+    let temp_path = dir.join(format!(
+        ".{name}.teex-{}-{}.tmp",
+        std::process::id(),
+        NEXT_TEMP_FILE_ID.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let result = (|| {
+        let mut file = fs::File::create(&temp_path)?;
+        std::io::Write::write_all(&mut file, bytes)?;
+        file.sync_all()?;
+        if let Some(metadata) = &existing {
+            fs::set_permissions(&temp_path, metadata.permissions())?;
         }
+        fs::rename(&temp_path, &target)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
 
-        let relative = match path.strip_prefix(&root_path) {
-            Ok(rel) => rel,
-            Err(_) => continue,
-        };
+fn content_hash(bytes: &[u8]) -> u64 {
+    use std::hash::{DefaultHasher, Hasher};
 
-        entries.push(ProjectEntry {
-            path: path_to_string(path),
-            rel_path: relative.to_string_lossy().to_string(),
-            is_dir: false,
-        });
-    }
+    let mut hasher = DefaultHasher::new();
+    hasher.write(bytes);
+    hasher.finish()
+}
 
-    // Include directories that have no visible file descendants
-    for dir in all_dirs {
-        let dir_prefix = format!("{}/", path_to_string(&dir));
-        let has_file_descendants = entries.iter().any(|f| f.path.starts_with(&dir_prefix));
-        if !has_file_descendants {
-            let relative = match dir.strip_prefix(&root_path) {
-                Ok(rel) => rel,
-                Err(_) => continue,
-            };
-            entries.push(ProjectEntry {
-                path: path_to_string(&dir),
-                rel_path: relative.to_string_lossy().to_string(),
-                is_dir: true,
-            });
-        }
-    }
+pub(crate) fn file_mtime_nanos(metadata: &fs::Metadata) -> u128 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or(0)
+}
 
-    entries.sort_by(|a, b| a.rel_path.cmp(&b.rel_path));
+/// Build the version token for a file's raw bytes and metadata.
+pub(crate) fn file_version(bytes: &[u8], metadata: &fs::Metadata) -> String {
+    format!(
+        "{:x}-{:016x}",
+        file_mtime_nanos(metadata),
+        content_hash(bytes)
+    )
+}
 
-    Ok(entries)
+/// Two tokens describe the same content when their hashes agree, even if
+/// the file was touched in between.
+pub(crate) fn same_file_version(a: &str, b: &str) -> bool {
+    a == b
+        || matches!((a.split_once('-'), b.split_once('-')), (Some((_, x)), Some((_, y))) if x == y)
 }
 
-#[tauri::command]
-pub(crate) fn read_text_file(path: String) -> Result<FilePayload, String> {
+pub(crate) fn read_text_payload(
+    cache: &LineIndexCache,
+    path: String,
+    forced: Option<&'static Encoding>,
+) -> Result<FilePayload, String> {
     let path_buf = PathBuf::from(&path);
     if !path_buf.is_file() {
         return Err("File was not found".to_string());
     }
 
-    let content = fs::read_to_string(&path_buf)
-        .map_err(|e| format!("Unable to read file as UTF-8 text: {e}"))?;
-
     let metadata =
         fs::metadata(&path_buf).map_err(|e| format!("Unable to read file metadata: {e}"))?;
+    if is_large_file(&metadata) {
+        if forced.is_some() {
+            return Err("Reopening large files with another encoding is not supported".to_string());
+        }
+        let (info, preview, encoding) = large_file_preview(cache, &path_buf)?;
+        let language = detect_language(&path_buf, &preview);
+        return Ok(FilePayload {
+            path,
+            line_ending: detect_line_ending(&preview).to_string(),
+            content: preview,
+            kind: language.kind.to_string(),
+            language: language.id.to_string(),
+            writable: false,
+            has_bom: false,
+            encoding: encoding.name().to_string(),
+            large_file: Some(info),
+            version: format!("{:x}-s{:x}", file_mtime_nanos(&metadata), metadata.len()),
+        });
+    }
+
+    let bytes = fs::read(&path_buf).map_err(|e| format!("Unable to read file: {e}"))?;
+    let version = file_version(&bytes, &metadata);
+    let DecodedText {
+        content,
+        encoding,
+        has_bom,
+    } = decode_text(&bytes, forced)?;
+    let language = detect_language(&path_buf, &content);
 
     Ok(FilePayload {
         path,
+        line_ending: detect_line_ending(&content).to_string(),
         content,
-        kind: file_kind(&path_buf).to_string(),
+        kind: language.kind.to_string(),
+        language: language.id.to_string(),
         writable: !metadata.permissions().readonly(),
+        has_bom,
+        encoding: encoding.name().to_string(),
+        large_file: None,
+        version,
     })
 }
 
 #[tauri::command]
-pub(crate) fn write_text_file(path: String, content: String) -> Result<(), String> {
-    fs::write(path, content).map_err(|e| format!("Unable to write file: {e}"))
+pub(crate) fn read_text_file(
+    cache: tauri::State<LineIndexCache>,
+    path: String,
+) -> Result<FilePayload, String> {
+    read_text_payload(&cache, path, None)
+}
+
+/// Re-read `path` decoding it as `encoding` instead of the detected one.
+/// Pass the returned `encoding` to `write_text_file` to save it back the
+/// same way.
+#[tauri::command]
+pub(crate) fn reopen_with_encoding(
+    cache: tauri::State<LineIndexCache>,
+    path: String,
+    encoding: String,
+) -> Result<FilePayload, String> {
+    read_text_payload(&cache, path, Some(encoding_for_label(&encoding)?))
 }
 
+/// Save `content` to `path` atomically.
+///
+/// `line_ending`, `has_bom` and `encoding` restore the style recorded in
+/// `FilePayload`. When omitted, the style of the file currently on disk is
+/// kept, so an editor that normalizes to LF does not rewrite a CRLF, BOM or
+/// legacy-encoded file. New files default to UTF-8 without a BOM.
+///
+/// With `expected_version`, the write only happens if the file on disk
+/// still matches that token; otherwise a `WriteFileError::Conflict` with
+/// both versions of the content is returned and nothing is written.
+///
+/// The on-disk content before the write and the saved result are both
+/// recorded in local history.
 #[tauri::command]
-pub(crate) fn trash_file(path: String) -> Result<(), String> {
+pub(crate) fn write_text_file(
+    app: tauri::AppHandle,
+    path: String,
+    content: String,
+    line_ending: Option<String>,
+    has_bom: Option<bool>,
+    encoding: Option<String>,
+    expected_version: Option<String>,
+) -> Result<WriteTextFileResult, WriteFileError> {
+    let history = app.state::<LocalHistory>();
+    let path_buf = PathBuf::from(&path);
+    history.record_file(&path_buf, HistorySource::BeforeSave);
+    let result = save_text_file(
+        path,
+        content,
+        line_ending,
+        has_bom,
+        encoding,
+        expected_version,
+    )?;
+    history.record_file(&path_buf, HistorySource::Save);
+    emit_task_progress(&app, &path_buf);
+    Ok(result)
+}
+
+/// The write behind `write_text_file`, without local history.
+pub(crate) fn save_text_file(
+    path: String,
+    content: String,
+    line_ending: Option<String>,
+    has_bom: Option<bool>,
+    encoding: Option<String>,
+    expected_version: Option<String>,
+) -> Result<WriteTextFileResult, WriteFileError> {
+    let io_error = |message: String| WriteFileError::Io { message };
     let path_buf = PathBuf::from(&path);
-    if !path_buf.exists() {
-        return Err("File not found".to_string());
+    let on_disk_bytes = fs::read(&path_buf).ok();
+    let on_disk = on_disk_bytes
+        .as_deref()
+        .and_then(|bytes| decode_text(bytes, None).ok());
+
+    if let (Some(expected), Some(bytes)) = (&expected_version, &on_disk_bytes) {
+        let metadata = fs::metadata(&path_buf)
+            .map_err(|e| io_error(format!("Unable to read file metadata: {e}")))?;
+        let current = file_version(bytes, &metadata);
+        if !same_file_version(expected, &current) {
+            let theirs = match &on_disk {
+                Some(decoded) => decoded.content.clone(),
+                None => String::from_utf8_lossy(bytes).into_owned(),
+            };
+            return Err(WriteFileError::Conflict(Box::new(WriteConflict {
+                message: "File was changed outside Teex since it was opened".to_string(),
+                path,
+                expected_version: expected.clone(),
+                current_version: current,
+                ours: content,
+                theirs,
+            })));
+        }
     }
-    trash::delete(&path_buf).map_err(|e| format!("Unable to move file to trash: {e}"))
+
+    let line_ending = line_ending.unwrap_or_else(|| {
+        on_disk
+            .as_ref()
+            .map(|decoded| detect_line_ending(&decoded.content))
+            .unwrap_or(LINE_ENDING_MIXED)
+            .to_string()
+    });
+    let has_bom = has_bom.unwrap_or_else(|| on_disk.as_ref().is_some_and(|d| d.has_bom));
+    let encoding = match encoding {
+        Some(label) => encoding_for_label(&label).map_err(io_error)?,
+        None => on_disk.as_ref().map(|d| d.encoding).unwrap_or(UTF_8),
+    };
+
+    let body = content.strip_prefix(UTF8_BOM).unwrap_or(&content);
+    let text = apply_line_ending(body, &line_ending);
+    let output = encode_text(&text, encoding, has_bom).map_err(io_error)?;
+
+    write_file_atomically(&path_buf, &output)
+        .map_err(|e| io_error(format!("Unable to write file: {e}")))?;
+    let metadata = fs::metadata(&path_buf)
+        .map_err(|e| io_error(format!("Unable to read file metadata: {e}")))?;
+    Ok(WriteTextFileResult {
+        version: file_version(&output, &metadata),
+    })
+}
+
+#[tauri::command]
+pub(crate) fn trash_file(app: tauri::AppHandle, path: String) -> Result<(), String> {
+    let operation = move_to_trash(Path::new(&path))?;
+    app.state::<FileJournal>().record(operation);
+    Ok(())
 }
 
 #[tauri::command]
 pub(crate) fn format_structured_text(
     content: String,
     preferred_kind: Option<String>,
+    style: Option<String>,
 ) -> Result<FormatStructuredTextResult, String> {
-    let (formatted, detected_kind) = format_structured(&content, preferred_kind.as_deref());
-    let Some(formatted) = formatted else {
+    let style = JsonStyle::from_label(style.as_deref());
+    let Some(result) = format_structured(&content, preferred_kind.as_deref(), style) else {
         return Ok(FormatStructuredTextResult {
             formatted: content,
             detected_kind: None,
             changed: false,
+            invalid_lines: Vec::new(),
         });
     };
 
-    let changed = formatted != content;
+    let changed = result.formatted != content;
     Ok(FormatStructuredTextResult {
-        formatted,
-        detected_kind,
+        formatted: result.formatted,
+        detected_kind: Some(result.kind.to_string()),
         changed,
+        invalid_lines: result.invalid_lines,
     })
 }
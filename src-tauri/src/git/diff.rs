@@ -23,7 +23,10 @@ pub(crate) struct DiffHunk {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub(crate) struct FileDiff {
     pub rel_path: String,
+    pub status: String, // "added" | "removed" | "changed"
     pub hunks: Vec<DiffHunk>,
+    /// Either side is binary, so there are no hunks to show.
+    pub binary: bool,
 }
 
 /// Parse full `git diff` output into per-file structured diffs.
@@ -53,16 +56,25 @@ pub(crate) fn parse_full_unified_diff(diff_output: &str) -> Vec<FileDiff> {
             continue;
         }
 
-        // Extract rel_path from "+++ b/..." line
+        // Extract rel_path from "+++ b/..." line, or "--- a/..." for deletions
         let rel_path = section
             .lines()
             .find(|l| l.starts_with("+++ b/"))
+            .or_else(|| section.lines().find(|l| l.starts_with("--- a/")))
             .map(|l| l[6..].to_string());
 
         let Some(rel_path) = rel_path else {
             continue;
         };
 
+        let status = if section.contains("\nnew file mode") {
+            "added"
+        } else if section.contains("\ndeleted file mode") {
+            "removed"
+        } else {
+            "changed"
+        };
+
         let mut hunks: Vec<DiffHunk> = Vec::new();
         let mut current_hunk: Option<DiffHunk> = None;
 
@@ -107,7 +119,12 @@ pub(crate) fn parse_full_unified_diff(diff_output: &str) -> Vec<FileDiff> {
         }
 
         if !hunks.is_empty() {
-            files.push(FileDiff { rel_path, hunks });
+            files.push(FileDiff {
+                rel_path,
+                status: status.to_string(),
+                hunks,
+                binary: false,
+            });
         }
     }
 
@@ -188,6 +205,19 @@ fn is_tracked(git_root: &Path, rel_path: &str) -> bool {
     }
 }
 
+/// Content of `rel_path` at `HEAD`, or `None` when it is not committed.
+pub(crate) fn head_file_content(git_root: &Path, rel_path: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["show", &format!("HEAD:{rel_path}")])
+        .current_dir(git_root)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
 fn all_lines_added(file_path: &Path) -> Result<Vec<LineDiff>, String> {
     let content =
         std::fs::read_to_string(file_path).map_err(|e| format!("Unable to read file: {e}"))?;
@@ -275,7 +305,9 @@ fn file_as_all_added(git_root: &Path, rel_path: &str) -> Option<FileDiff> {
     let header = format!("@@ -0,0 +1,{} @@", lines.len());
     Some(FileDiff {
         rel_path: rel_path.to_string(),
+        status: "added".to_string(),
         hunks: vec![DiffHunk { header, lines }],
+        binary: false,
     })
 }
 
@@ -1,12 +1,15 @@
 mod diff;
 mod status;
 
+pub(crate) use diff::find_git_root;
 pub(crate) use diff::git_diff;
 pub(crate) use diff::git_diff_all;
+pub(crate) use diff::head_file_content;
+pub(crate) use diff::{DiffHunk, DiffLine, FileDiff};
 pub(crate) use status::git_status;
 
 #[cfg(test)]
-pub(crate) use diff::{parse_full_unified_diff, DiffLine};
+pub(crate) use diff::parse_full_unified_diff;
 #[cfg(test)]
 pub(crate) use diff::{parse_unified_diff, LineDiff};
 #[cfg(test)]
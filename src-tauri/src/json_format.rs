@@ -0,0 +1,563 @@
+use serde_json::Value;
+
+const INDENT: &str = "  ";
+
+/// How a JSON document is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum JsonStyle {
+    /// Two-space indentation in the original key order.
+    Pretty,
+    /// Like `Pretty`, with object keys sorted at every level.
+    SortKeys,
+    /// Everything on one line, without comments.
+    Minify,
+}
+
+impl JsonStyle {
+    /// `"sortKeys"` or `"minify"`; anything else is `Pretty`.
+    pub(crate) fn from_label(label: Option<&str>) -> Self {
+        match label {
+            Some("sortKeys") => Self::SortKeys,
+            Some("minify") => Self::Minify,
+            _ => Self::Pretty,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    Punct(u8),
+    Comment,
+    /// A string, number, literal or unquoted JSON5 key.
+    Atom,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    kind: TokenKind,
+    text: &'a str,
+    newline_before: bool,
+}
+
+fn is_delimiter(byte: u8) -> bool {
+    byte.is_ascii_whitespace() || b"{}[]:,\"'".contains(&byte)
+}
+
+fn tokenize(content: &str) -> Option<Vec<Token<'_>>> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let bytes = content.as_bytes();
+    let mut tokens = Vec::new();
+    let mut newline_before = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        let kind = match bytes[i] {
+            b'\n' => {
+                newline_before = true;
+                i += 1;
+                continue;
+            }
+            byte if byte.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = content[i..].find('\n').map_or(bytes.len(), |end| i + end);
+                TokenKind::Comment
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2 + content[i + 2..].find("*/")? + 2;
+                TokenKind::Comment
+            }
+            byte @ (b'{' | b'}' | b'[' | b']' | b':' | b',') => {
+                i += 1;
+                TokenKind::Punct(byte)
+            }
+            quote @ (b'"' | b'\'') => {
+                i += 1;
+                loop {
+                    match *bytes.get(i)? {
+                        b'\\' => i += 2,
+                        byte if byte == quote => break,
+                        _ => i += 1,
+                    }
+                }
+                i += 1;
+                TokenKind::Atom
+            }
+            _ => {
+                while i < bytes.len()
+                    && !is_delimiter(bytes[i])
+                    && !(bytes[i] == b'/' && matches!(bytes.get(i + 1), Some(b'/' | b'*')))
+                {
+                    i += 1;
+                }
+                TokenKind::Atom
+            }
+        };
+        tokens.push(Token {
+            kind,
+            text: content[start..i].trim_end(),
+            newline_before,
+        });
+        newline_before = false;
+    }
+    Some(tokens)
+}
+
+#[derive(Debug)]
+enum Node<'a> {
+    Atom(&'a str),
+    Container {
+        open: u8,
+        /// The opening bracket as it appears in the source.
+        opening: &'a str,
+        entries: Vec<Entry<'a>>,
+        /// Comments after the last entry, before the closing bracket.
+        dangling: Vec<&'a str>,
+        trailing_comma: bool,
+    },
+}
+
+#[derive(Debug)]
+struct Entry<'a> {
+    /// Comments on their own lines before the entry.
+    leading: Vec<&'a str>,
+    key: Option<&'a str>,
+    /// Block comments between the key and the value.
+    inner: Vec<&'a str>,
+    value: Node<'a>,
+    /// Comments after the value on the same line.
+    trailing: Vec<&'a str>,
+}
+
+struct Document<'a> {
+    leading: Vec<&'a str>,
+    root: Node<'a>,
+    trailing: Vec<&'a str>,
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<Token<'a>> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let token = self.peek()?;
+        self.pos += 1;
+        Some(token)
+    }
+
+    fn comments(&mut self, same_line_only: bool) -> Vec<&'a str> {
+        let mut out = Vec::new();
+        while let Some(token) = self.peek() {
+            if token.kind != TokenKind::Comment || (same_line_only && token.newline_before) {
+                break;
+            }
+            out.push(token.text);
+            self.pos += 1;
+        }
+        out
+    }
+
+    fn value(&mut self) -> Option<Node<'a>> {
+        let token = self.next()?;
+        match token.kind {
+            TokenKind::Atom => Some(Node::Atom(token.text)),
+            TokenKind::Punct(open @ (b'{' | b'[')) => self.container(open, token.text),
+            _ => None,
+        }
+    }
+
+    fn container(&mut self, open: u8, opening: &'a str) -> Option<Node<'a>> {
+        let close = if open == b'{' { b'}' } else { b']' };
+        let mut entries = Vec::new();
+        let mut trailing_comma = false;
+        loop {
+            let leading = self.comments(false);
+            if self.peek()?.kind == TokenKind::Punct(close) {
+                self.pos += 1;
+                return Some(Node::Container {
+                    open,
+                    opening,
+                    entries,
+                    dangling: leading,
+                    trailing_comma,
+                });
+            }
+            trailing_comma = false;
+
+            let (key, inner) = if open == b'{' {
+                let key = self.next().filter(|token| token.kind == TokenKind::Atom)?;
+                let mut inner = self.comments(false);
+                if self.next()?.kind != TokenKind::Punct(b':') {
+                    return None;
+                }
+                inner.extend(self.comments(false));
+                // A line comment here would swallow the value once joined
+                // onto the key's line.
+                if inner.iter().any(|comment| comment.starts_with("//")) {
+                    return None;
+                }
+                (Some(key.text), inner)
+            } else {
+                (None, Vec::new())
+            };
+            let value = self.value()?;
+            let mut trailing = self.comments(true);
+            let pending = self.comments(false);
+
+            let separator = self.next()?.kind;
+            if separator == TokenKind::Punct(b',') {
+                trailing.extend(pending);
+                trailing.extend(self.comments(true));
+                trailing_comma = true;
+                entries.push(Entry {
+                    leading,
+                    key,
+                    inner,
+                    value,
+                    trailing,
+                });
+                continue;
+            }
+            if separator != TokenKind::Punct(close) {
+                return None;
+            }
+            entries.push(Entry {
+                leading,
+                key,
+                inner,
+                value,
+                trailing,
+            });
+            return Some(Node::Container {
+                open,
+                opening,
+                entries,
+                dangling: pending,
+                trailing_comma,
+            });
+        }
+    }
+}
+
+impl<'a> Parser<'a> {
+    fn document(&mut self) -> Option<Document<'a>> {
+        let leading = self.comments(false);
+        let root = self.value()?;
+        let trailing = self.comments(false);
+        (self.pos == self.tokens.len()).then_some(Document {
+            leading,
+            root,
+            trailing,
+        })
+    }
+}
+
+fn parse_document(content: &str) -> Option<Document<'_>> {
+    Parser {
+        tokens: tokenize(content)?,
+        pos: 0,
+    }
+    .document()
+}
+
+/// Byte offset near where a JSONC or JSON5 document stops parsing, or
+/// `None` when it is valid.
+pub(crate) fn relaxed_json_error_offset(content: &str) -> Option<usize> {
+    let Some(tokens) = tokenize(content) else {
+        // An unterminated string or comment runs to the end.
+        return Some(content.len());
+    };
+    let mut parser = Parser { tokens, pos: 0 };
+    if parser.document().is_some() {
+        return None;
+    }
+    let failed = parser.pos.saturating_sub(1);
+    Some(parser.tokens.get(failed).map_or(content.len(), |token| {
+        token.text.as_ptr() as usize - content.as_ptr() as usize
+    }))
+}
+
+fn atom_value(text: &str) -> Value {
+    if let Some(inner) = text
+        .strip_prefix('\'')
+        .and_then(|text| text.strip_suffix('\''))
+    {
+        let quoted = format!("\"{}\"", inner.replace("\\'", "'").replace('"', "\\\""));
+        return serde_json::from_str(&quoted).unwrap_or_else(|_| Value::String(inner.to_string()));
+    }
+    serde_json::from_str(text).unwrap_or_else(|_| Value::String(text.to_string()))
+}
+
+fn key_value(key: &str) -> String {
+    match atom_value(key) {
+        Value::String(key) => key,
+        other => other.to_string(),
+    }
+}
+
+fn node_value(node: &Node) -> Value {
+    match node {
+        Node::Atom(text) => atom_value(text),
+        Node::Container { open, entries, .. } if *open == b'{' => Value::Object(
+            entries
+                .iter()
+                .map(|entry| {
+                    (
+                        key_value(entry.key.unwrap_or_default()),
+                        node_value(&entry.value),
+                    )
+                })
+                .collect(),
+        ),
+        Node::Container { entries, .. } => Value::Array(
+            entries
+                .iter()
+                .map(|entry| node_value(&entry.value))
+                .collect(),
+        ),
+    }
+}
+
+/// Parse a JSON, JSONC or JSON5 document into a value. JSON5 numbers
+/// serde_json cannot represent, such as hexadecimal or `Infinity`, are
+/// kept as strings.
+pub(crate) fn relaxed_json_value(content: &str) -> Option<Value> {
+    Some(node_value(&parse_document(content)?.root))
+}
+
+/// Byte offset of the node at `pointer`, given as unescaped JSON Pointer
+/// tokens. Object members point at their key. When the pointer does not
+/// resolve fully, the deepest node found is used.
+pub(crate) fn relaxed_json_pointer_offset(content: &str, pointer: &[String]) -> Option<usize> {
+    fn start<'a>(node: &Node<'a>) -> &'a str {
+        match node {
+            Node::Atom(text) => text,
+            Node::Container { opening, .. } => opening,
+        }
+    }
+
+    let document = parse_document(content)?;
+    let mut node = &document.root;
+    let mut at = start(node);
+    for token in pointer {
+        let Node::Container { open, entries, .. } = node else {
+            break;
+        };
+        let entry = if *open == b'{' {
+            entries
+                .iter()
+                .find(|entry| entry.key.is_some_and(|key| key_value(key) == *token))
+        } else {
+            token
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| entries.get(index))
+        };
+        let Some(entry) = entry else {
+            break;
+        };
+        at = entry.key.unwrap_or_else(|| start(&entry.value));
+        node = &entry.value;
+    }
+    Some(at.as_ptr() as usize - content.as_ptr() as usize)
+}
+
+fn key_name(key: Option<&str>) -> &str {
+    let key = key.unwrap_or_default();
+    key.strip_prefix(['"', '\''])
+        .and_then(|inner| inner.strip_suffix(['"', '\'']))
+        .unwrap_or(key)
+}
+
+fn ordered<'e, 'a>(entries: &'e [Entry<'a>], open: u8, sort: bool) -> Vec<&'e Entry<'a>> {
+    let mut ordered: Vec<&Entry> = entries.iter().collect();
+    if sort && open == b'{' {
+        ordered.sort_by(|a, b| key_name(a.key).cmp(key_name(b.key)));
+    }
+    ordered
+}
+
+fn close_of(open: u8) -> char {
+    if open == b'{' {
+        '}'
+    } else {
+        ']'
+    }
+}
+
+fn write_pretty(node: &Node, depth: usize, sort: bool, out: &mut String) {
+    let Node::Container {
+        open,
+        entries,
+        dangling,
+        trailing_comma,
+        ..
+    } = node
+    else {
+        if let Node::Atom(text) = node {
+            out.push_str(text);
+        }
+        return;
+    };
+    out.push(*open as char);
+    if entries.is_empty() && dangling.is_empty() {
+        out.push(close_of(*open));
+        return;
+    }
+    out.push('\n');
+    let inner_indent = INDENT.repeat(depth + 1);
+    let entries = ordered(entries, *open, sort);
+    for (index, entry) in entries.iter().enumerate() {
+        for comment in &entry.leading {
+            out.push_str(&inner_indent);
+            out.push_str(comment);
+            out.push('\n');
+        }
+        out.push_str(&inner_indent);
+        if let Some(key) = entry.key {
+            out.push_str(key);
+            out.push_str(": ");
+            for comment in &entry.inner {
+                out.push_str(comment);
+                out.push(' ');
+            }
+        }
+        write_pretty(&entry.value, depth + 1, sort, out);
+        if index + 1 < entries.len() || *trailing_comma {
+            out.push(',');
+        }
+        for comment in &entry.trailing {
+            out.push(' ');
+            out.push_str(comment);
+        }
+        out.push('\n');
+    }
+    for comment in dangling {
+        out.push_str(&inner_indent);
+        out.push_str(comment);
+        out.push('\n');
+    }
+    out.push_str(&INDENT.repeat(depth));
+    out.push(close_of(*open));
+}
+
+fn write_compact(node: &Node, sort: bool, out: &mut String) {
+    match node {
+        Node::Atom(text) => out.push_str(text),
+        Node::Container { open, entries, .. } => {
+            out.push(*open as char);
+            for (index, entry) in ordered(entries, *open, sort).into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                if let Some(key) = entry.key {
+                    out.push_str(key);
+                    out.push(':');
+                }
+                write_compact(&entry.value, sort, out);
+            }
+            out.push(close_of(*open));
+        }
+    }
+}
+
+fn comment_texts(tokens: &[Token]) -> Vec<String> {
+    let mut texts: Vec<String> = tokens
+        .iter()
+        .filter(|token| token.kind == TokenKind::Comment)
+        .map(|token| token.text.to_string())
+        .collect();
+    texts.sort();
+    texts
+}
+
+fn value_texts(tokens: &[Token], sort: bool) -> Vec<String> {
+    let mut texts: Vec<String> = tokens
+        .iter()
+        .filter(|token| token.kind != TokenKind::Comment)
+        .map(|token| token.text.to_string())
+        .collect();
+    if sort {
+        texts.sort();
+    }
+    texts
+}
+
+/// Format a JSON document, or a JSONC/JSON5 one when `strict` is false.
+/// Comments, trailing commas and the spelling of keys and values are kept,
+/// so only the layout changes. Returns `None` when the content does not
+/// parse, or when the result would lose a comment or change a value.
+pub(crate) fn format_json_document(
+    content: &str,
+    style: JsonStyle,
+    strict: bool,
+) -> Option<String> {
+    if strict {
+        serde_json::from_str::<serde_json::Value>(content).ok()?;
+    }
+    let document = parse_document(content)?;
+    let mut out = String::new();
+    if style == JsonStyle::Minify {
+        write_compact(&document.root, false, &mut out);
+        return Some(out);
+    }
+
+    for comment in &document.leading {
+        out.push_str(comment);
+        out.push('\n');
+    }
+    write_pretty(&document.root, 0, style == JsonStyle::SortKeys, &mut out);
+    for comment in &document.trailing {
+        out.push('\n');
+        out.push_str(comment);
+    }
+
+    let before = tokenize(content)?;
+    let after = tokenize(&out)?;
+    let sort = style == JsonStyle::SortKeys;
+    (comment_texts(&before) == comment_texts(&after)
+        && value_texts(&before, sort) == value_texts(&after, sort))
+    .then_some(out)
+}
+
+/// Format JSON Lines one record per line: each valid line is written
+/// compactly, with sorted keys for `SortKeys`. Invalid lines are kept as
+/// they are and their 1-based numbers returned. Returns `None` when no
+/// line is valid JSON.
+pub(crate) fn format_json_lines(content: &str, style: JsonStyle) -> Option<(String, Vec<usize>)> {
+    let sort = style == JsonStyle::SortKeys;
+    let mut lines = Vec::new();
+    let mut invalid_lines = Vec::new();
+    let mut any_valid = false;
+    for (index, line) in content.lines().enumerate() {
+        let record = line.trim();
+        if record.is_empty() {
+            lines.push(String::new());
+            continue;
+        }
+        let parsed = serde_json::from_str::<serde_json::Value>(record)
+            .ok()
+            .and_then(|_| parse_document(record));
+        match parsed {
+            Some(document) => {
+                let mut out = String::new();
+                write_compact(&document.root, sort, &mut out);
+                lines.push(out);
+                any_valid = true;
+            }
+            None => {
+                lines.push(line.to_string());
+                invalid_lines.push(index + 1);
+            }
+        }
+    }
+    any_valid.then(|| (lines.join("\n"), invalid_lines))
+}
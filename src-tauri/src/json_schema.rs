@@ -0,0 +1,319 @@
+use super::*;
+use crate::json_format::{relaxed_json_pointer_offset, relaxed_json_value};
+use crate::semantic_diff::{parse_documents, parse_structured, structured_kind};
+use crate::validation::{line_column, Diagnostic};
+use crate::yaml_layout::{yaml_document_start_lines, yaml_pointer_position};
+use globset::Glob;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
+use serde_json::{json, Value};
+
+/// Project settings file. Its `[schemas]` table maps file globs, relative
+/// to the folder containing it, to schema paths or URLs.
+pub(crate) const TEEX_PROJECT_FILE: &str = ".teex.toml";
+
+/// Comment that names a YAML file's schema, as used by the YAML language
+/// server.
+const YAML_SCHEMA_MODELINE: &str = "yaml-language-server: $schema=";
+
+/// Characters escaped when turning a path into a `file://` URI.
+const PATH_URI_SET: &AsciiSet = &CONTROLS.add(b' ').add(b'"').add(b'#').add(b'%').add(b'?');
+
+struct BundledSchema {
+    url: &'static str,
+    file_globs: &'static [&'static str],
+    source: &'static str,
+}
+
+/// Schemas shipped with the app so common config files validate offline.
+const BUNDLED_SCHEMAS: &[BundledSchema] = &[
+    BundledSchema {
+        url: "https://json.schemastore.org/package.json",
+        file_globs: &["**/package.json"],
+        source: include_str!("../schemas/package.schema.json"),
+    },
+    BundledSchema {
+        url: "https://json.schemastore.org/tsconfig.json",
+        file_globs: &["**/tsconfig.json", "**/tsconfig.*.json", "**/jsconfig.json"],
+        source: include_str!("../schemas/tsconfig.schema.json"),
+    },
+    BundledSchema {
+        url: "https://json.schemastore.org/github-workflow.json",
+        file_globs: &["**/.github/workflows/*.yml", "**/.github/workflows/*.yaml"],
+        source: include_str!("../schemas/github-workflow.schema.json"),
+    },
+];
+
+/// Where a document's schema comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SchemaSource {
+    Url(String),
+    File(PathBuf),
+}
+
+fn url_key(url: &str) -> &str {
+    let url = url.trim_end_matches('#');
+    url.strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url)
+}
+
+fn bundled_schema_for_url(url: &str) -> Option<&'static BundledSchema> {
+    BUNDLED_SCHEMAS
+        .iter()
+        .find(|schema| url_key(schema.url) == url_key(url))
+}
+
+fn glob_matches(pattern: &str, path: &Path) -> bool {
+    Glob::new(pattern)
+        .map(|glob| glob.compile_matcher().is_match(path))
+        .unwrap_or(false)
+}
+
+/// A schema reference as written in a document or `.teex.toml`. Relative
+/// paths are resolved against `base_dir`.
+fn schema_source(reference: &str, base_dir: &Path) -> SchemaSource {
+    let reference = reference.trim();
+    if reference.starts_with("http://") || reference.starts_with("https://") {
+        return SchemaSource::Url(reference.to_string());
+    }
+    match reference.strip_prefix("file://") {
+        Some(path) => SchemaSource::File(PathBuf::from(
+            percent_decode_str(path).decode_utf8_lossy().as_ref(),
+        )),
+        None => SchemaSource::File(base_dir.join(reference)),
+    }
+}
+
+fn yaml_modeline_schema(content: &str) -> Option<&str> {
+    content
+        .lines()
+        .map(str::trim)
+        .take_while(|line| line.is_empty() || line.starts_with('#') || line == &"---")
+        .find_map(|line| {
+            let comment = line.strip_prefix('#')?.trim();
+            comment.strip_prefix(YAML_SCHEMA_MODELINE)
+        })
+}
+
+fn project_schema(path: &Path) -> Option<SchemaSource> {
+    let settings_path = path
+        .ancestors()
+        .skip(1)
+        .map(|dir| dir.join(TEEX_PROJECT_FILE))
+        .find(|candidate| candidate.is_file())?;
+    let project_dir = settings_path.parent()?;
+    let relative = path.strip_prefix(project_dir).ok()?;
+    let settings = fs::read_to_string(&settings_path)
+        .ok()?
+        .parse::<toml_edit::DocumentMut>()
+        .ok()?;
+    // The first matching pattern wins, in the order they are written.
+    let (_, schema) = settings
+        .get("schemas")?
+        .as_table_like()?
+        .iter()
+        .find(|(pattern, _)| glob_matches(pattern, relative))?;
+    Some(schema_source(schema.as_str()?, project_dir))
+}
+
+/// The schema for the document at `path`: its own `$schema` key (or YAML
+/// modeline comment), then a `.teex.toml` mapping, then the bundled
+/// catalog.
+pub(crate) fn resolve_schema(path: &Path, content: &str, value: &Value) -> Option<SchemaSource> {
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+    let declared = value.get("$schema").and_then(Value::as_str).or_else(|| {
+        yaml_modeline_schema(content).filter(|_| structured_kind(path) == Some("yaml"))
+    });
+    if let Some(reference) = declared {
+        return Some(schema_source(reference, base_dir));
+    }
+    project_schema(path).or_else(|| {
+        BUNDLED_SCHEMAS
+            .iter()
+            .find(|schema| {
+                schema
+                    .file_globs
+                    .iter()
+                    .any(|pattern| glob_matches(pattern, path))
+            })
+            .map(|schema| SchemaSource::Url(schema.url.to_string()))
+    })
+}
+
+fn read_schema_file(path: &Path) -> Result<Value, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Unable to read schema {}: {e}", path.display()))?;
+    match structured_kind(path) {
+        Some(kind @ ("yaml" | "toml")) => parse_structured(&content, kind),
+        _ => relaxed_json_value(&content)
+            .ok_or_else(|| format!("Schema {} is not valid JSON", path.display())),
+    }
+}
+
+fn file_uri(path: &Path) -> String {
+    format!(
+        "file://{}",
+        utf8_percent_encode(&path.to_string_lossy(), PATH_URI_SET)
+    )
+}
+
+/// Resolves `$ref`s to bundled schemas and local files only, so validation
+/// never touches the network.
+struct OfflineRetriever;
+
+impl jsonschema::Retrieve for OfflineRetriever {
+    fn retrieve(
+        &self,
+        uri: &jsonschema::Uri<String>,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let uri = uri.as_str();
+        if let Some(schema) = bundled_schema_for_url(uri) {
+            return Ok(serde_json::from_str(schema.source)?);
+        }
+        match uri.strip_prefix("file://") {
+            Some(path) => {
+                let path = percent_decode_str(path).decode_utf8_lossy();
+                Ok(read_schema_file(Path::new(path.as_ref()))?)
+            }
+            None => Err(format!("{uri} is not available offline").into()),
+        }
+    }
+}
+
+fn build_validator(source: &SchemaSource) -> Result<jsonschema::Validator, String> {
+    let options = jsonschema::options().with_retriever(OfflineRetriever);
+    match source {
+        SchemaSource::Url(url) => match bundled_schema_for_url(url) {
+            Some(schema) => {
+                let schema: Value = serde_json::from_str(schema.source)
+                    .map_err(|e| format!("Invalid bundled schema: {e}"))?;
+                options
+                    .build(&schema)
+                    .map_err(|e| format!("Invalid bundled schema: {e}"))
+            }
+            // Meta-schemas such as draft-07 are built into the validator.
+            None => options
+                .build(&json!({ "$schema": url, "$ref": url }))
+                .map_err(|_| format!("Schema {url} is not available offline")),
+        },
+        SchemaSource::File(path) => {
+            let schema = read_schema_file(path)?;
+            options
+                .with_base_uri(file_uri(path))
+                .build(&schema)
+                .map_err(|e| format!("Invalid schema {}: {e}", path.display()))
+        }
+    }
+}
+
+/// Unescaped tokens of a JSON Pointer such as `/jobs/build/steps/0`.
+fn pointer_tokens(pointer: &str) -> Vec<String> {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect()
+}
+
+fn toml_pointer_offset(content: &str, pointer: &[String]) -> Option<usize> {
+    let document = toml_edit::Document::parse(content).ok()?;
+    let mut item = document.as_item();
+    let mut offset = 0;
+    for token in pointer {
+        let next = match item.as_table_like() {
+            Some(table) => table.get_key_value(token).map(|(key, item)| {
+                offset = key.span().map_or(offset, |span| span.start);
+                item
+            }),
+            None => token.parse::<usize>().ok().and_then(|index| {
+                let item = item.get(index)?;
+                offset = item.span().map_or(offset, |span| span.start);
+                Some(item)
+            }),
+        };
+        match next {
+            Some(next) => item = next,
+            None => break,
+        }
+    }
+    Some(offset)
+}
+
+/// 1-based line and column of the value at `pointer` in a document.
+fn pointer_position(content: &str, kind: &str, pointer: &[String]) -> (usize, usize) {
+    let offset = match kind {
+        "yaml" => return yaml_pointer_position(content, pointer),
+        "toml" => toml_pointer_offset(content, pointer),
+        _ => relaxed_json_pointer_offset(content, pointer),
+    };
+    line_column(content, offset.unwrap_or(0))
+}
+
+/// Every document in `content`; only YAML streams hold more than one.
+fn document_values(content: &str, kind: &str) -> Option<Vec<Value>> {
+    match kind {
+        "json" | "jsonc" | "json5" => relaxed_json_value(content).map(|value| vec![value]),
+        "yaml" | "toml" => parse_documents(content, kind).ok(),
+        _ => None,
+    }
+}
+
+/// The text of each document in a YAML stream with the line it starts on,
+/// or the whole of `content` when the stream cannot be split to match
+/// `count` documents.
+fn document_sections(content: &str, kind: &str, count: usize) -> Vec<(usize, String)> {
+    let starts = yaml_document_start_lines(content);
+    if kind != "yaml" || count < 2 || starts.len() != count {
+        return vec![(0, content.to_string())];
+    }
+    let lines: Vec<&str> = content.lines().collect();
+    starts
+        .iter()
+        .zip(starts.iter().skip(1).chain([&lines.len()]))
+        .map(|(&start, &end)| (start, lines[start..end].join("\n")))
+        .collect()
+}
+
+/// Schema violations in the document at `path`, or nothing when it has no
+/// schema or does not parse. Every document of a YAML stream is checked
+/// against the schema found for the first. A schema that cannot be loaded
+/// is reported on the first line.
+pub(crate) fn schema_diagnostics(path: &Path, content: &str, kind: &str) -> Vec<Diagnostic> {
+    let Some(values) = document_values(content, kind) else {
+        return Vec::new();
+    };
+    let Some(source) = values
+        .first()
+        .and_then(|value| resolve_schema(path, content, value))
+    else {
+        return Vec::new();
+    };
+    let validator = match build_validator(&source) {
+        Ok(validator) => validator,
+        Err(message) => return vec![Diagnostic::warning(message, 1, 1)],
+    };
+    let sections = document_sections(content, kind, values.len());
+    let mut diagnostics = Vec::new();
+    for (index, value) in values.iter().enumerate() {
+        let (first_line, section) = sections
+            .get(index)
+            .map_or((0, content), |(line, text)| (*line, text.as_str()));
+        for error in validator.iter_errors(value) {
+            let mut pointer = pointer_tokens(error.instance_path().as_str());
+            // Point at the first unexpected key rather than its object.
+            if let jsonschema::error::ValidationErrorKind::AdditionalProperties { unexpected }
+            | jsonschema::error::ValidationErrorKind::UnevaluatedProperties { unexpected } =
+                error.kind()
+            {
+                pointer.extend(unexpected.first().cloned());
+            }
+            let (line, column) = pointer_position(section, kind, &pointer);
+            diagnostics.push(Diagnostic::warning(
+                error.to_string(),
+                first_line + line,
+                column,
+            ));
+        }
+    }
+    diagnostics
+}
@@ -0,0 +1,509 @@
+use super::*;
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Lines at the start and end of a file searched for Vim modelines, the
+/// same window Vim uses by default.
+const MODELINE_LINES: usize = 5;
+
+/// One entry of the language registry.
+///
+/// `file_names` match the whole file name, case-insensitively; a name
+/// ending in `.` matches as a prefix, so `"Dockerfile."` covers
+/// `Dockerfile.dev`. `interpreters` are shebang commands without version
+/// suffixes, and `aliases` are extra names accepted in modelines.
+pub(crate) struct Language {
+    pub(crate) id: &'static str,
+    /// Editor mode for the frontend: `"markdown"`, `"code"` or `"text"`.
+    pub(crate) kind: &'static str,
+    extensions: &'static [&'static str],
+    file_names: &'static [&'static str],
+    interpreters: &'static [&'static str],
+    aliases: &'static [&'static str],
+}
+
+const fn lang(
+    id: &'static str,
+    kind: &'static str,
+    extensions: &'static [&'static str],
+    file_names: &'static [&'static str],
+    interpreters: &'static [&'static str],
+    aliases: &'static [&'static str],
+) -> Language {
+    Language {
+        id,
+        kind,
+        extensions,
+        file_names,
+        interpreters,
+        aliases,
+    }
+}
+
+pub(crate) const PLAIN_TEXT: Language =
+    lang("plaintext", "text", &["txt", "text"], &[], &[], &["text"]);
+
+static LANGUAGES: &[Language] = &[
+    lang(
+        "markdown",
+        "markdown",
+        &["md", "markdown", "mdown", "mkd", "mkdn", "rmd"],
+        &[],
+        &[],
+        &["md", "gfm"],
+    ),
+    PLAIN_TEXT,
+    lang("restructuredtext", "text", &["rst"], &[], &[], &["rst"]),
+    lang("log", "text", &["log"], &[], &[], &[]),
+    lang("json", "code", &["json"], &[], &[], &[]),
+    lang(
+        "jsonc",
+        "code",
+        &["jsonc"],
+        &[
+            ".babelrc",
+            ".eslintrc",
+            ".prettierrc",
+            "tsconfig.json",
+            "jsconfig.json",
+        ],
+        &[],
+        &[],
+    ),
+    lang("json5", "code", &["json5"], &[], &[], &[]),
+    lang("jsonl", "code", &["jsonl", "ndjson"], &[], &[], &[]),
+    lang("yaml", "code", &["yaml", "yml"], &[], &[], &[]),
+    lang(
+        "toml",
+        "code",
+        &["toml"],
+        &["Cargo.lock", "Pipfile", "poetry.lock"],
+        &[],
+        &[],
+    ),
+    lang(
+        "xml",
+        "code",
+        &[
+            "xml", "xsl", "xslt", "xsd", "wsdl", "rss", "atom", "svg", "plist",
+        ],
+        &[],
+        &[],
+        &["nxml"],
+    ),
+    lang(
+        "html",
+        "code",
+        &["html", "htm", "xhtml"],
+        &[],
+        &[],
+        &["mhtml", "web"],
+    ),
+    lang("css", "code", &["css"], &[], &[], &[]),
+    lang("scss", "code", &["scss"], &[], &[], &[]),
+    lang("sass", "code", &["sass"], &[], &[], &[]),
+    lang("less", "code", &["less"], &[], &[], &[]),
+    lang("stylus", "code", &["styl"], &[], &[], &[]),
+    lang(
+        "javascript",
+        "code",
+        &["js", "mjs", "cjs", "jsx"],
+        &[],
+        &["node", "nodejs", "bun"],
+        &["js", "js2", "js3", "javascriptreact"],
+    ),
+    lang(
+        "typescript",
+        "code",
+        &["ts", "mts", "cts", "tsx"],
+        &[],
+        &["deno", "ts-node", "tsx"],
+        &["ts", "typescriptreact"],
+    ),
+    lang("vue", "code", &["vue"], &[], &[], &[]),
+    lang("svelte", "code", &["svelte"], &[], &[], &[]),
+    lang("astro", "code", &["astro"], &[], &[], &[]),
+    lang(
+        "python",
+        "code",
+        &["py", "pyw", "pyi", "pyx", "pxd"],
+        &["SConstruct", "SConscript"],
+        &["python", "pypy"],
+        &["py"],
+    ),
+    lang(
+        "ruby",
+        "code",
+        &["rb", "rbw", "rake", "gemspec", "ru"],
+        &[
+            "Gemfile",
+            "Rakefile",
+            "Guardfile",
+            "Podfile",
+            "Vagrantfile",
+            "Brewfile",
+        ],
+        &["ruby", "jruby"],
+        &["rb"],
+    ),
+    lang(
+        "perl",
+        "code",
+        &["pl", "pm", "pod", "t", "psgi"],
+        &[],
+        &["perl"],
+        &["cperl"],
+    ),
+    lang(
+        "php",
+        "code",
+        &[
+            "php", "php3", "php4", "php5", "php7", "php8", "phtml", "phar",
+        ],
+        &[],
+        &["php"],
+        &[],
+    ),
+    lang("lua", "code", &["lua"], &[], &["lua", "luajit"], &[]),
+    lang(
+        "shellscript",
+        "code",
+        &["sh", "bash", "zsh", "ksh", "csh", "tcsh", "command", "tool"],
+        &[
+            ".bashrc",
+            ".bash_profile",
+            ".bash_aliases",
+            ".profile",
+            ".zshrc",
+            ".zprofile",
+            ".zshenv",
+            "PKGBUILD",
+        ],
+        &[
+            "sh", "bash", "zsh", "ksh", "mksh", "dash", "ash", "csh", "tcsh",
+        ],
+        &["sh", "bash", "zsh", "shell", "shell-script"],
+    ),
+    lang("fish", "code", &["fish"], &[], &["fish"], &[]),
+    lang(
+        "powershell",
+        "code",
+        &["ps1", "psm1", "psd1", "ps1xml"],
+        &[],
+        &["pwsh", "powershell"],
+        &["ps1"],
+    ),
+    lang(
+        "bat",
+        "code",
+        &["bat", "cmd"],
+        &[],
+        &[],
+        &["dosbatch", "batch"],
+    ),
+    lang(
+        "awk",
+        "code",
+        &["awk"],
+        &[],
+        &["awk", "gawk", "mawk", "nawk"],
+        &[],
+    ),
+    lang("tcl", "code", &["tcl", "tk"], &[], &["tclsh", "wish"], &[]),
+    lang(
+        "r",
+        "code",
+        &["r", "rnw"],
+        &[".Rprofile"],
+        &["Rscript"],
+        &["ess-r"],
+    ),
+    lang("julia", "code", &["jl"], &[], &["julia"], &[]),
+    lang("go", "code", &["go"], &[], &[], &["golang"]),
+    lang("rust", "code", &["rs"], &[], &[], &["rs"]),
+    lang("zig", "code", &["zig"], &[], &[], &[]),
+    lang("nim", "code", &["nim", "nims", "nimble"], &[], &[], &[]),
+    lang("v", "code", &["v"], &[], &[], &["vlang"]),
+    lang("c", "code", &["c", "h"], &[], &[], &[]),
+    lang(
+        "cpp",
+        "code",
+        &["cpp", "cxx", "cc", "c++", "hpp", "hxx", "hh", "h++"],
+        &[],
+        &[],
+        &["c++"],
+    ),
+    lang("objective-c", "code", &["m", "mm"], &[], &[], &["objc"]),
+    lang("csharp", "code", &["cs", "csx"], &[], &[], &["cs"]),
+    lang(
+        "fsharp",
+        "code",
+        &["fs", "fsi", "fsx", "fsscript"],
+        &[],
+        &[],
+        &[],
+    ),
+    lang("java", "code", &["java"], &[], &[], &[]),
+    lang("kotlin", "code", &["kt", "kts"], &[], &["kotlin"], &[]),
+    lang("scala", "code", &["scala", "sc"], &[], &["scala"], &[]),
+    lang(
+        "groovy",
+        "code",
+        &["groovy", "gvy", "gy", "gsh", "gradle"],
+        &["Jenkinsfile"],
+        &["groovy"],
+        &[],
+    ),
+    lang("swift", "code", &["swift"], &[], &["swift"], &[]),
+    lang("dart", "code", &["dart"], &[], &["dart"], &[]),
+    lang(
+        "elixir",
+        "code",
+        &["ex", "exs", "heex", "leex"],
+        &[],
+        &["elixir"],
+        &[],
+    ),
+    lang(
+        "erlang",
+        "code",
+        &["erl", "hrl"],
+        &["rebar.config"],
+        &["escript"],
+        &[],
+    ),
+    lang(
+        "haskell",
+        "code",
+        &["hs", "lhs"],
+        &[],
+        &["runghc", "runhaskell", "stack"],
+        &[],
+    ),
+    lang(
+        "clojure",
+        "code",
+        &["clj", "cljs", "cljc", "edn"],
+        &[],
+        &["bb"],
+        &[],
+    ),
+    lang(
+        "ocaml",
+        "code",
+        &["ml", "mli"],
+        &[],
+        &["ocaml"],
+        &["tuareg"],
+    ),
+    lang("elisp", "code", &["el"], &[".emacs"], &[], &["emacs-lisp"]),
+    lang("nix", "code", &["nix"], &[], &[], &[]),
+    lang(
+        "vim",
+        "code",
+        &["vim", "vimrc"],
+        &[".vimrc", "_vimrc", ".gvimrc"],
+        &[],
+        &["viml"],
+    ),
+    lang("sql", "code", &["sql", "ddl", "dml"], &[], &[], &[]),
+    lang("graphql", "code", &["graphql", "gql"], &[], &[], &[]),
+    lang("protobuf", "code", &["proto"], &[], &[], &["proto"]),
+    lang(
+        "hcl",
+        "code",
+        &["tf", "tfvars", "hcl"],
+        &[],
+        &[],
+        &["terraform"],
+    ),
+    lang(
+        "dockerfile",
+        "code",
+        &["dockerfile", "containerfile"],
+        &[
+            "Dockerfile",
+            "Dockerfile.",
+            "Containerfile",
+            "Containerfile.",
+        ],
+        &[],
+        &[],
+    ),
+    lang(
+        "makefile",
+        "code",
+        &["mk", "mak", "makefile"],
+        &["Makefile", "makefile", "GNUmakefile"],
+        &["make"],
+        &["make"],
+    ),
+    lang("cmake", "code", &["cmake"], &["CMakeLists.txt"], &[], &[]),
+    lang("diff", "code", &["diff", "patch"], &[], &[], &[]),
+    lang(
+        "ini",
+        "code",
+        &["ini", "cfg", "conf"],
+        &[".editorconfig", ".npmrc", ".gitconfig", ".gitmodules"],
+        &[],
+        &["dosini", "conf"],
+    ),
+    lang(
+        "properties",
+        "code",
+        &["properties"],
+        &[],
+        &[],
+        &["jproperties"],
+    ),
+    lang("dotenv", "code", &["env"], &[".env", ".env."], &[], &[]),
+    lang(
+        "ignore",
+        "code",
+        &["gitignore"],
+        &[
+            ".gitignore",
+            ".dockerignore",
+            ".npmignore",
+            ".prettierignore",
+            ".eslintignore",
+            ".teexignore",
+        ],
+        &[],
+        &["gitignore"],
+    ),
+    lang(
+        "gitattributes",
+        "code",
+        &["gitattributes"],
+        &[".gitattributes"],
+        &[],
+        &[],
+    ),
+    lang(
+        "apacheconf",
+        "code",
+        &["htaccess"],
+        &[".htaccess"],
+        &[],
+        &["apache"],
+    ),
+    lang("csv", "code", &["csv"], &[], &[], &[]),
+    lang("tsv", "code", &["tsv", "tab"], &[], &[], &[]),
+];
+
+static VIM_MODELINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?:^|\s)(?:vi|vim|ex):\s*(?:set?\s+)?(?:.*[\s:])?(?:ft|filetype|syntax)=([\w+.-]+)",
+    )
+    .expect("valid vim modeline regex")
+});
+static EMACS_MODELINE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"-\*-\s*(.*?)\s*-\*-").expect("valid emacs modeline regex"));
+
+fn matches_file_name(pattern: &str, name: &str) -> bool {
+    if pattern.ends_with('.') {
+        name.len() > pattern.len()
+            && name
+                .get(..pattern.len())
+                .is_some_and(|head| head.eq_ignore_ascii_case(pattern))
+    } else {
+        name.eq_ignore_ascii_case(pattern)
+    }
+}
+
+/// Look a language up by file name, then extension.
+pub(crate) fn language_for_path(path: &Path) -> Option<&'static Language> {
+    let name = path.file_name()?.to_str()?;
+    if let Some(found) = LANGUAGES.iter().find(|language| {
+        language
+            .file_names
+            .iter()
+            .any(|p| matches_file_name(p, name))
+    }) {
+        return Some(found);
+    }
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    LANGUAGES
+        .iter()
+        .find(|language| language.extensions.contains(&ext.as_str()))
+}
+
+/// Look a language up by a modeline name: its id, an alias, or an
+/// extension, since Vim filetypes often match those.
+fn language_for_name(name: &str) -> Option<&'static Language> {
+    let name = name.to_ascii_lowercase();
+    let name = name.strip_suffix("-mode").unwrap_or(&name);
+    LANGUAGES
+        .iter()
+        .find(|language| language.id == name || language.aliases.contains(&name))
+        .or_else(|| {
+            LANGUAGES
+                .iter()
+                .find(|language| language.extensions.contains(&name))
+        })
+}
+
+/// `python3.11` and `pypy3` resolve like `python` and `pypy`.
+fn interpreter_base(command: &str) -> &str {
+    let name = command.rsplit('/').next().unwrap_or(command);
+    name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.')
+}
+
+fn language_for_shebang(first_line: &str) -> Option<&'static Language> {
+    let mut words = first_line.strip_prefix("#!")?.split_whitespace();
+    let mut command = words.next()?;
+    if command.ends_with("/env") {
+        command = words.find(|word| !word.starts_with('-') && !word.contains('='))?;
+    }
+    let interpreter = interpreter_base(command);
+    LANGUAGES
+        .iter()
+        .find(|language| language.interpreters.contains(&interpreter))
+}
+
+fn emacs_mode(line: &str) -> Option<&str> {
+    let inner = EMACS_MODELINE.captures(line)?.get(1)?.as_str();
+    if !inner.contains(':') {
+        return Some(inner.trim_end_matches(';').trim());
+    }
+    inner.split(';').find_map(|pair| {
+        let (key, value) = pair.split_once(':')?;
+        key.trim()
+            .eq_ignore_ascii_case("mode")
+            .then_some(value.trim())
+    })
+}
+
+fn language_for_modeline(content: &str) -> Option<&'static Language> {
+    let lines: Vec<&str> = content.lines().collect();
+    // Emacs only honors the first line, or the second after a shebang.
+    let emacs = lines
+        .iter()
+        .take(2)
+        .find_map(|line| emacs_mode(line))
+        .and_then(language_for_name);
+    if emacs.is_some() {
+        return emacs;
+    }
+    let tail_start = lines
+        .len()
+        .saturating_sub(MODELINE_LINES)
+        .max(MODELINE_LINES);
+    lines
+        .iter()
+        .take(MODELINE_LINES)
+        .chain(lines.iter().skip(tail_start))
+        .find_map(|line| VIM_MODELINE.captures(line))
+        .and_then(|captures| language_for_name(captures.get(1)?.as_str()))
+}
+
+/// Detect the language of a file. A modeline wins since it is an explicit
+/// choice in the file, then the file name and extension, then the shebang
+/// interpreter. Anything unrecognized is plain text.
+pub(crate) fn detect_language(path: &Path, content: &str) -> &'static Language {
+    language_for_modeline(content)
+        .or_else(|| language_for_path(path))
+        .or_else(|| language_for_shebang(content.lines().next().unwrap_or_default()))
+        .unwrap_or(&PLAIN_TEXT)
+}
@@ -0,0 +1,311 @@
+use super::*;
+use crate::project_search::{
+    build_search_regex, match_line, SearchMatch, SearchOptions, DEFAULT_MAX_RESULTS,
+};
+use crate::text_encoding::sniff_encoding;
+use encoding_rs::Encoding;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::time::SystemTime;
+
+/// Files larger than this open in large-file mode: read-only, with lines
+/// served on demand by `read_text_range` instead of one big string.
+pub(crate) const LARGE_FILE_THRESHOLD_BYTES: u64 = 16 * 1024 * 1024;
+/// Every `LINE_INDEX_STRIDE`-th line start is recorded in the line index.
+const LINE_INDEX_STRIDE: usize = 1024;
+/// Lines included in `FilePayload.content` so the file shows immediately.
+const PREVIEW_LINES: usize = 1000;
+const MAX_RANGE_LINES: usize = 10_000;
+const SNIFF_BYTES: usize = 64 * 1024;
+const MAX_CACHED_INDEXES: usize = 8;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LargeFileInfo {
+    pub(crate) size_bytes: u64,
+    pub(crate) line_count: usize,
+    pub(crate) line_index_stride: usize,
+    /// Byte offset of every `line_index_stride`-th line, starting with line 1.
+    pub(crate) line_index: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TextRange {
+    pub(crate) start_line: usize,
+    pub(crate) lines: Vec<String>,
+    pub(crate) total_lines: usize,
+    pub(crate) has_more: bool,
+}
+
+#[derive(Clone)]
+struct LineIndex {
+    size_bytes: u64,
+    modified: Option<SystemTime>,
+    encoding: &'static Encoding,
+    line_count: usize,
+    checkpoints: Vec<u64>,
+}
+
+impl LineIndex {
+    fn is_current(&self, metadata: &fs::Metadata) -> bool {
+        self.size_bytes == metadata.len() && self.modified == metadata.modified().ok()
+    }
+
+    fn info(&self) -> LargeFileInfo {
+        LargeFileInfo {
+            size_bytes: self.size_bytes,
+            line_count: self.line_count,
+            line_index_stride: LINE_INDEX_STRIDE,
+            line_index: self.checkpoints.clone(),
+        }
+    }
+}
+
+/// Line indexes of the most recently read large files, newest last, so
+/// paging and searching do not rescan a file until it changes.
+pub(crate) struct LineIndexCache {
+    indexes: Mutex<Vec<(PathBuf, LineIndex)>>,
+}
+
+impl LineIndexCache {
+    pub(crate) fn new() -> Self {
+        Self {
+            indexes: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn index(&self, path: &Path) -> Result<LineIndex, String> {
+        let metadata =
+            fs::metadata(path).map_err(|e| format!("Unable to read file metadata: {e}"))?;
+        let key = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+
+        if let Ok(mut indexes) = self.indexes.lock() {
+            if let Some(position) = indexes.iter().position(|(p, _)| *p == key) {
+                let (_, index) = indexes.remove(position);
+                if index.is_current(&metadata) {
+                    indexes.push((key, index.clone()));
+                    return Ok(index);
+                }
+            }
+        }
+
+        let index = build_line_index(path, &metadata)?;
+        if let Ok(mut indexes) = self.indexes.lock() {
+            if indexes.len() >= MAX_CACHED_INDEXES {
+                indexes.remove(0);
+            }
+            indexes.push((key, index.clone()));
+        }
+        Ok(index)
+    }
+}
+
+pub(crate) fn is_large_file(metadata: &fs::Metadata) -> bool {
+    metadata.len() > LARGE_FILE_THRESHOLD_BYTES
+}
+
+fn build_line_index(path: &Path, metadata: &fs::Metadata) -> Result<LineIndex, String> {
+    let mut file = fs::File::open(path).map_err(|e| format!("Unable to read file: {e}"))?;
+
+    let mut sample = Vec::with_capacity(SNIFF_BYTES);
+    (&mut file)
+        .take(SNIFF_BYTES as u64)
+        .read_to_end(&mut sample)
+        .map_err(|e| format!("Unable to read file: {e}"))?;
+    let (encoding, bom_len) = sniff_encoding(&sample)?;
+
+    file.seek(SeekFrom::Start(bom_len as u64))
+        .map_err(|e| format!("Unable to read file: {e}"))?;
+    let mut reader = BufReader::with_capacity(1024 * 1024, file);
+    let mut checkpoints = vec![bom_len as u64];
+    let mut offset = bom_len as u64;
+    let mut line_count = 0;
+    let mut ends_with_newline = true;
+
+    loop {
+        let buffer = reader
+            .fill_buf()
+            .map_err(|e| format!("Unable to read file: {e}"))?;
+        if buffer.is_empty() {
+            break;
+        }
+        for (i, byte) in buffer.iter().enumerate() {
+            if *byte != b'\n' {
+                continue;
+            }
+            line_count += 1;
+            if line_count % LINE_INDEX_STRIDE == 0 {
+                checkpoints.push(offset + i as u64 + 1);
+            }
+        }
+        ends_with_newline = buffer.last() == Some(&b'\n');
+        let consumed = buffer.len();
+        offset += consumed as u64;
+        reader.consume(consumed);
+    }
+    if !ends_with_newline {
+        line_count += 1;
+    }
+    // A trailing newline does not start another line worth indexing.
+    if checkpoints.len() > 1 && checkpoints.last() == Some(&offset) {
+        checkpoints.pop();
+    }
+
+    Ok(LineIndex {
+        size_bytes: metadata.len(),
+        modified: metadata.modified().ok(),
+        encoding,
+        line_count,
+        checkpoints,
+    })
+}
+
+fn decode_line(raw: &[u8], encoding: &'static Encoding) -> String {
+    let trimmed = raw.strip_suffix(b"\n").unwrap_or(raw);
+    let trimmed = trimmed.strip_suffix(b"\r").unwrap_or(trimmed);
+    encoding.decode_without_bom_handling(trimmed).0.into_owned()
+}
+
+/// Read up to `count` lines starting at 1-based `start_line`.
+fn read_lines(
+    path: &Path,
+    index: &LineIndex,
+    start_line: usize,
+    count: usize,
+) -> Result<Vec<String>, String> {
+    let start_line = start_line.max(1);
+    let checkpoint = (start_line - 1) / LINE_INDEX_STRIDE;
+    let Some(offset) = index.checkpoints.get(checkpoint) else {
+        return Ok(Vec::new());
+    };
+
+    let mut file = fs::File::open(path).map_err(|e| format!("Unable to read file: {e}"))?;
+    file.seek(SeekFrom::Start(*offset))
+        .map_err(|e| format!("Unable to read file: {e}"))?;
+    let mut reader = BufReader::new(file);
+    let mut raw = Vec::new();
+
+    for _ in 0..(start_line - 1) % LINE_INDEX_STRIDE {
+        raw.clear();
+        if reader
+            .read_until(b'\n', &mut raw)
+            .map_err(|e| format!("Unable to read file: {e}"))?
+            == 0
+        {
+            return Ok(Vec::new());
+        }
+    }
+
+    let mut lines = Vec::with_capacity(count.min(MAX_RANGE_LINES));
+    while lines.len() < count {
+        raw.clear();
+        if reader
+            .read_until(b'\n', &mut raw)
+            .map_err(|e| format!("Unable to read file: {e}"))?
+            == 0
+        {
+            break;
+        }
+        lines.push(decode_line(&raw, index.encoding));
+    }
+    Ok(lines)
+}
+
+/// Metadata and the first lines of a file in large-file mode, used by
+/// `read_text_file` in place of the full content.
+pub(crate) fn large_file_preview(
+    cache: &LineIndexCache,
+    path: &Path,
+) -> Result<(LargeFileInfo, String, &'static Encoding), String> {
+    let index = cache.index(path)?;
+    let mut preview = read_lines(path, &index, 1, PREVIEW_LINES)?.join("\n");
+    if index.line_count > PREVIEW_LINES {
+        preview.push('\n');
+    }
+    Ok((index.info(), preview, index.encoding))
+}
+
+/// `count` lines of the file at `path` starting at 1-based `start_line`.
+pub(crate) fn text_range(
+    cache: &LineIndexCache,
+    path: &Path,
+    start_line: usize,
+    count: usize,
+) -> Result<TextRange, String> {
+    if !path.is_file() {
+        return Err("File was not found".to_string());
+    }
+    let index = cache.index(path)?;
+    let start_line = start_line.max(1);
+    let lines = read_lines(path, &index, start_line, count.min(MAX_RANGE_LINES))?;
+    let has_more = start_line - 1 + lines.len() < index.line_count;
+    Ok(TextRange {
+        start_line,
+        lines,
+        total_lines: index.line_count,
+        has_more,
+    })
+}
+
+/// Search the file at `path` line by line without loading it into memory,
+/// returning matches with 1-based line numbers.
+pub(crate) fn search_file_lines(
+    cache: &LineIndexCache,
+    path: &Path,
+    options: &SearchOptions,
+) -> Result<Vec<SearchMatch>, String> {
+    if !path.is_file() {
+        return Err("File was not found".to_string());
+    }
+    let regex = build_search_regex(options)?;
+    let limit = options.max_results.unwrap_or(DEFAULT_MAX_RESULTS).max(1);
+    let index = cache.index(path)?;
+
+    let mut file = fs::File::open(path).map_err(|e| format!("Unable to read file: {e}"))?;
+    let start = index.checkpoints.first().copied().unwrap_or(0);
+    file.seek(SeekFrom::Start(start))
+        .map_err(|e| format!("Unable to read file: {e}"))?;
+    let mut reader = BufReader::with_capacity(1024 * 1024, file);
+
+    let mut matches = Vec::new();
+    let mut raw = Vec::new();
+    let mut line_number = 0;
+    while matches.len() < limit {
+        raw.clear();
+        if reader
+            .read_until(b'\n', &mut raw)
+            .map_err(|e| format!("Unable to read file: {e}"))?
+            == 0
+        {
+            break;
+        }
+        line_number += 1;
+        let line = decode_line(&raw, index.encoding);
+        if let Some(found) = match_line(line_number, &line, &regex) {
+            matches.push(found);
+        }
+    }
+    Ok(matches)
+}
+
+/// Serve `count` lines of a large file starting at 1-based `start_line`.
+/// Jump-to-line is a range read at the target line.
+#[tauri::command]
+pub(crate) fn read_text_range(
+    cache: tauri::State<LineIndexCache>,
+    path: String,
+    start_line: usize,
+    count: usize,
+) -> Result<TextRange, String> {
+    text_range(&cache, Path::new(&path), start_line, count)
+}
+
+/// Search a single file without loading it into memory.
+#[tauri::command]
+pub(crate) fn search_text_file(
+    cache: tauri::State<LineIndexCache>,
+    path: String,
+    options: SearchOptions,
+) -> Result<Vec<SearchMatch>, String> {
+    search_file_lines(&cache, Path::new(&path), &options)
+}
@@ -1,3 +1,4 @@
+use ignore::DirEntry;
 use notify::{
     event::ModifyKind, Config as NotifyConfig, Event, EventKind, RecommendedWatcher, RecursiveMode,
     Watcher,
@@ -19,52 +20,96 @@ use tauri::{Emitter, Manager};
 use tauri_plugin_dialog::DialogExt;
 #[cfg(target_os = "macos")]
 use tauri_plugin_dialog::MessageDialogKind;
-use walkdir::{DirEntry, WalkDir};
 
 mod app_runtime;
+mod binary_files;
 mod constants;
+mod file_index;
+mod file_journal;
+mod file_ops;
 mod files;
 mod git;
+mod json_format;
+mod json_schema;
+mod languages;
+mod large_file;
 mod launch;
 #[cfg(target_os = "linux")]
 mod linux;
+mod local_history;
+mod log_follow;
 #[cfg(target_os = "macos")]
 mod macos;
+mod markdown_links;
+mod markdown_outline;
+mod markdown_table;
 mod menu;
 mod menu_events;
+mod path_diff;
 mod path_utils;
+mod project_ignore;
+mod project_search;
+mod project_tree;
 mod recent_files;
+mod semantic_diff;
+mod table_view;
 mod tabs;
+mod task_list;
+mod text_encoding;
+mod validation;
 mod watchers;
 mod window;
+mod yaml_layout;
 
 use constants::*;
 
+use binary_files::read_binary_range;
+use file_index::fuzzy_find_files;
+use file_journal::undo_file_operation;
+use file_ops::{create_file, create_folder, duplicate_path, move_path, rename_path};
 use files::{
-    format_structured_text, list_project_entries, read_text_file, trash_file, write_text_file,
+    format_structured_text, read_text_file, reopen_with_encoding, trash_file, write_text_file,
 };
 use git::git_diff;
 use git::git_diff_all;
 use git::git_status;
+use large_file::{read_text_range, search_text_file};
 #[cfg(target_os = "macos")]
 use launch::queue_open_paths;
 use launch::{
     categorize_paths, get_launch_context, open_paths_in_new_window, queue_open_paths_for_window,
     take_pending_open_paths,
 };
+use local_history::{diff_file_history, list_file_history, restore_file_history};
+use log_follow::{follow_file, stop_following_file};
+use markdown_links::check_markdown_links;
+use markdown_outline::markdown_outline;
+use markdown_table::{csv_to_markdown_table, markdown_table_to_csv};
 use menu_events::{emit_to_window, handle_app_menu_event, set_menu_item_enabled};
 #[cfg(test)]
 use menu_events::{next_transfer_request_id, window_event};
+use path_diff::diff_paths;
+pub use path_diff::run_diff_cli;
 use path_utils::{
-    file_kind, is_dotfile_config, is_text_like, path_to_string, should_traverse_with_hidden,
+    is_always_blocked_dir, is_visible_project_file, path_to_string, project_file_binary_type,
+    project_file_binary_type_by_name, should_traverse_with_hidden,
 };
+#[cfg(test)]
+use path_utils::{is_dotfile_config, is_text_like};
+use project_search::{cancel_project_search, replace_in_files, search_project};
+use project_tree::{list_directory, list_project_entries, stream_project_entries};
 use recent_files::{add_recent_file, add_recent_folder};
+use semantic_diff::{git_semantic_diff, semantic_diff_paths};
+use table_view::{read_table, update_table_cell};
 use tabs::{
     cancel_cross_window_drag_hover, cleanup_drag_entries_for_window, create_window_from_drag,
     get_drag_preview_content, hide_tab_drag_preview, report_drag_position, route_tab_transfer,
     route_tab_transfer_result, show_tab_drag_preview, CrossWindowDragRegistry,
     RequestExportAllTabsPayload, TabDragPreviewState,
 };
+use task_list::read_task_list;
+pub use task_list::run_tasks_cli;
+use validation::validate_structured_text;
 use watchers::{
     clear_project_file_watch_for_label, clear_project_folder_watch_for_label,
     install_project_file_watch, install_project_folder_watch,
@@ -88,6 +133,9 @@ struct FolderWatchRegistry {
 
 struct WindowFolderWatch {
     root: PathBuf,
+    show_hidden: bool,
+    show_ignored: bool,
+    index: file_index::SharedFileIndex,
     _watcher: RecommendedWatcher,
 }
 
@@ -216,8 +264,19 @@ fn notify_window_focused(window: tauri::Window) {
 }
 
 #[tauri::command]
-fn watch_project_folder(window: tauri::Window, root: String) -> Result<(), String> {
-    install_project_folder_watch(window.app_handle(), window.label(), PathBuf::from(root))
+fn watch_project_folder(
+    window: tauri::Window,
+    root: String,
+    show_hidden: bool,
+    show_ignored: bool,
+) -> Result<(), String> {
+    install_project_folder_watch(
+        window.app_handle(),
+        window.label(),
+        PathBuf::from(root),
+        show_hidden,
+        show_ignored,
+    )
 }
 
 #[tauri::command]
@@ -278,6 +337,22 @@ fn set_show_modified_only_checked(app: tauri::AppHandle, checked: bool) -> Resul
     Ok(())
 }
 
+#[tauri::command]
+fn set_show_ignored_files_checked(app: tauri::AppHandle, checked: bool) -> Result<(), String> {
+    let Some(menu) = app.menu() else {
+        return Ok(());
+    };
+    let Some(item) = menu.get(MENU_SHOW_IGNORED_FILES) else {
+        return Ok(());
+    };
+    if let Some(check_item) = item.as_check_menuitem() {
+        check_item
+            .set_checked(checked)
+            .map_err(|e| format!("{e}"))?;
+    }
+    Ok(())
+}
+
 #[tauri::command]
 fn focus_window(app: tauri::AppHandle, label: String) -> Result<(), String> {
     let window = app
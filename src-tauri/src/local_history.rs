@@ -0,0 +1,364 @@
+use super::*;
+use crate::files::write_file_atomically;
+use crate::git::FileDiff;
+use crate::path_diff::text_diff_hunks;
+use crate::text_encoding::decode_text;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+use std::sync::atomic::AtomicUsize;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const HISTORY_DIR: &str = "history";
+const OBJECTS_DIR: &str = "objects";
+const INDEX_DIR: &str = "index";
+/// Snapshots kept per file; older ones are pruned first.
+const MAX_SNAPSHOTS_PER_FILE: usize = 50;
+/// Snapshots older than this are pruned.
+const MAX_SNAPSHOT_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+/// Larger files are not snapshotted.
+const MAX_SNAPSHOT_BYTES: u64 = 5 * 1024 * 1024;
+/// Unused objects are collected after this many prunes, off the save path.
+const PRUNES_PER_GC: usize = 20;
+
+/// Why a snapshot was taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum HistorySource {
+    /// The on-disk content just before Teex overwrote it.
+    BeforeSave,
+    /// Content written by `write_text_file`.
+    Save,
+    /// A change made outside Teex, seen by the file watcher.
+    External,
+    /// Content just before a snapshot was restored over it.
+    BeforeRestore,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HistoryEntry {
+    /// Milliseconds since the Unix epoch, unique per file.
+    pub(crate) id: u64,
+    pub(crate) hash: String,
+    pub(crate) size: u64,
+    pub(crate) source: HistorySource,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct HistoryIndex {
+    path: String,
+    entries: Vec<HistoryEntry>,
+}
+
+/// Compressed, content-addressed snapshots of text files. Each distinct
+/// content is stored once under `objects/`; `index/` holds one JSON list
+/// of snapshots per file.
+pub(crate) struct LocalHistory {
+    dir: PathBuf,
+    lock: Arc<Mutex<()>>,
+    prunes_since_gc: AtomicUsize,
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn history_key(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn decode_snapshot(bytes: &[u8]) -> Result<String, String> {
+    decode_text(bytes, None)
+        .map(|decoded| decoded.content)
+        .map_err(|_| "Binary snapshots cannot be diffed".to_string())
+}
+
+/// Delete objects under `dir` that no index refers to any more. Callers
+/// hold the history lock.
+fn collect_garbage(dir: &Path) {
+    let Ok(indexes) = fs::read_dir(dir.join(INDEX_DIR)) else {
+        return;
+    };
+    let live: std::collections::HashSet<String> = indexes
+        .flatten()
+        .filter_map(|entry| fs::read(entry.path()).ok())
+        .filter_map(|data| serde_json::from_slice::<HistoryIndex>(&data).ok())
+        .flat_map(|index| index.entries.into_iter().map(|entry| entry.hash))
+        .collect();
+    let Ok(objects) = fs::read_dir(dir.join(OBJECTS_DIR)) else {
+        return;
+    };
+    for object in objects.flatten() {
+        let name = object.file_name().to_string_lossy().to_string();
+        let hash = name.strip_suffix(".gz").unwrap_or(&name);
+        if !live.contains(hash) {
+            let _ = fs::remove_file(object.path());
+        }
+    }
+}
+
+impl LocalHistory {
+    pub(crate) fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            lock: Arc::new(Mutex::new(())),
+            prunes_since_gc: AtomicUsize::new(0),
+        }
+    }
+
+    /// History stored under the app data dir, or a temp dir when the app
+    /// data dir cannot be resolved.
+    pub(crate) fn for_app(app: &tauri::AppHandle) -> Self {
+        let base = app
+            .path()
+            .app_data_dir()
+            .unwrap_or_else(|_| env::temp_dir().join("teex"));
+        Self::new(base.join(HISTORY_DIR))
+    }
+
+    fn object_path(&self, hash: &str) -> PathBuf {
+        self.dir.join(OBJECTS_DIR).join(format!("{hash}.gz"))
+    }
+
+    fn index_path(&self, key: &Path) -> PathBuf {
+        let name = sha256_hex(path_to_string(key).as_bytes());
+        self.dir.join(INDEX_DIR).join(format!("{name}.json"))
+    }
+
+    fn load_index(&self, key: &Path) -> HistoryIndex {
+        fs::read(self.index_path(key))
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_else(|| HistoryIndex {
+                path: path_to_string(key),
+                entries: Vec::new(),
+            })
+    }
+
+    fn save_index(&self, key: &Path, index: &HistoryIndex) -> Result<(), String> {
+        let path = self.index_path(key);
+        if index.entries.is_empty() {
+            let _ = fs::remove_file(&path);
+            return Ok(());
+        }
+        let data =
+            serde_json::to_vec(index).map_err(|e| format!("Unable to write local history: {e}"))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Unable to write local history: {e}"))?;
+        }
+        write_file_atomically(&path, &data)
+            .map_err(|e| format!("Unable to write local history: {e}"))
+    }
+
+    fn store_object(&self, hash: &str, bytes: &[u8]) -> Result<(), String> {
+        let path = self.object_path(hash);
+        if path.exists() {
+            return Ok(());
+        }
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(bytes)
+            .and_then(|_| encoder.finish())
+            .and_then(|compressed| {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                write_file_atomically(&path, &compressed)
+            })
+            .map_err(|e| format!("Unable to write local history: {e}"))
+    }
+
+    fn load_object(&self, hash: &str) -> Result<Vec<u8>, String> {
+        let file = fs::File::open(self.object_path(hash))
+            .map_err(|e| format!("Unable to read snapshot: {e}"))?;
+        let mut bytes = Vec::new();
+        GzDecoder::new(file)
+            .read_to_end(&mut bytes)
+            .map_err(|e| format!("Unable to read snapshot: {e}"))?;
+        Ok(bytes)
+    }
+
+    /// Drop snapshots beyond the count and age limits. Returns whether
+    /// anything was removed.
+    fn prune(index: &mut HistoryIndex, now: u64) -> bool {
+        let before = index.entries.len();
+        let oldest_allowed = now.saturating_sub(MAX_SNAPSHOT_AGE.as_millis() as u64);
+        index.entries.retain(|entry| entry.id >= oldest_allowed);
+        if index.entries.len() > MAX_SNAPSHOTS_PER_FILE {
+            let excess = index.entries.len() - MAX_SNAPSHOTS_PER_FILE;
+            index.entries.drain(..excess);
+        }
+        index.entries.len() != before
+    }
+
+    /// Run the pass `schedule_garbage_collection` runs in the background.
+    #[cfg(test)]
+    pub(crate) fn collect_garbage(&self) {
+        if let Ok(_guard) = self.lock.lock() {
+            collect_garbage(&self.dir);
+        }
+    }
+
+    /// Count a prune and, every `PRUNES_PER_GC` of them, collect garbage on
+    /// a background thread once the current record releases the lock.
+    fn schedule_garbage_collection(&self) {
+        if self.prunes_since_gc.fetch_add(1, Ordering::Relaxed) + 1 < PRUNES_PER_GC {
+            return;
+        }
+        self.prunes_since_gc.store(0, Ordering::Relaxed);
+        let dir = self.dir.clone();
+        let lock = Arc::clone(&self.lock);
+        std::thread::spawn(move || {
+            if let Ok(_guard) = lock.lock() {
+                collect_garbage(&dir);
+            }
+        });
+    }
+
+    /// Record `bytes` as the newest version of `path`. Content identical
+    /// to the newest snapshot is skipped and `None` is returned.
+    pub(crate) fn record(
+        &self,
+        path: &Path,
+        bytes: &[u8],
+        source: HistorySource,
+    ) -> Result<Option<HistoryEntry>, String> {
+        if bytes.len() as u64 > MAX_SNAPSHOT_BYTES {
+            return Ok(None);
+        }
+        let key = history_key(path);
+        let hash = sha256_hex(bytes);
+        let _guard = self.lock.lock().map_err(|e| e.to_string())?;
+
+        let mut index = self.load_index(&key);
+        if index.entries.last().is_some_and(|last| last.hash == hash) {
+            return Ok(None);
+        }
+
+        self.store_object(&hash, bytes)?;
+        let now = now_millis();
+        let id = match index.entries.last() {
+            Some(last) if last.id >= now => last.id + 1,
+            _ => now,
+        };
+        let entry = HistoryEntry {
+            id,
+            hash,
+            size: bytes.len() as u64,
+            source,
+        };
+        index.entries.push(entry.clone());
+        let pruned = Self::prune(&mut index, now);
+        self.save_index(&key, &index)?;
+        if pruned {
+            self.schedule_garbage_collection();
+        }
+        Ok(Some(entry))
+    }
+
+    /// Read `path` from disk and record it. Missing or unreadable files
+    /// are ignored.
+    pub(crate) fn record_file(&self, path: &Path, source: HistorySource) {
+        if fs::metadata(path).is_ok_and(|m| m.is_file() && m.len() <= MAX_SNAPSHOT_BYTES) {
+            if let Ok(bytes) = fs::read(path) {
+                let _ = self.record(path, &bytes, source);
+            }
+        }
+    }
+
+    /// Snapshots of `path`, newest first.
+    pub(crate) fn list(&self, path: &Path) -> Vec<HistoryEntry> {
+        let mut entries = self.load_index(&history_key(path)).entries;
+        entries.reverse();
+        entries
+    }
+
+    /// The stored bytes of snapshot `id` of `path`.
+    pub(crate) fn read(&self, path: &Path, id: u64) -> Result<Vec<u8>, String> {
+        let index = self.load_index(&history_key(path));
+        let entry = index
+            .entries
+            .iter()
+            .find(|entry| entry.id == id)
+            .ok_or_else(|| "Snapshot not found".to_string())?;
+        self.load_object(&entry.hash)
+    }
+
+    /// Diff snapshot `from_id` against snapshot `to_id`, or against the
+    /// file on disk when `to_id` is `None`. Returns `None` when identical.
+    pub(crate) fn diff(
+        &self,
+        path: &Path,
+        from_id: u64,
+        to_id: Option<u64>,
+    ) -> Result<Option<FileDiff>, String> {
+        let old = decode_snapshot(&self.read(path, from_id)?)?;
+        let new_bytes = match to_id {
+            Some(id) => self.read(path, id)?,
+            None => fs::read(path).map_err(|e| format!("Unable to read file: {e}"))?,
+        };
+        let new = decode_snapshot(&new_bytes)?;
+
+        let hunks = text_diff_hunks(&old, &new);
+        if hunks.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(FileDiff {
+            rel_path: path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            status: "changed".to_string(),
+            hunks,
+            binary: false,
+        }))
+    }
+
+    /// Write snapshot `id` back to `path`. The current content is
+    /// snapshotted first, so a restore can itself be undone.
+    pub(crate) fn restore(&self, path: &Path, id: u64) -> Result<(), String> {
+        let bytes = self.read(path, id)?;
+        self.record_file(path, HistorySource::BeforeRestore);
+        write_file_atomically(path, &bytes).map_err(|e| format!("Unable to write file: {e}"))?;
+        self.record(path, &bytes, HistorySource::Save)?;
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub(crate) fn list_file_history(app: tauri::AppHandle, path: String) -> Vec<HistoryEntry> {
+    app.state::<LocalHistory>().list(Path::new(&path))
+}
+
+#[tauri::command]
+pub(crate) fn diff_file_history(
+    app: tauri::AppHandle,
+    path: String,
+    from_id: u64,
+    to_id: Option<u64>,
+) -> Result<Option<FileDiff>, String> {
+    app.state::<LocalHistory>()
+        .diff(Path::new(&path), from_id, to_id)
+}
+
+#[tauri::command]
+pub(crate) fn restore_file_history(
+    app: tauri::AppHandle,
+    path: String,
+    id: u64,
+) -> Result<(), String> {
+    app.state::<LocalHistory>().restore(Path::new(&path), id)
+}
@@ -0,0 +1,279 @@
+use super::*;
+use regex::Regex;
+use std::io::{Read, Seek, SeekFrom};
+
+/// Upper bound on bytes read per poll so a burst of output is delivered in
+/// several events instead of one huge payload.
+const MAX_FOLLOW_READ_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FileAppended {
+    pub(crate) path: String,
+    /// Newly appended text. With a filter, only complete matching lines
+    /// (each ending in `\n`) are included.
+    pub(crate) text: String,
+    /// Byte offset in the file up to which content has been consumed.
+    pub(crate) offset: u64,
+    /// The file shrank, so reading restarted from the beginning.
+    pub(crate) truncated: bool,
+    /// The path now points at a different file (log rotation), so reading
+    /// restarted from the beginning of the new file.
+    pub(crate) rotated: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FollowStarted {
+    pub(crate) path: String,
+    pub(crate) offset: u64,
+}
+
+#[cfg(unix)]
+fn file_identity(metadata: &fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.ino())
+}
+
+#[cfg(not(unix))]
+fn file_identity(metadata: &fs::Metadata) -> Option<u64> {
+    metadata
+        .created()
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|elapsed| elapsed.as_nanos() as u64)
+}
+
+/// Read position and pending partial data for one followed file.
+pub(crate) struct FollowState {
+    path: PathBuf,
+    offset: u64,
+    identity: Option<u64>,
+    filter: Option<Regex>,
+    /// Bytes read but not yet emitted: an incomplete UTF-8 sequence, or an
+    /// unterminated line when a filter is active.
+    pending: Vec<u8>,
+    /// The last poll stopped at `MAX_FOLLOW_READ_BYTES` with more to read.
+    has_more: bool,
+}
+
+impl FollowState {
+    /// Start following `path`. With `from_end`, only content appended after
+    /// this call is reported; otherwise the existing content comes first.
+    pub(crate) fn new(path: &Path, filter: Option<&str>, from_end: bool) -> Result<Self, String> {
+        let metadata = fs::metadata(path).map_err(|e| format!("Unable to follow file: {e}"))?;
+        let filter = filter
+            .filter(|pattern| !pattern.is_empty())
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| format!("Invalid filter pattern: {e}"))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            offset: if from_end { metadata.len() } else { 0 },
+            identity: file_identity(&metadata),
+            filter,
+            pending: Vec::new(),
+            has_more: false,
+        })
+    }
+
+    pub(crate) fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub(crate) fn has_more(&self) -> bool {
+        self.has_more
+    }
+
+    /// Read whatever was appended since the last poll. Returns `None` when
+    /// there is nothing new to report, including while a rotated file has
+    /// not been recreated yet.
+    pub(crate) fn poll(&mut self) -> Option<FileAppended> {
+        self.has_more = false;
+        let metadata = fs::metadata(&self.path).ok()?;
+        let identity = file_identity(&metadata);
+        let rotated = identity.is_some() && identity != self.identity;
+        let truncated = !rotated && metadata.len() < self.offset;
+        if rotated || truncated {
+            self.identity = identity;
+            self.offset = 0;
+            self.pending.clear();
+        }
+
+        let mut file = fs::File::open(&self.path).ok()?;
+        file.seek(SeekFrom::Start(self.offset)).ok()?;
+        let mut appended = Vec::new();
+        file.take(MAX_FOLLOW_READ_BYTES)
+            .read_to_end(&mut appended)
+            .ok()?;
+        if appended.is_empty() && !rotated && !truncated {
+            return None;
+        }
+        self.offset += appended.len() as u64;
+        self.has_more = self.offset < metadata.len();
+        self.pending.extend_from_slice(&appended);
+
+        let text = if self.filter.is_some() {
+            self.take_matching_lines()
+        } else {
+            self.take_complete_utf8()
+        };
+        if text.is_empty() && !rotated && !truncated {
+            return None;
+        }
+        Some(FileAppended {
+            path: path_to_string(&self.path),
+            text,
+            offset: self.offset,
+            truncated,
+            rotated,
+        })
+    }
+
+    fn take_complete_utf8(&mut self) -> String {
+        let valid = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => self.pending.len(),
+        };
+        let rest = self.pending.split_off(valid);
+        let text = String::from_utf8_lossy(&self.pending).into_owned();
+        self.pending = rest;
+        text
+    }
+
+    fn take_matching_lines(&mut self) -> String {
+        let Some(last_newline) = self.pending.iter().rposition(|b| *b == b'\n') else {
+            return String::new();
+        };
+        let rest = self.pending.split_off(last_newline + 1);
+        let complete = String::from_utf8_lossy(&self.pending).into_owned();
+        self.pending = rest;
+
+        complete
+            .split_inclusive('\n')
+            .filter(|line| {
+                self.filter
+                    .as_ref()
+                    .is_none_or(|filter| filter.is_match(line.trim_end_matches(['\n', '\r'])))
+            })
+            .collect()
+    }
+}
+
+/// Poll until the file is read to its current end, emitting each update.
+fn drain_follow(app: &tauri::AppHandle, label: &str, state: &Mutex<FollowState>) {
+    let Ok(mut state) = state.lock() else {
+        return;
+    };
+    loop {
+        if let Some(update) = state.poll() {
+            emit_to_window(app, label, EVENT_FILE_APPENDED, update);
+        }
+        if !state.has_more() {
+            break;
+        }
+    }
+}
+
+struct FileFollow {
+    _watcher: RecommendedWatcher,
+}
+
+/// Active follows per window, keyed by the followed file path.
+pub(crate) struct FollowRegistry {
+    by_window: Mutex<HashMap<String, HashMap<PathBuf, FileFollow>>>,
+}
+
+impl FollowRegistry {
+    pub(crate) fn new() -> Self {
+        Self {
+            by_window: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn clear_for_label(&self, label: &str) {
+        if let Ok(mut follows) = self.by_window.lock() {
+            follows.remove(label);
+        };
+    }
+}
+
+/// Follow a growing file and emit `FileAppended` events to the calling
+/// window with only the new content. Truncation and rotation restart the
+/// read from the start of the file and are flagged on the event. `filter`
+/// is a regex; when set, only matching complete lines are sent.
+#[tauri::command]
+pub(crate) fn follow_file(
+    window: tauri::Window,
+    path: String,
+    filter: Option<String>,
+    from_end: Option<bool>,
+) -> Result<FollowStarted, String> {
+    let path_buf = PathBuf::from(&path);
+    if !path_buf.is_file() {
+        return Err("File was not found".to_string());
+    }
+    let canonical = fs::canonicalize(&path_buf).unwrap_or(path_buf);
+    let Some(parent) = canonical.parent().map(Path::to_path_buf) else {
+        return Err("Unable to follow file without a parent folder".to_string());
+    };
+
+    let state = FollowState::new(&canonical, filter.as_deref(), from_end.unwrap_or(true))?;
+    let started = FollowStarted {
+        path: path_to_string(&canonical),
+        offset: state.offset(),
+    };
+    let state = Arc::new(Mutex::new(state));
+
+    let app = window.app_handle().clone();
+    let label = window.label().to_string();
+    let followed = canonical.clone();
+    let watched_state = Arc::clone(&state);
+    let mut watcher = RecommendedWatcher::new(
+        move |result: notify::Result<Event>| {
+            let Ok(event) = result else {
+                return;
+            };
+            if !event.paths.contains(&followed) {
+                return;
+            }
+            drain_follow(&app, &label, &watched_state);
+        },
+        NotifyConfig::default(),
+    )
+    .map_err(|e| format!("Unable to start file watcher: {e}"))?;
+    // The parent folder is watched so a rotated log is picked up when it
+    // is recreated under the same name.
+    watcher
+        .watch(&parent, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Unable to watch file: {e}"))?;
+
+    let registry = window.app_handle().state::<FollowRegistry>();
+    let mut follows = registry
+        .by_window
+        .lock()
+        .map_err(|_| "Unable to update follow registry".to_string())?;
+    follows
+        .entry(window.label().to_string())
+        .or_default()
+        .insert(canonical, FileFollow { _watcher: watcher });
+    drop(follows);
+
+    if started.offset == 0 {
+        drain_follow(window.app_handle(), window.label(), &state);
+    }
+    Ok(started)
+}
+
+#[tauri::command]
+pub(crate) fn stop_following_file(window: tauri::Window, path: String) {
+    let path_buf = PathBuf::from(&path);
+    let canonical = fs::canonicalize(&path_buf).unwrap_or(path_buf);
+    let registry = window.app_handle().state::<FollowRegistry>();
+    if let Ok(mut follows) = registry.by_window.lock() {
+        if let Some(window_follows) = follows.get_mut(window.label()) {
+            window_follows.remove(&canonical);
+        }
+    };
+}
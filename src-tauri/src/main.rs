@@ -12,8 +12,19 @@ description: Companion review tool for AI workflows. Use when the user should re
 "#;
 
 enum StartupAction {
-    LaunchApp { wait: bool, app_args: Vec<String> },
+    LaunchApp {
+        wait: bool,
+        app_args: Vec<String>,
+    },
     InstallSkill,
+    Diff {
+        old: String,
+        new: String,
+        semantic: bool,
+    },
+    Tasks {
+        path: String,
+    },
     PrintHelp,
     UsageError(String),
 }
@@ -25,6 +36,10 @@ fn main() {
             process::exit(0);
         }
         StartupAction::InstallSkill => install_skill(),
+        StartupAction::Diff { old, new, semantic } => {
+            process::exit(teex_lib::run_diff_cli(&old, &new, semantic))
+        }
+        StartupAction::Tasks { path } => process::exit(teex_lib::run_tasks_cli(&path)),
         StartupAction::UsageError(message) => {
             eprintln!("{message}\n");
             print_help_stderr();
@@ -54,6 +69,31 @@ fn parse_startup_action(args: Vec<String>) -> StartupAction {
                 "install-skill does not accept additional arguments".to_string(),
             );
         }
+
+        if first == "diff" {
+            let semantic = args[1..].iter().any(|arg| arg == "--semantic");
+            let paths: Vec<&String> = args[1..]
+                .iter()
+                .filter(|arg| *arg != "--semantic")
+                .collect();
+            return match paths[..] {
+                [old, new] if !old.starts_with('-') && !new.starts_with('-') => {
+                    StartupAction::Diff {
+                        old: old.clone(),
+                        new: new.clone(),
+                        semantic,
+                    }
+                }
+                _ => StartupAction::UsageError("diff expects exactly two paths".to_string()),
+            };
+        }
+
+        if first == "tasks" {
+            return match &args[1..] {
+                [path] if !path.starts_with('-') => StartupAction::Tasks { path: path.clone() },
+                _ => StartupAction::UsageError("tasks expects exactly one file".to_string()),
+            };
+        }
     }
 
     let mut wait = false;
@@ -163,13 +203,18 @@ Usage:
   teex [--wait] [FILES...]
   teex [--wait] [FOLDER]
   teex install-skill
+  teex diff [--semantic] OLD NEW
+  teex tasks FILE
   teex --help | -h
 
 Commands:
   install-skill    Install teex skill files for Claude Code and Codex
+  diff             Print a unified diff of two files or folders, no git needed
+  tasks            Print the task list progress of a Markdown file as JSON
 
 Options:
   --wait            Wait until the UI exits before returning
+  --semantic       With diff, compare JSON, YAML and TOML files by keys instead of lines
   -h, --help       Show this help and exit
 
 Examples:
@@ -177,11 +222,16 @@ Examples:
   teex report.md config.json
   teex /path/to/folder
   teex install-skill
+  teex diff plan-v1.md plan-v2.md
+  teex diff out-a/ out-b/
+  teex diff --semantic deploy-old.yaml deploy.yaml
+  teex tasks plan.md
 
 Notes:
   By default, teex launches the UI and returns immediately.
   Multiple files open in a single window with tabs.
   teex supports Markdown preview, syntax-highlighted code review, and Git-aware inspection.
+  teex diff exits with 0 when the inputs are identical, 1 when they differ and 2 on errors.
 "#
 }
 
@@ -0,0 +1,252 @@
+use super::*;
+use crate::markdown_outline::{
+    is_markdown_path, line_of, line_starts, parse_outline, parser_options, read_markdown,
+};
+use percent_encoding::percent_decode_str;
+use pulldown_cmark::{BrokenLink, Event as MdEvent, LinkType, Parser, Tag};
+use std::path::Component;
+
+const STATUS_OK: &str = "ok";
+const STATUS_BROKEN: &str = "broken";
+const STATUS_EXTERNAL: &str = "external";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MarkdownLink {
+    /// 1-based line of the link or image.
+    pub(crate) line: usize,
+    /// 1-based column, counted in characters.
+    pub(crate) column: usize,
+    /// `"link"` or `"image"`.
+    pub(crate) kind: &'static str,
+    /// The destination as written.
+    pub(crate) target: String,
+    /// `"ok"`, `"broken"` or `"external"`. External URLs are never fetched.
+    pub(crate) status: &'static str,
+    /// The local file the link resolves to, whether or not it exists.
+    pub(crate) path: Option<String>,
+    /// Why a broken link is broken.
+    pub(crate) message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LinkReport {
+    pub(crate) links: Vec<MarkdownLink>,
+    pub(crate) broken: usize,
+    pub(crate) external: usize,
+}
+
+enum LinkTarget {
+    External,
+    /// `#anchor` in the document itself.
+    Anchor(String),
+    Local {
+        path: PathBuf,
+        anchor: Option<String>,
+    },
+}
+
+/// `scheme:` prefix of a URL. Single letters are left alone so `C:\` stays
+/// a path.
+fn has_scheme(target: &str) -> bool {
+    let Some((scheme, _)) = target.split_once(':') else {
+        return false;
+    };
+    scheme.len() > 1
+        && scheme.starts_with(|ch: char| ch.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '+' | '-' | '.'))
+}
+
+/// Join `relative` onto `base` and drop `.` and `..` without touching the
+/// file system, the way the preview resolves image paths.
+fn join_lexically(base: &Path, relative: &Path) -> PathBuf {
+    let mut joined = PathBuf::new();
+    for component in base.join(relative).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                joined.pop();
+            }
+            other => joined.push(other),
+        }
+    }
+    joined
+}
+
+fn decode(text: &str) -> String {
+    percent_decode_str(text).decode_utf8_lossy().into_owned()
+}
+
+fn classify(target: &str, document: &Path) -> LinkTarget {
+    let target = target.trim();
+    let local = if let Some(rest) = target.strip_prefix("file://") {
+        rest
+    } else if target.starts_with("//") || has_scheme(target) {
+        return LinkTarget::External;
+    } else {
+        target
+    };
+
+    let (location, anchor) = match local.split_once('#') {
+        Some((location, anchor)) => (location, Some(decode(anchor))),
+        None => (local, None),
+    };
+    let location = location.split('?').next().unwrap_or_default();
+    if location.is_empty() {
+        return LinkTarget::Anchor(anchor.unwrap_or_default());
+    }
+    let base = document.parent().unwrap_or(Path::new("/"));
+    LinkTarget::Local {
+        path: join_lexically(base, Path::new(&decode(location))),
+        anchor,
+    }
+}
+
+fn heading_slugs(content: &str) -> Vec<String> {
+    parse_outline(content)
+        .outline(None)
+        .headings
+        .into_iter()
+        .map(|heading| heading.slug)
+        .collect()
+}
+
+fn has_anchor(slugs: &[String], anchor: &str) -> bool {
+    anchor.is_empty() || slugs.iter().any(|slug| *slug == anchor.to_lowercase())
+}
+
+/// Check every link and image of `content`, the text of the Markdown file
+/// at `document`. Anchors into other Markdown files are checked against
+/// their headings; anchors into other files are not checked.
+pub(crate) fn check_links(content: &str, document: &Path) -> LinkReport {
+    let starts = line_starts(content);
+    let position = |offset: usize| {
+        let line = line_of(&starts, offset);
+        let column = content[starts[line - 1]..offset].chars().count() + 1;
+        (line, column)
+    };
+    let own_slugs = heading_slugs(content);
+    let mut other_slugs: HashMap<PathBuf, Vec<String>> = HashMap::new();
+    let mut links = Vec::new();
+
+    let mut undefined: Vec<(usize, String)> = Vec::new();
+    let callback = |link: BrokenLink| {
+        // `[text]` alone is too common in prose to count as a reference.
+        if matches!(link.link_type, LinkType::Reference | LinkType::Collapsed) {
+            undefined.push((link.span.start, link.reference.to_string()));
+        }
+        None
+    };
+    let parser = Parser::new_with_broken_link_callback(content, parser_options(), Some(callback));
+    for (event, range) in parser.into_offset_iter() {
+        let (kind, target, link_type) = match event {
+            MdEvent::Start(Tag::Link {
+                link_type,
+                dest_url,
+                ..
+            }) => ("link", dest_url, link_type),
+            MdEvent::Start(Tag::Image {
+                link_type,
+                dest_url,
+                ..
+            }) => ("image", dest_url, link_type),
+            _ => continue,
+        };
+        let (line, column) = position(range.start);
+        let mut link = MarkdownLink {
+            line,
+            column,
+            kind,
+            target: target.to_string(),
+            status: STATUS_OK,
+            path: None,
+            message: None,
+        };
+        // `<user@example.com>` comes through without its `mailto:`.
+        let resolved = if link_type == LinkType::Email {
+            LinkTarget::External
+        } else {
+            classify(&target, document)
+        };
+        match resolved {
+            LinkTarget::External => link.status = STATUS_EXTERNAL,
+            LinkTarget::Anchor(anchor) => {
+                if !has_anchor(&own_slugs, &anchor) {
+                    link.status = STATUS_BROKEN;
+                    link.message = Some(format!("No heading #{anchor} in this file"));
+                }
+            }
+            LinkTarget::Local { path, anchor } => {
+                link.path = Some(path_to_string(&path));
+                let name = path_to_string(path.file_name().map_or(path.as_path(), Path::new));
+                if !path.exists() {
+                    link.status = STATUS_BROKEN;
+                    link.message = Some(format!("{name} does not exist"));
+                } else if kind == "image" && !path.is_file() {
+                    link.status = STATUS_BROKEN;
+                    link.message = Some(format!("{name} is not a file"));
+                } else if let Some(anchor) = anchor.filter(|_| is_markdown_path(&path)) {
+                    let slugs = if path == document {
+                        &own_slugs
+                    } else {
+                        other_slugs.entry(path.clone()).or_insert_with(|| {
+                            read_markdown(&path)
+                                .map(|text| heading_slugs(&text))
+                                .unwrap_or_default()
+                        })
+                    };
+                    if !has_anchor(slugs, &anchor) {
+                        link.status = STATUS_BROKEN;
+                        link.message = Some(format!("No heading #{anchor} in {name}"));
+                    }
+                }
+            }
+        }
+        links.push(link);
+    }
+
+    for (offset, reference) in undefined {
+        let (line, column) = position(offset);
+        links.push(MarkdownLink {
+            line,
+            column,
+            kind: "link",
+            target: reference.clone(),
+            status: STATUS_BROKEN,
+            path: None,
+            message: Some(format!("Undefined link reference [{reference}]")),
+        });
+    }
+    links.sort_by_key(|link| (link.line, link.column));
+
+    LinkReport {
+        broken: links
+            .iter()
+            .filter(|link| link.status == STATUS_BROKEN)
+            .count(),
+        external: links
+            .iter()
+            .filter(|link| link.status == STATUS_EXTERNAL)
+            .count(),
+        links,
+    }
+}
+
+/// Resolve the relative file links, heading anchors and image paths of a
+/// Markdown file and report the broken ones. Pass `content` for unsaved
+/// text; otherwise `path` is read from disk.
+#[tauri::command]
+pub(crate) fn check_markdown_links(
+    path: String,
+    content: Option<String>,
+) -> Result<LinkReport, String> {
+    let document = PathBuf::from(&path);
+    let content = match content {
+        Some(content) => content,
+        None => read_markdown(&document)?,
+    };
+    Ok(check_links(&content, &document))
+}
@@ -0,0 +1,391 @@
+use super::*;
+use crate::files::file_version;
+use crate::languages::language_for_path;
+use crate::text_encoding::{decode_text, DecodedText};
+use pulldown_cmark::{Event as MdEvent, Options, Parser, Tag, TagEnd};
+
+/// Documents kept for incremental reparsing before the cache is cleared.
+const MAX_CACHED_OUTLINES: usize = 16;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TaskCounts {
+    pub(crate) total: usize,
+    pub(crate) done: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OutlineHeading {
+    pub(crate) level: u8,
+    pub(crate) text: String,
+    /// 1-based line the heading starts on.
+    pub(crate) line: usize,
+    /// GitHub-style anchor, unique within the document.
+    pub(crate) slug: String,
+    /// Task list items from the heading down to the next heading of the
+    /// same or a higher level, subsections included.
+    pub(crate) tasks: TaskCounts,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MarkdownOutline {
+    /// File version the outline was read at; `None` for unsaved content.
+    pub(crate) version: Option<String>,
+    pub(crate) headings: Vec<OutlineHeading>,
+    /// Task list items in the whole document.
+    pub(crate) tasks: TaskCounts,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OutlineChangedPayload {
+    path: String,
+    outline: MarkdownOutline,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RawHeading {
+    level: u8,
+    text: String,
+    line: usize,
+    offset: usize,
+    /// An ATX heading outside any container. Nothing after it changes how
+    /// the text before it parses, so reparsing can start here.
+    restart: bool,
+}
+
+/// Headings and task markers of a document, before slugs and section
+/// counts are assigned.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ParsedOutline {
+    headings: Vec<RawHeading>,
+    /// Line and checked state of every task list marker.
+    tasks: Vec<(usize, bool)>,
+    /// Link reference definitions can change heading text anywhere in the
+    /// document, so their presence turns off incremental reparsing.
+    has_references: bool,
+}
+
+pub(crate) fn parser_options() -> Options {
+    Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS
+}
+
+/// Byte offset of the start of every line.
+pub(crate) fn line_starts(content: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(index, _)| index + 1))
+        .collect()
+}
+
+/// 1-based line of the byte `offset`, given the `line_starts`.
+pub(crate) fn line_of(starts: &[usize], offset: usize) -> usize {
+    starts.partition_point(|&start| start <= offset)
+}
+
+fn is_atx_line(content: &str, offset: usize) -> bool {
+    let line = content[offset..].trim_start_matches(' ');
+    let indent = content.len() - offset - line.len();
+    let rest = line.trim_start_matches('#');
+    let hashes = line.len() - rest.len();
+    indent <= 3
+        && (1..=6).contains(&hashes)
+        && (rest.is_empty() || rest.starts_with([' ', '\t', '\r', '\n']))
+}
+
+/// Parse `content` as if it began on line `first_line` at byte
+/// `first_offset` of the document.
+fn parse_from(content: &str, first_line: usize, first_offset: usize) -> ParsedOutline {
+    let starts = line_starts(content);
+    let mut parsed = ParsedOutline::default();
+    let mut depth = 0usize;
+    let mut current: Option<RawHeading> = None;
+
+    let mut events = Parser::new_ext(content, parser_options()).into_offset_iter();
+    for (event, range) in events.by_ref() {
+        match event {
+            MdEvent::Start(Tag::Heading { level, .. }) => {
+                current = Some(RawHeading {
+                    level: level as u8,
+                    text: String::new(),
+                    line: first_line + line_of(&starts, range.start) - 1,
+                    offset: first_offset + range.start,
+                    restart: depth == 0 && is_atx_line(content, range.start),
+                });
+                depth += 1;
+            }
+            MdEvent::End(TagEnd::Heading(_)) => {
+                depth -= 1;
+                if let Some(mut heading) = current.take() {
+                    heading.text = heading.text.trim().to_string();
+                    parsed.headings.push(heading);
+                }
+            }
+            MdEvent::Start(_) => depth += 1,
+            MdEvent::End(_) => depth = depth.saturating_sub(1),
+            MdEvent::Text(text) | MdEvent::Code(text) | MdEvent::InlineMath(text) => {
+                if let Some(heading) = current.as_mut() {
+                    heading.text.push_str(&text);
+                }
+            }
+            MdEvent::SoftBreak | MdEvent::HardBreak => {
+                if let Some(heading) = current.as_mut() {
+                    heading.text.push(' ');
+                }
+            }
+            MdEvent::TaskListMarker(checked) => {
+                let line = first_line + line_of(&starts, range.start) - 1;
+                parsed.tasks.push((line, checked));
+            }
+            _ => {}
+        }
+    }
+    parsed.has_references = events.reference_definitions().iter().next().is_some();
+    parsed
+}
+
+pub(crate) fn parse_outline(content: &str) -> ParsedOutline {
+    parse_from(content, 1, 0)
+}
+
+/// Reparse `new` after it replaced `old`, keeping everything before the
+/// last restartable heading above the first changed line.
+pub(crate) fn reparse_outline(previous: &ParsedOutline, old: &str, new: &str) -> ParsedOutline {
+    if previous.has_references {
+        return parse_outline(new);
+    }
+    let common = old
+        .bytes()
+        .zip(new.bytes())
+        .take_while(|(a, b)| a == b)
+        .count();
+    if common == old.len() && common == new.len() {
+        return previous.clone();
+    }
+    let changed_line = new.as_bytes()[..common]
+        .iter()
+        .filter(|&&byte| byte == b'\n')
+        .count()
+        + 1;
+    let Some(restart) = previous
+        .headings
+        .iter()
+        .rposition(|heading| heading.restart && heading.line < changed_line)
+    else {
+        return parse_outline(new);
+    };
+
+    let anchor = &previous.headings[restart];
+    let suffix = parse_from(&new[anchor.offset..], anchor.line, anchor.offset);
+    if suffix.has_references {
+        return parse_outline(new);
+    }
+    let mut headings = previous.headings[..restart].to_vec();
+    headings.extend(suffix.headings);
+    let mut tasks: Vec<(usize, bool)> = previous
+        .tasks
+        .iter()
+        .copied()
+        .take_while(|(line, _)| *line < anchor.line)
+        .collect();
+    tasks.extend(suffix.tasks);
+    ParsedOutline {
+        headings,
+        tasks,
+        has_references: false,
+    }
+}
+
+/// Anchor in the style GitHub renders for headings: lowercase, spaces to
+/// dashes, punctuation other than `-` and `_` dropped.
+pub(crate) fn heading_slug(text: &str) -> String {
+    text.trim()
+        .chars()
+        .filter_map(|ch| match ch {
+            ' ' => Some('-'),
+            '-' | '_' => Some(ch),
+            ch if ch.is_alphanumeric() => Some(ch),
+            _ => None,
+        })
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn count_tasks<'a>(tasks: impl Iterator<Item = &'a (usize, bool)>) -> TaskCounts {
+    tasks.fold(TaskCounts::default(), |counts, (_, done)| TaskCounts {
+        total: counts.total + 1,
+        done: counts.done + usize::from(*done),
+    })
+}
+
+impl ParsedOutline {
+    pub(crate) fn outline(&self, version: Option<String>) -> MarkdownOutline {
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        let headings = self
+            .headings
+            .iter()
+            .enumerate()
+            .map(|(index, heading)| {
+                let base = match heading_slug(&heading.text) {
+                    slug if slug.is_empty() => "section".to_string(),
+                    slug => slug,
+                };
+                let count = seen.entry(base.clone()).or_insert(0);
+                let slug = match *count {
+                    0 => base,
+                    n => format!("{base}-{n}"),
+                };
+                *count += 1;
+
+                let end = self.headings[index + 1..]
+                    .iter()
+                    .find(|next| next.level <= heading.level)
+                    .map_or(usize::MAX, |next| next.line);
+                let tasks = count_tasks(
+                    self.tasks
+                        .iter()
+                        .filter(|(line, _)| *line >= heading.line && *line < end),
+                );
+                OutlineHeading {
+                    level: heading.level,
+                    text: heading.text.clone(),
+                    line: heading.line,
+                    slug,
+                    tasks,
+                }
+            })
+            .collect();
+        MarkdownOutline {
+            version,
+            headings,
+            tasks: count_tasks(self.tasks.iter()),
+        }
+    }
+}
+
+struct CachedOutline {
+    content: String,
+    version: Option<String>,
+    parsed: ParsedOutline,
+}
+
+/// The last parse of each outlined document, so edits and file change
+/// events only reparse from the first changed section.
+pub(crate) struct OutlineCache {
+    documents: Mutex<HashMap<PathBuf, CachedOutline>>,
+}
+
+impl OutlineCache {
+    pub(crate) fn new() -> Self {
+        Self {
+            documents: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn update(
+        &self,
+        path: &Path,
+        content: String,
+        version: Option<String>,
+    ) -> Result<MarkdownOutline, String> {
+        let mut documents = self
+            .documents
+            .lock()
+            .map_err(|_| "Unable to read outline cache".to_string())?;
+        let parsed = match documents.get(path) {
+            Some(cached) if version.is_some() && cached.version == version => {
+                return Ok(cached.parsed.outline(version));
+            }
+            Some(cached) => reparse_outline(&cached.parsed, &cached.content, &content),
+            None => parse_outline(&content),
+        };
+        let outline = parsed.outline(version.clone());
+        if documents.len() >= MAX_CACHED_OUTLINES && !documents.contains_key(path) {
+            documents.clear();
+        }
+        documents.insert(
+            path.to_path_buf(),
+            CachedOutline {
+                content,
+                version,
+                parsed,
+            },
+        );
+        Ok(outline)
+    }
+
+    fn is_cached(&self, path: &Path) -> bool {
+        self.documents
+            .lock()
+            .map(|documents| documents.contains_key(path))
+            .unwrap_or(false)
+    }
+}
+
+pub(crate) fn is_markdown_path(path: &Path) -> bool {
+    language_for_path(path).is_some_and(|language| language.kind == "markdown")
+}
+
+pub(crate) fn read_markdown(path: &Path) -> Result<String, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Unable to read file: {e}"))?;
+    let DecodedText { content, .. } = decode_text(&bytes, None)?;
+    Ok(content)
+}
+
+fn read_markdown_text(path: &Path) -> Result<(String, String), String> {
+    let bytes = fs::read(path).map_err(|e| format!("Unable to read file: {e}"))?;
+    let metadata = fs::metadata(path).map_err(|e| format!("Unable to read file metadata: {e}"))?;
+    let version = file_version(&bytes, &metadata);
+    let DecodedText { content, .. } = decode_text(&bytes, None)?;
+    Ok((content, version))
+}
+
+/// Emit a fresh outline for a watched file that changed on disk, if its
+/// outline was requested before.
+pub(crate) fn emit_outline_for_changed_file(app: &tauri::AppHandle, label: &str, path: &Path) {
+    let cache = app.state::<OutlineCache>();
+    if !cache.is_cached(path) {
+        return;
+    }
+    let Ok((content, version)) = read_markdown_text(path) else {
+        return;
+    };
+    if let Ok(outline) = cache.update(path, content, Some(version)) {
+        emit_to_window(
+            app,
+            label,
+            EVENT_MARKDOWN_OUTLINE_CHANGED,
+            OutlineChangedPayload {
+                path: path_to_string(path),
+                outline,
+            },
+        );
+    }
+}
+
+/// Headings with levels, lines, slugs and task counts for a Markdown
+/// document. Pass `content` for unsaved text; otherwise `path` is read from
+/// disk. Either way the parse is cached by `path` and later calls reparse
+/// only what changed.
+#[tauri::command]
+pub(crate) fn markdown_outline(
+    cache: tauri::State<OutlineCache>,
+    path: Option<String>,
+    content: Option<String>,
+) -> Result<MarkdownOutline, String> {
+    // Keyed like the file watcher's paths so change events find the entry.
+    let path = path.map(|path| fs::canonicalize(&path).unwrap_or_else(|_| PathBuf::from(path)));
+    match (path, content) {
+        (Some(path), Some(content)) => cache.update(&path, content, None),
+        (Some(path), None) => {
+            let (content, version) = read_markdown_text(&path)?;
+            cache.update(&path, content, Some(version))
+        }
+        (None, Some(content)) => Ok(parse_outline(&content).outline(None)),
+        (None, None) => Err("Pass a path or content to outline".to_string()),
+    }
+}
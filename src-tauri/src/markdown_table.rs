@@ -0,0 +1,389 @@
+use super::*;
+use crate::table_view::{parse_table, TableQuery};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Alignment {
+    None,
+    Left,
+    Center,
+    Right,
+}
+
+/// A GitHub-flavoured pipe table. The first row is the header.
+struct PipeTable {
+    /// Indentation or blockquote markers written before every row.
+    prefix: String,
+    alignments: Vec<Alignment>,
+    rows: Vec<Vec<String>>,
+}
+
+/// A table found in a Markdown document, as a range of line indices.
+struct TableSpan {
+    start: usize,
+    end: usize,
+    table: PipeTable,
+}
+
+/// Columns `c` takes in a monospace font: East Asian wide and fullwidth
+/// characters and emoji take two, combining marks and zero-width
+/// characters none.
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x0300..=0x036F | 0x200B..=0x200F | 0x20D0..=0x20FF | 0xFE00..=0xFE0F | 0xFE20..=0xFE2F => {
+            0
+        }
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+pub(crate) fn display_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// The line without its `\n` or `\r\n`, and the line ending.
+fn split_line_ending(line: &str) -> (&str, &str) {
+    let body = line.trim_end_matches(['\n', '\r']);
+    (body, &line[body.len()..])
+}
+
+/// Trimmed cells of a row. `\|` is kept in the cell rather than starting a
+/// new one; the outer pipes are optional.
+fn split_row(row: &str) -> Vec<String> {
+    let row = row.trim();
+    let inner = row.strip_prefix('|').unwrap_or(row);
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut escaped = false;
+    let mut ends_with_pipe = false;
+    for c in inner.chars() {
+        ends_with_pipe = false;
+        if escaped {
+            cell.push(c);
+            escaped = false;
+        } else if c == '|' {
+            cells.push(cell.trim().to_string());
+            cell.clear();
+            ends_with_pipe = true;
+        } else {
+            escaped = c == '\\';
+            cell.push(c);
+        }
+    }
+    if !ends_with_pipe {
+        cells.push(cell.trim().to_string());
+    }
+    cells
+}
+
+fn delimiter_alignments(row: &str) -> Option<Vec<Alignment>> {
+    split_row(row)
+        .iter()
+        .map(|cell| {
+            let left = cell.starts_with(':');
+            let right = cell.len() > 1 && cell.ends_with(':');
+            let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+            if dashes.is_empty() || !dashes.chars().all(|c| c == '-') {
+                return None;
+            }
+            Some(match (left, right) {
+                (true, true) => Alignment::Center,
+                (true, false) => Alignment::Left,
+                (false, true) => Alignment::Right,
+                (false, false) => Alignment::None,
+            })
+        })
+        .collect()
+}
+
+/// `line` without the table's `prefix`. Blockquote markers may lose their
+/// trailing space on otherwise identical lines.
+fn strip_table_prefix<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+    line.strip_prefix(prefix)
+        .or_else(|| line.strip_prefix(prefix.trim_end()))
+}
+
+/// The table whose header is on line `start`, if there is one.
+fn table_at(lines: &[&str], start: usize) -> Option<TableSpan> {
+    let (header, _) = split_line_ending(lines[start]);
+    let prefix_len = header.len() - header.trim_start_matches([' ', '\t', '>']).len();
+    let (prefix, header) = header.split_at(prefix_len);
+    if !header.contains('|') {
+        return None;
+    }
+    let (delimiter, _) = split_line_ending(lines.get(start + 1)?);
+    let delimiter = strip_table_prefix(delimiter, prefix)?;
+    if !delimiter.contains('|') {
+        return None;
+    }
+    let alignments = delimiter_alignments(delimiter)?;
+    let header = split_row(header);
+    if header.len() != alignments.len() {
+        return None;
+    }
+
+    let mut rows = vec![header];
+    let mut end = start + 2;
+    while let Some(line) = lines.get(end) {
+        let (line, _) = split_line_ending(line);
+        match strip_table_prefix(line, prefix) {
+            Some(row) if !row.trim().is_empty() && row.contains('|') => rows.push(split_row(row)),
+            _ => break,
+        }
+        end += 1;
+    }
+    Some(TableSpan {
+        start,
+        end,
+        table: PipeTable {
+            prefix: prefix.to_string(),
+            alignments,
+            rows,
+        },
+    })
+}
+
+/// The character and length of a ``` or ~~~ code fence.
+fn code_fence(line: &str) -> Option<(char, usize)> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let length = trimmed.chars().take_while(|c| *c == marker).count();
+    (length >= 3).then_some((marker, length))
+}
+
+/// Pipe tables outside fenced code blocks.
+fn find_tables(lines: &[&str]) -> Vec<TableSpan> {
+    let mut tables = Vec::new();
+    let mut fence: Option<(char, usize)> = None;
+    let mut index = 0;
+    while index < lines.len() {
+        let (line, _) = split_line_ending(lines[index]);
+        if let Some((marker, length)) = code_fence(line) {
+            fence = match fence {
+                None => Some((marker, length)),
+                Some((open, open_length)) if open == marker && length >= open_length => None,
+                open => open,
+            };
+        } else if fence.is_none() {
+            if let Some(span) = table_at(lines, index) {
+                index = span.end;
+                tables.push(span);
+                continue;
+            }
+        }
+        index += 1;
+    }
+    tables
+}
+
+impl PipeTable {
+    /// One line per row, columns padded to the widest cell. Rows with more
+    /// cells than the header widen the table rather than losing cells.
+    fn render(&self) -> Vec<String> {
+        let columns = self
+            .rows
+            .iter()
+            .map(Vec::len)
+            .chain([self.alignments.len()])
+            .max()
+            .unwrap_or(0);
+        let alignment = |column: usize| {
+            self.alignments
+                .get(column)
+                .copied()
+                .unwrap_or(Alignment::None)
+        };
+        let mut widths = vec![3; columns];
+        for row in &self.rows {
+            for (column, cell) in row.iter().enumerate() {
+                widths[column] = widths[column].max(display_width(cell));
+            }
+        }
+
+        let render_row = |cells: Vec<String>| format!("{}| {} |", self.prefix, cells.join(" | "));
+        let pad = |cell: &str, column: usize| {
+            let space = widths[column] - display_width(cell);
+            let (before, after) = match alignment(column) {
+                Alignment::Right => (space, 0),
+                Alignment::Center => (space / 2, space - space / 2),
+                Alignment::None | Alignment::Left => (0, space),
+            };
+            format!("{}{cell}{}", " ".repeat(before), " ".repeat(after))
+        };
+
+        let mut lines = Vec::with_capacity(self.rows.len() + 1);
+        for (index, row) in self.rows.iter().enumerate() {
+            lines.push(render_row(
+                (0..columns)
+                    .map(|column| pad(row.get(column).map_or("", String::as_str), column))
+                    .collect(),
+            ));
+            if index == 0 {
+                lines.push(render_row(
+                    (0..columns)
+                        .map(|column| {
+                            let width = widths[column];
+                            match alignment(column) {
+                                Alignment::None => "-".repeat(width),
+                                Alignment::Left => format!(":{}", "-".repeat(width - 1)),
+                                Alignment::Right => format!("{}:", "-".repeat(width - 1)),
+                                Alignment::Center => format!(":{}:", "-".repeat(width - 2)),
+                            }
+                        })
+                        .collect(),
+                ));
+            }
+        }
+        lines
+    }
+}
+
+/// `content` with every pipe table aligned. Everything outside the tables,
+/// including each row's line ending, is left as written.
+pub(crate) fn format_markdown_tables(content: &str) -> Option<String> {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let mut formatted = String::with_capacity(content.len());
+    let mut next = 0;
+    for span in find_tables(&lines) {
+        lines[next..span.start]
+            .iter()
+            .for_each(|line| formatted.push_str(line));
+        for (line, rendered) in lines[span.start..span.end].iter().zip(span.table.render()) {
+            formatted.push_str(&rendered);
+            formatted.push_str(split_line_ending(line).1);
+        }
+        next = span.end;
+    }
+    lines[next..]
+        .iter()
+        .for_each(|line| formatted.push_str(line));
+    Some(formatted)
+}
+
+fn markdown_cell(text: &str) -> String {
+    text.trim()
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+fn csv_cell(text: &str) -> String {
+    text.replace("\\|", "|")
+        .replace("<br/>", "\n")
+        .replace("<br />", "\n")
+        .replace("<br>", "\n")
+}
+
+/// A Markdown table with the same rows as a CSV or TSV file. The header is
+/// detected like the table view does; numeric columns are right-aligned.
+pub(crate) fn csv_to_markdown(content: &str, path: &Path) -> Result<String, String> {
+    let page = parse_table(content, path, String::new()).page(&TableQuery {
+        limit: Some(usize::MAX),
+        ..TableQuery::default()
+    });
+    if page.columns.is_empty() {
+        return Err("No rows to convert".to_string());
+    }
+    let mut rows = vec![page
+        .columns
+        .iter()
+        .map(|column| markdown_cell(&column.name))
+        .collect::<Vec<_>>()];
+    rows.extend(
+        page.rows
+            .iter()
+            .map(|row| row.cells.iter().map(|cell| markdown_cell(cell)).collect()),
+    );
+    let table = PipeTable {
+        prefix: String::new(),
+        alignments: page
+            .columns
+            .iter()
+            .map(|column| match column.kind {
+                "integer" | "number" => Alignment::Right,
+                _ => Alignment::None,
+            })
+            .collect(),
+        rows,
+    };
+    Ok(table.render().join("\n") + "\n")
+}
+
+/// The Markdown table around 1-based `line`, or the first one in the
+/// document, as CSV with `delimiter`.
+pub(crate) fn markdown_to_csv(
+    content: &str,
+    line: Option<usize>,
+    delimiter: u8,
+) -> Result<String, String> {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let tables = find_tables(&lines);
+    let span = line
+        .and_then(|line| {
+            let index = line.checked_sub(1)?;
+            tables
+                .iter()
+                .find(|span| span.start <= index && index < span.end)
+        })
+        .or_else(|| tables.first())
+        .ok_or_else(|| "No Markdown table found".to_string())?;
+
+    let columns = span.table.rows.iter().map(Vec::len).max().unwrap_or(0);
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .terminator(csv::Terminator::Any(b'\n'))
+        .from_writer(Vec::new());
+    for row in &span.table.rows {
+        let cells =
+            (0..columns).map(|column| row.get(column).map_or(String::new(), |cell| csv_cell(cell)));
+        writer
+            .write_record(cells)
+            .map_err(|e| format!("Unable to write CSV: {e}"))?;
+    }
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| format!("Unable to write CSV: {e}"))?;
+    String::from_utf8(bytes).map_err(|e| format!("Unable to write CSV: {e}"))
+}
+
+/// Convert CSV or TSV text to a Markdown table. `path` only helps guess the
+/// delimiter.
+#[tauri::command]
+pub(crate) fn csv_to_markdown_table(
+    content: String,
+    path: Option<String>,
+) -> Result<String, String> {
+    csv_to_markdown(&content, Path::new(path.as_deref().unwrap_or("")))
+}
+
+/// Convert the Markdown table at `line` (1-based), or the first table, to
+/// CSV. `delimiter` defaults to a comma.
+#[tauri::command]
+pub(crate) fn markdown_table_to_csv(
+    content: String,
+    line: Option<usize>,
+    delimiter: Option<String>,
+) -> Result<String, String> {
+    let delimiter = match delimiter.as_deref() {
+        None | Some("") => b',',
+        Some(value) if value.len() == 1 && value.is_ascii() => value.as_bytes()[0],
+        Some(value) => return Err(format!("Unsupported delimiter: {value:?}")),
+    };
+    markdown_to_csv(&content, line, delimiter)
+}
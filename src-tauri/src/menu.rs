@@ -59,6 +59,13 @@ pub(crate) fn build_app_menu(
         true,
         Some("CmdOrCtrl+Shift+R"),
     )?;
+    let reopen_with_encoding_item = MenuItem::with_id(
+        app,
+        MENU_REOPEN_WITH_ENCODING,
+        "Reopen with Encoding...",
+        true,
+        None::<&str>,
+    )?;
     let close_active_file_item = MenuItem::with_id(
         app,
         MENU_CLOSE_ACTIVE_FILE,
@@ -87,6 +94,13 @@ pub(crate) fn build_app_menu(
         true,
         Some("CmdOrCtrl+E"),
     )?;
+    let toggle_table_view_item = MenuItem::with_id(
+        app,
+        MENU_TOGGLE_TABLE_VIEW,
+        "Toggle CSV Table View",
+        true,
+        None::<&str>,
+    )?;
     let toggle_status_bar_item = MenuItem::with_id(
         app,
         MENU_TOGGLE_STATUS_BAR,
@@ -102,6 +116,14 @@ pub(crate) fn build_app_menu(
         true,
         Some("CmdOrCtrl+Shift+."),
     )?;
+    let show_ignored_files_item = CheckMenuItem::with_id(
+        app,
+        MENU_SHOW_IGNORED_FILES,
+        "Show Ignored Files",
+        true,
+        false,
+        None::<&str>,
+    )?;
     let show_modified_only_item = CheckMenuItem::with_id(
         app,
         MENU_SHOW_MODIFIED_ONLY,
@@ -147,6 +169,7 @@ pub(crate) fn build_app_menu(
             &recent_submenu,
             &PredefinedMenuItem::separator(app)?,
             &restore_session_item,
+            &reopen_with_encoding_item,
             &PredefinedMenuItem::separator(app)?,
             &close_active_file_item,
             &close_window_item,
@@ -158,10 +181,41 @@ pub(crate) fn build_app_menu(
     let find_item = MenuItem::with_id(app, MENU_FIND, "Find", true, Some("CmdOrCtrl+F"))?;
     let format_file_item =
         MenuItem::with_id(app, MENU_FORMAT_FILE, "Format File", true, Some("Ctrl+F"))?;
+    let format_file_sort_keys_item = MenuItem::with_id(
+        app,
+        MENU_FORMAT_FILE_SORT_KEYS,
+        "Format File with Sorted Keys",
+        true,
+        None::<&str>,
+    )?;
+    let minify_file_item =
+        MenuItem::with_id(app, MENU_MINIFY_FILE, "Minify File", true, None::<&str>)?;
+    let copy_as_markdown_table_item = MenuItem::with_id(
+        app,
+        MENU_COPY_AS_MARKDOWN_TABLE,
+        "Copy as Markdown Table",
+        true,
+        None::<&str>,
+    )?;
+    let copy_table_as_csv_item = MenuItem::with_id(
+        app,
+        MENU_COPY_TABLE_AS_CSV,
+        "Copy Table as CSV",
+        true,
+        None::<&str>,
+    )?;
+    let undo_file_operation_item = MenuItem::with_id(
+        app,
+        MENU_UNDO_FILE_OPERATION,
+        "Undo Last File Operation",
+        true,
+        None::<&str>,
+    )?;
     let edit_submenu = SubmenuBuilder::new(app, "Edit")
         .items(&[
             &PredefinedMenuItem::undo(app, None)?,
             &PredefinedMenuItem::redo(app, None)?,
+            &undo_file_operation_item,
             &PredefinedMenuItem::separator(app)?,
             &PredefinedMenuItem::cut(app, None)?,
             &PredefinedMenuItem::copy(app, None)?,
@@ -170,6 +224,11 @@ pub(crate) fn build_app_menu(
             &PredefinedMenuItem::separator(app)?,
             &find_item,
             &format_file_item,
+            &format_file_sort_keys_item,
+            &minify_file_item,
+            &PredefinedMenuItem::separator(app)?,
+            &copy_as_markdown_table_item,
+            &copy_table_as_csv_item,
         ])
         .build()?;
 
@@ -178,12 +237,14 @@ pub(crate) fn build_app_menu(
             &toggle_sidebar_item,
             &toggle_status_bar_item,
             &show_hidden_files_item,
+            &show_ignored_files_item,
             &show_modified_only_item,
             &toggle_collapse_all_folders_item,
             &PredefinedMenuItem::separator(app)?,
             &unified_diff_item,
             &PredefinedMenuItem::separator(app)?,
             &toggle_markdown_mode_item,
+            &toggle_table_view_item,
             &PredefinedMenuItem::separator(app)?,
             &theme_submenu,
         ])
@@ -250,6 +250,11 @@ pub(super) fn handle_app_menu_event(app: &tauri::AppHandle, event: tauri::menu::
                 emit_to_window(app, window.label(), EVENT_TOGGLE_MARKDOWN_MODE, ());
             }
         }
+        MENU_TOGGLE_TABLE_VIEW => {
+            if let Some(window) = target_window(app) {
+                emit_to_window(app, window.label(), EVENT_TOGGLE_TABLE_VIEW, ());
+            }
+        }
         MENU_TOGGLE_STATUS_BAR => {
             if let Some(window) = target_window(app) {
                 emit_to_window(app, window.label(), EVENT_TOGGLE_STATUS_BAR, ());
@@ -258,6 +263,9 @@ pub(super) fn handle_app_menu_event(app: &tauri::AppHandle, event: tauri::menu::
         MENU_SHOW_HIDDEN_FILES => {
             let _ = app.emit(EVENT_TOGGLE_HIDDEN_FILES, ());
         }
+        MENU_SHOW_IGNORED_FILES => {
+            let _ = app.emit(EVENT_TOGGLE_IGNORED_FILES, ());
+        }
         MENU_SHOW_MODIFIED_ONLY => {
             let _ = app.emit(EVENT_TOGGLE_MODIFIED_ONLY, ());
         }
@@ -271,11 +279,41 @@ pub(super) fn handle_app_menu_event(app: &tauri::AppHandle, event: tauri::menu::
                 emit_to_window(app, window.label(), EVENT_FIND, ());
             }
         }
+        MENU_REOPEN_WITH_ENCODING => {
+            if let Some(window) = target_window(app) {
+                emit_to_window(app, window.label(), EVENT_REOPEN_WITH_ENCODING, ());
+            }
+        }
         MENU_FORMAT_FILE => {
             if let Some(window) = target_window(app) {
                 emit_to_window(app, window.label(), EVENT_FORMAT_FILE, ());
             }
         }
+        MENU_FORMAT_FILE_SORT_KEYS => {
+            if let Some(window) = target_window(app) {
+                emit_to_window(app, window.label(), EVENT_FORMAT_FILE, "sortKeys");
+            }
+        }
+        MENU_MINIFY_FILE => {
+            if let Some(window) = target_window(app) {
+                emit_to_window(app, window.label(), EVENT_FORMAT_FILE, "minify");
+            }
+        }
+        MENU_COPY_AS_MARKDOWN_TABLE => {
+            if let Some(window) = target_window(app) {
+                emit_to_window(app, window.label(), EVENT_COPY_TABLE, "markdown");
+            }
+        }
+        MENU_COPY_TABLE_AS_CSV => {
+            if let Some(window) = target_window(app) {
+                emit_to_window(app, window.label(), EVENT_COPY_TABLE, "csv");
+            }
+        }
+        MENU_UNDO_FILE_OPERATION => {
+            if let Some(window) = target_window(app) {
+                emit_to_window(app, window.label(), EVENT_UNDO_FILE_OPERATION, ());
+            }
+        }
         MENU_UNIFIED_DIFF => {
             let _ = app.emit(EVENT_TOGGLE_UNIFIED_DIFF, ());
         }
@@ -0,0 +1,238 @@
+use super::*;
+use crate::binary_files::binary_type_of;
+use crate::git::{DiffHunk, DiffLine, FileDiff};
+use crate::project_ignore::project_walk_builder;
+use crate::semantic_diff::{format_semantic_changes, semantic_diff_files};
+use crate::text_encoding::decode_text;
+use similar::{ChangeTag, TextDiff};
+use std::collections::BTreeSet;
+
+const CONTEXT_LINES: usize = 3;
+
+/// Line diff of two texts as unified-diff hunks with three lines of
+/// context, in the shape `git_diff_all` returns.
+pub(crate) fn text_diff_hunks(old: &str, new: &str) -> Vec<DiffHunk> {
+    let diff = TextDiff::from_lines(old, new);
+    let mut unified = diff.unified_diff();
+    unified.context_radius(CONTEXT_LINES);
+    unified
+        .iter_hunks()
+        .map(|hunk| DiffHunk {
+            header: hunk.header().to_string(),
+            lines: hunk
+                .iter_changes()
+                .map(|change| DiffLine {
+                    content: change
+                        .value()
+                        .trim_end_matches('\n')
+                        .trim_end_matches('\r')
+                        .to_string(),
+                    line_type: match change.tag() {
+                        ChangeTag::Insert => "added",
+                        ChangeTag::Delete => "removed",
+                        ChangeTag::Equal => "context",
+                    }
+                    .to_string(),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn read_side(path: Option<&Path>) -> Result<Option<Vec<u8>>, String> {
+    path.map(|path| fs::read(path).map_err(|e| format!("Unable to read {}: {e}", path.display())))
+        .transpose()
+}
+
+fn decode_side(bytes: Option<&[u8]>) -> Option<String> {
+    match bytes {
+        None => Some(String::new()),
+        Some(bytes) if binary_type_of(bytes).is_some() => None,
+        Some(bytes) => decode_text(bytes, None).ok().map(|decoded| decoded.content),
+    }
+}
+
+/// Diff one file pair where either side may be missing. Returns `None`
+/// for identical bytes. Binary files are flagged and have no hunks; text
+/// that only differs in its encoding or BOM has no hunks either.
+fn diff_file_pair(
+    rel_path: &str,
+    old: Option<&Path>,
+    new: Option<&Path>,
+) -> Result<Option<FileDiff>, String> {
+    let old_bytes = read_side(old)?;
+    let new_bytes = read_side(new)?;
+    if old_bytes == new_bytes {
+        return Ok(None);
+    }
+    let status = match (&old_bytes, &new_bytes) {
+        (None, _) => "added",
+        (_, None) => "removed",
+        _ => "changed",
+    };
+    let (hunks, binary) = match (
+        decode_side(old_bytes.as_deref()),
+        decode_side(new_bytes.as_deref()),
+    ) {
+        (Some(old), Some(new)) => (text_diff_hunks(&old, &new), false),
+        _ => (Vec::new(), true),
+    };
+    Ok(Some(FileDiff {
+        rel_path: rel_path.to_string(),
+        status: status.to_string(),
+        hunks,
+        binary,
+    }))
+}
+
+/// Files under `root` relative to it, with `/` separators. Ignore rules,
+/// `.git` and other always-blocked folders are skipped like in the sidebar.
+fn relative_files(root: &Path) -> BTreeSet<String> {
+    project_walk_builder(root, true, false)
+        .build()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+        .filter_map(|entry| {
+            let rel = entry.path().strip_prefix(root).ok()?;
+            Some(
+                rel.components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/"),
+            )
+        })
+        .collect()
+}
+
+/// Compare two files, or two folders file by file. Folder results list
+/// every added, removed and changed file in path order; a file pair is
+/// reported under the name of `new`.
+pub(crate) fn diff_path_pair(old: &Path, new: &Path) -> Result<Vec<FileDiff>, String> {
+    match (old.is_dir(), new.is_dir()) {
+        (true, true) => {
+            let old_files = relative_files(old);
+            let new_files = relative_files(new);
+            let mut diffs = Vec::new();
+            for rel in old_files.union(&new_files) {
+                let old_path = old_files.contains(rel).then(|| old.join(rel));
+                let new_path = new_files.contains(rel).then(|| new.join(rel));
+                if let Some(diff) = diff_file_pair(rel, old_path.as_deref(), new_path.as_deref())? {
+                    diffs.push(diff);
+                }
+            }
+            Ok(diffs)
+        }
+        (false, false) => {
+            for path in [old, new] {
+                if !path.is_file() {
+                    return Err(format!("File was not found: {}", path.display()));
+                }
+            }
+            let name = new.file_name().unwrap_or_default().to_string_lossy();
+            Ok(diff_file_pair(&name, Some(old), Some(new))?
+                .into_iter()
+                .collect())
+        }
+        _ => Err("Cannot compare a file with a folder".to_string()),
+    }
+}
+
+/// Render diffs as unified diff text, as `diff -ru` would print them.
+pub(crate) fn format_unified_diff(diffs: &[FileDiff]) -> String {
+    let mut out = String::new();
+    for diff in diffs {
+        let old_name = if diff.status == "added" {
+            "/dev/null".to_string()
+        } else {
+            format!("a/{}", diff.rel_path)
+        };
+        let new_name = if diff.status == "removed" {
+            "/dev/null".to_string()
+        } else {
+            format!("b/{}", diff.rel_path)
+        };
+        if diff.binary {
+            out.push_str(&format!("Binary files {old_name} and {new_name} differ\n"));
+            continue;
+        }
+        out.push_str(&format!("--- {old_name}\n+++ {new_name}\n"));
+        for hunk in &diff.hunks {
+            out.push_str(&hunk.header);
+            out.push('\n');
+            for line in &hunk.lines {
+                let prefix = match line.line_type.as_str() {
+                    "added" => '+',
+                    "removed" => '-',
+                    _ => ' ',
+                };
+                out.push(prefix);
+                out.push_str(&line.content);
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+/// Compare two files or folders without git. See `diff_path_pair`.
+#[tauri::command]
+pub(crate) fn diff_paths(a: String, b: String) -> Result<Vec<FileDiff>, String> {
+    diff_path_pair(Path::new(&a), Path::new(&b))
+}
+
+/// The two files behind `diff`, an entry of `diff_path_pair(old, new)`.
+fn diff_sides(old: &Path, new: &Path, diff: &FileDiff) -> (Option<PathBuf>, Option<PathBuf>) {
+    let (old, new) = if old.is_dir() {
+        (old.join(&diff.rel_path), new.join(&diff.rel_path))
+    } else {
+        (old.to_path_buf(), new.to_path_buf())
+    };
+    (
+        (diff.status != "added").then_some(old),
+        (diff.status != "removed").then_some(new),
+    )
+}
+
+/// Like `format_unified_diff`, but JSON, YAML and TOML files are listed as
+/// structural changes. Files that differ only in formatting or key order
+/// are left out; files that fail to parse fall back to a line diff.
+fn format_semantic_diff(old: &Path, new: &Path, diffs: &[FileDiff]) -> String {
+    let mut out = String::new();
+    for diff in diffs {
+        let (old_side, new_side) = diff_sides(old, new, diff);
+        match semantic_diff_files(old_side.as_deref(), new_side.as_deref()) {
+            Ok(changes) if changes.is_empty() => {}
+            Ok(changes) => {
+                out.push_str(&format!("--- a/{0}\n+++ b/{0}\n", diff.rel_path));
+                out.push_str(&format_semantic_changes(&changes));
+            }
+            Err(_) => out.push_str(&format_unified_diff(std::slice::from_ref(diff))),
+        }
+    }
+    out
+}
+
+/// `teex diff [--semantic] A B`: print a unified diff, or a structural one
+/// with `semantic`, and return the exit code: 0 when identical, 1 when
+/// different and 2 on errors, like `diff(1)`.
+pub fn run_diff_cli(a: &str, b: &str, semantic: bool) -> i32 {
+    let (old, new) = (Path::new(a), Path::new(b));
+    let output = diff_path_pair(old, new).map(|diffs| {
+        if semantic {
+            format_semantic_diff(old, new, &diffs)
+        } else {
+            format_unified_diff(&diffs)
+        }
+    });
+    match output {
+        Ok(text) if text.is_empty() => 0,
+        Ok(text) => {
+            print!("{text}");
+            1
+        }
+        Err(err) => {
+            eprintln!("teex diff: {err}");
+            2
+        }
+    }
+}
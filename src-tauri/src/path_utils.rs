@@ -1,10 +1,12 @@
 use super::*;
+use crate::binary_files::{binary_type_for_extension, sniff_binary_type};
+use crate::languages::language_for_path;
 
-const ALWAYS_BLOCKED_DIRS: &[&str] = &[".git", "node_modules", "target", "dist", "build"];
+const ALWAYS_BLOCKED_DIRS: &[&str] = &[".git"];
 const ALWAYS_EXCLUDED_FILES: &[&str] = &[".DS_Store"];
 
 pub(super) fn should_traverse_with_hidden(entry: &DirEntry, show_hidden: bool) -> bool {
-    if !entry.file_type().is_dir() {
+    if !entry.file_type().is_some_and(|t| t.is_dir()) {
         return true;
     }
 
@@ -12,137 +14,62 @@ pub(super) fn should_traverse_with_hidden(entry: &DirEntry, show_hidden: bool) -
         return false;
     };
 
-    if ALWAYS_BLOCKED_DIRS.contains(&name) {
+    if is_always_blocked_dir(name) {
         return false;
     }
 
     show_hidden || !name.starts_with('.')
 }
 
-pub(super) fn is_dotfile_config(path: &Path) -> bool {
+pub(super) fn is_always_blocked_dir(name: &str) -> bool {
+    ALWAYS_BLOCKED_DIRS.contains(&name)
+}
+
+/// Whether a file shows up in the project sidebar. Every file is listed
+/// except OS clutter like `.DS_Store` and, unless `show_hidden`, dotfiles;
+/// binary files carry a badge from `project_file_binary_type`.
+pub(super) fn is_visible_project_file(path: &Path, show_hidden: bool) -> bool {
     let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
         return false;
     };
     if ALWAYS_EXCLUDED_FILES.contains(&name) {
         return false;
     }
-    name.starts_with('.') && path.extension().is_none()
+    show_hidden || !name.starts_with('.')
 }
 
-pub(super) fn file_kind(path: &Path) -> &'static str {
-    if is_markdown(path) {
-        "markdown"
-    } else if is_code(path) || is_named_code_file(path) {
-        "code"
-    } else {
-        "text"
+/// Badge for a binary project file, e.g. `"png"`. Known text files are
+/// trusted by name; everything else is sniffed by content.
+pub(super) fn project_file_binary_type(path: &Path) -> Option<&'static str> {
+    if is_text_like(path) || is_dotfile_config(path) {
+        return None;
     }
+    sniff_binary_type(path)
 }
 
-fn is_named_code_file(path: &Path) -> bool {
-    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
-        return false;
-    };
-    matches!(
-        name,
-        ".gitignore" | ".dockerignore" | ".gitattributes" | "Makefile"
-    ) || name == "Dockerfile"
-        || name.starts_with("Dockerfile.")
-        || name.ends_with(".Dockerfile")
+/// Like `project_file_binary_type`, but judged by extension alone so full
+/// project walks never open files. Binary files with unknown extensions go
+/// unbadged until their folder is listed.
+pub(super) fn project_file_binary_type_by_name(path: &Path) -> Option<&'static str> {
+    if is_text_like(path) || is_dotfile_config(path) {
+        return None;
+    }
+    binary_type_for_extension(path)
 }
 
-pub(super) fn is_code(path: &Path) -> bool {
-    let Some(ext) = path.extension().and_then(|ext| ext.to_str()) else {
+pub(super) fn is_dotfile_config(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
         return false;
     };
-
-    matches!(
-        ext.to_ascii_lowercase().as_str(),
-        "json"
-            | "jsonc"
-            | "jsonl"
-            | "yaml"
-            | "yml"
-            | "toml"
-            | "xml"
-            | "csv"
-            | "ini"
-            | "cfg"
-            | "conf"
-            | "js"
-            | "ts"
-            | "jsx"
-            | "tsx"
-            | "mjs"
-            | "cjs"
-            | "html"
-            | "css"
-            | "scss"
-            | "less"
-            | "rs"
-            | "py"
-            | "go"
-            | "java"
-            | "kt"
-            | "swift"
-            | "c"
-            | "cpp"
-            | "h"
-            | "hpp"
-            | "cc"
-            | "cs"
-            | "rb"
-            | "php"
-            | "lua"
-            | "zig"
-            | "sql"
-            | "graphql"
-            | "gql"
-            | "proto"
-            | "r"
-            | "m"
-            | "pl"
-            | "pm"
-            | "ex"
-            | "exs"
-            | "hs"
-            | "ml"
-            | "mli"
-            | "scala"
-            | "clj"
-            | "cljs"
-            | "dart"
-            | "v"
-            | "nim"
-            | "tf"
-            | "hcl"
-            | "el"
-            | "vim"
-            | "sh"
-            | "zsh"
-            | "bash"
-            | "dockerfile"
-            | "makefile"
-    )
-}
-
-pub(super) fn is_markdown(path: &Path) -> bool {
-    path.extension()
-        .and_then(|ext| ext.to_str())
-        .map(|ext| matches!(ext.to_ascii_lowercase().as_str(), "md" | "markdown"))
-        .unwrap_or(false)
+    if ALWAYS_EXCLUDED_FILES.contains(&name) {
+        return false;
+    }
+    name.starts_with('.') && path.extension().is_none()
 }
 
+/// Whether the file name or extension is a known text format.
 pub(super) fn is_text_like(path: &Path) -> bool {
-    if is_markdown(path) || is_code(path) || is_named_code_file(path) {
-        return true;
-    }
-
-    let Some(ext) = path.extension().and_then(|ext| ext.to_str()) else {
-        return false;
-    };
-
-    matches!(ext.to_ascii_lowercase().as_str(), "txt" | "rst" | "log")
+    language_for_path(path).is_some()
 }
 
 pub(super) fn path_to_string(path: &Path) -> String {
@@ -0,0 +1,160 @@
+use super::*;
+use crate::git::find_git_root;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::{Match, WalkBuilder};
+
+pub(crate) const TEEX_IGNORE_FILE: &str = ".teexignore";
+const GIT_IGNORE_FILE: &str = ".gitignore";
+
+/// Build a walker over a project root that honors `.gitignore`,
+/// `.git/info/exclude`, the global git excludes file and `.teexignore`.
+///
+/// Ignore rules apply even when the folder is not inside a git repository,
+/// so a bare `.gitignore` still hides build output. Parent directories are
+/// only consulted when the root is nested inside a repository. Hidden
+/// entries and `.git` are filtered through `should_traverse_with_hidden`.
+pub(crate) fn project_walk_builder(
+    root: &Path,
+    show_hidden: bool,
+    show_ignored: bool,
+) -> WalkBuilder {
+    let nested_in_repo = find_git_root(root).is_some_and(|git_root| git_root != root);
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .follow_links(false)
+        .hidden(false)
+        .ignore(false)
+        .parents(nested_in_repo)
+        .require_git(false)
+        .git_ignore(!show_ignored)
+        .git_exclude(!show_ignored)
+        .git_global(!show_ignored)
+        .filter_entry(move |entry| should_traverse_with_hidden(entry, show_hidden));
+    if !show_ignored {
+        builder.add_custom_ignore_filename(TEEX_IGNORE_FILE);
+    }
+    builder
+}
+
+pub(crate) fn is_ignore_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .map(|name| name == GIT_IGNORE_FILE || name == TEEX_IGNORE_FILE)
+        .unwrap_or(false)
+}
+
+/// Answers "is this path ignored?" for individual paths reported by the
+/// folder watcher, using the same rule sources as `project_walk_builder`.
+///
+/// Per-directory ignore files are loaded lazily and cached; call
+/// `invalidate` when one of them changes on disk.
+pub(crate) struct ProjectIgnore {
+    root: PathBuf,
+    top: PathBuf,
+    exclude: Gitignore,
+    global: Gitignore,
+    by_dir: Mutex<HashMap<PathBuf, Arc<Gitignore>>>,
+}
+
+impl ProjectIgnore {
+    pub(crate) fn new(root: &Path) -> Self {
+        let git_root = find_git_root(root);
+        let exclude = git_root
+            .as_ref()
+            .map(|git_root| {
+                let mut builder = GitignoreBuilder::new(git_root);
+                builder.add(git_root.join(".git").join("info").join("exclude"));
+                builder.build().unwrap_or_else(|_| Gitignore::empty())
+            })
+            .unwrap_or_else(Gitignore::empty);
+        let (global, _) = Gitignore::global();
+
+        Self {
+            root: root.to_path_buf(),
+            top: git_root.unwrap_or_else(|| root.to_path_buf()),
+            exclude,
+            global,
+            by_dir: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn invalidate(&self, ignore_file: &Path) {
+        let Some(dir) = ignore_file.parent() else {
+            return;
+        };
+        if let Ok(mut cache) = self.by_dir.lock() {
+            cache.remove(dir);
+        };
+    }
+
+    /// Returns true when `path` or any of its parent directories below the
+    /// project root is ignored. `.git` internals are always ignored.
+    pub(crate) fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        if relative.components().any(|c| c.as_os_str() == ".git") {
+            return true;
+        }
+
+        let mut current = self.root.clone();
+        let components: Vec<_> = relative.components().collect();
+        for (index, component) in components.iter().enumerate() {
+            current.push(component);
+            let current_is_dir = if index + 1 == components.len() {
+                is_dir
+            } else {
+                true
+            };
+            if self.matches_self(&current, current_is_dir) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn matches_self(&self, path: &Path, is_dir: bool) -> bool {
+        let mut dir = path.parent();
+        while let Some(current) = dir {
+            if let Some(matcher) = self.matcher_for_dir(current) {
+                match matcher.matched(path, is_dir) {
+                    Match::Ignore(_) => return true,
+                    Match::Whitelist(_) => return false,
+                    Match::None => {}
+                }
+            }
+            if current == self.top {
+                break;
+            }
+            dir = current.parent();
+        }
+
+        for matcher in [&self.exclude, &self.global] {
+            match matcher.matched(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+
+    fn matcher_for_dir(&self, dir: &Path) -> Option<Arc<Gitignore>> {
+        let mut cache = self.by_dir.lock().ok()?;
+        if let Some(existing) = cache.get(dir) {
+            return Some(Arc::clone(existing));
+        }
+
+        // `.teexignore` is added last so its rules take precedence.
+        let mut builder = GitignoreBuilder::new(dir);
+        for name in [GIT_IGNORE_FILE, TEEX_IGNORE_FILE] {
+            let candidate = dir.join(name);
+            if candidate.is_file() {
+                builder.add(candidate);
+            }
+        }
+        let matcher = Arc::new(builder.build().unwrap_or_else(|_| Gitignore::empty()));
+        cache.insert(dir.to_path_buf(), Arc::clone(&matcher));
+        Some(matcher)
+    }
+}
@@ -0,0 +1,548 @@
+use super::*;
+use crate::files::{file_version, same_file_version, write_file_atomically};
+use crate::local_history::{HistorySource, LocalHistory};
+use crate::project_ignore::project_walk_builder;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use regex::{NoExpand, Regex, RegexBuilder};
+use std::sync::atomic::AtomicBool;
+
+pub(crate) const DEFAULT_MAX_RESULTS: usize = 10_000;
+const MAX_SEARCH_FILE_BYTES: u64 = 10 * 1024 * 1024;
+const SEARCH_CHUNK_FILES: usize = 50;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct SearchOptions {
+    pub(crate) query: String,
+    pub(crate) is_regex: bool,
+    pub(crate) case_sensitive: bool,
+    pub(crate) whole_word: bool,
+    pub(crate) include: Vec<String>,
+    pub(crate) exclude: Vec<String>,
+    pub(crate) show_hidden: bool,
+    pub(crate) show_ignored: bool,
+    pub(crate) max_results: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SearchMatch {
+    pub(crate) line_number: usize,
+    pub(crate) line_text: String,
+    /// Half-open `[start, end)` character ranges of `line_text` that matched.
+    pub(crate) ranges: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FileSearchResult {
+    pub(crate) path: String,
+    pub(crate) rel_path: String,
+    pub(crate) matches: Vec<SearchMatch>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SearchResultsChunk {
+    pub(crate) request_id: String,
+    pub(crate) files: Vec<FileSearchResult>,
+    pub(crate) done: bool,
+    pub(crate) cancelled: bool,
+    pub(crate) truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ReplacementLine {
+    pub(crate) line_number: usize,
+    pub(crate) before: String,
+    pub(crate) after: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FileReplacement {
+    pub(crate) path: String,
+    pub(crate) rel_path: String,
+    pub(crate) replacements: usize,
+    pub(crate) lines: Vec<ReplacementLine>,
+    /// `version` token of the file when previewed, or after it was written.
+    pub(crate) version: String,
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ReplaceResult {
+    pub(crate) files: Vec<FileReplacement>,
+    pub(crate) total_replacements: usize,
+    pub(crate) applied: bool,
+}
+
+/// Cancellation flags for the search currently running in each window.
+/// Starting a new search flips the previous flag so its worker stops at the
+/// next file boundary.
+pub(crate) struct SearchRegistry {
+    by_window: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl SearchRegistry {
+    pub(crate) fn new() -> Self {
+        Self {
+            by_window: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn start(&self, label: &str) -> Arc<AtomicBool> {
+        let flag = Arc::new(AtomicBool::new(false));
+        if let Ok(mut searches) = self.by_window.lock() {
+            if let Some(previous) = searches.insert(label.to_string(), Arc::clone(&flag)) {
+                previous.store(true, Ordering::Relaxed);
+            }
+        };
+        flag
+    }
+
+    pub(crate) fn cancel(&self, label: &str) {
+        if let Ok(mut searches) = self.by_window.lock() {
+            if let Some(previous) = searches.remove(label) {
+                previous.store(true, Ordering::Relaxed);
+            }
+        };
+    }
+}
+
+pub(crate) fn build_search_regex(options: &SearchOptions) -> Result<Regex, String> {
+    if options.query.is_empty() {
+        return Err("Search query is empty".to_string());
+    }
+    let pattern = if options.is_regex {
+        options.query.clone()
+    } else {
+        regex::escape(&options.query)
+    };
+    let pattern = if options.whole_word {
+        format!(r"\b(?:{pattern})\b")
+    } else {
+        pattern
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!options.case_sensitive)
+        .build()
+        .map_err(|e| format!("Invalid search pattern: {e}"))
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<Option<GlobSet>, String> {
+    let patterns: Vec<&str> = patterns
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .collect();
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        // A bare name like `*.rs` or `target` should match at any depth.
+        let pattern = if pattern.contains('/') {
+            pattern.trim_start_matches('/').to_string()
+        } else {
+            format!("**/{pattern}")
+        };
+        for candidate in [pattern.clone(), format!("{pattern}/**")] {
+            let glob = Glob::new(&candidate).map_err(|e| format!("Invalid glob pattern: {e}"))?;
+            builder.add(glob);
+        }
+    }
+    builder
+        .build()
+        .map(Some)
+        .map_err(|e| format!("Invalid glob pattern: {e}"))
+}
+
+/// Include/exclude filter applied to root-relative paths.
+struct PathFilter {
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+}
+
+impl PathFilter {
+    fn new(options: &SearchOptions) -> Result<Self, String> {
+        Ok(Self {
+            include: build_glob_set(&options.include)?,
+            exclude: build_glob_set(&options.exclude)?,
+        })
+    }
+
+    fn allows(&self, rel_path: &Path) -> bool {
+        if self
+            .exclude
+            .as_ref()
+            .is_some_and(|set| set.is_match(rel_path))
+        {
+            return false;
+        }
+        self.include
+            .as_ref()
+            .is_none_or(|set| set.is_match(rel_path))
+    }
+}
+
+/// Read a file for searching, skipping anything too large or not UTF-8.
+fn read_searchable_text(path: &Path) -> Option<String> {
+    let metadata = fs::metadata(path).ok()?;
+    if metadata.len() > MAX_SEARCH_FILE_BYTES {
+        return None;
+    }
+    let bytes = fs::read(path).ok()?;
+    if bytes.contains(&0) {
+        return None;
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn byte_to_char_offset(text: &str, byte: usize) -> usize {
+    text[..byte].chars().count()
+}
+
+/// Split `content` into lines, each paired with its line terminator so the
+/// original line endings survive a replace round-trip.
+fn lines_with_endings(content: &str) -> impl Iterator<Item = (&str, &str)> {
+    content.split_inclusive('\n').map(|line| {
+        let body = line.trim_end_matches(['\n', '\r']);
+        (body, &line[body.len()..])
+    })
+}
+
+/// Match a single line, returning `None` when nothing (non-empty) matched.
+pub(crate) fn match_line(line_number: usize, line: &str, regex: &Regex) -> Option<SearchMatch> {
+    let ranges: Vec<(usize, usize)> = regex
+        .find_iter(line)
+        .filter(|m| !m.is_empty())
+        .map(|m| {
+            (
+                byte_to_char_offset(line, m.start()),
+                byte_to_char_offset(line, m.end()),
+            )
+        })
+        .collect();
+    if ranges.is_empty() {
+        return None;
+    }
+    Some(SearchMatch {
+        line_number,
+        line_text: line.to_string(),
+        ranges,
+    })
+}
+
+fn search_text(content: &str, regex: &Regex, budget: usize) -> Vec<SearchMatch> {
+    lines_with_endings(content)
+        .enumerate()
+        .filter_map(|(index, (line, _))| match_line(index + 1, line, regex))
+        .take(budget)
+        .collect()
+}
+
+/// Walk the project and report every file with at least one matching line.
+///
+/// `is_cancelled` is polled before each file. Returns true when the
+/// result cap was reached before the walk finished.
+pub(crate) fn search_project_files(
+    root: &Path,
+    options: &SearchOptions,
+    is_cancelled: impl Fn() -> bool,
+    mut emit: impl FnMut(FileSearchResult),
+) -> Result<bool, String> {
+    let regex = build_search_regex(options)?;
+    let filter = PathFilter::new(options)?;
+    let mut remaining = options.max_results.unwrap_or(DEFAULT_MAX_RESULTS).max(1);
+
+    for entry in project_walk_builder(root, options.show_hidden, options.show_ignored).build() {
+        if is_cancelled() {
+            break;
+        }
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let Ok(relative) = path.strip_prefix(root) else {
+            continue;
+        };
+        if !filter.allows(relative) {
+            continue;
+        }
+        let Some(content) = read_searchable_text(path) else {
+            continue;
+        };
+
+        let matches = search_text(&content, &regex, remaining);
+        if matches.is_empty() {
+            continue;
+        }
+        remaining -= matches.len();
+        emit(FileSearchResult {
+            path: path_to_string(path),
+            rel_path: relative.to_string_lossy().to_string(),
+            matches,
+        });
+        if remaining == 0 {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Search the project rooted at `root`, streaming `SearchResultsChunk`
+/// events to the calling window. Any search already running in the window
+/// is cancelled first. Returns the request id carried by every chunk.
+#[tauri::command]
+pub(crate) fn search_project(
+    window: tauri::Window,
+    root: String,
+    options: SearchOptions,
+) -> Result<String, String> {
+    let root_path = PathBuf::from(root);
+    if !root_path.is_dir() {
+        return Err("Selected path is not a folder".to_string());
+    }
+    // Surface pattern and glob errors synchronously instead of as events.
+    build_search_regex(&options)?;
+    PathFilter::new(&options)?;
+
+    let request_id = format!(
+        "project-search-{}",
+        NEXT_SEARCH_REQUEST_ID.fetch_add(1, Ordering::Relaxed)
+    );
+    let app = window.app_handle().clone();
+    let label = window.label().to_string();
+    let cancelled = app.state::<SearchRegistry>().start(&label);
+    let id = request_id.clone();
+
+    std::thread::spawn(move || {
+        let mut pending = Vec::new();
+        let truncated = search_project_files(
+            &root_path,
+            &options,
+            || cancelled.load(Ordering::Relaxed),
+            |result| {
+                pending.push(result);
+                if pending.len() >= SEARCH_CHUNK_FILES && !cancelled.load(Ordering::Relaxed) {
+                    let chunk = SearchResultsChunk {
+                        request_id: id.clone(),
+                        files: std::mem::take(&mut pending),
+                        done: false,
+                        cancelled: false,
+                        truncated: false,
+                    };
+                    emit_to_window(&app, &label, EVENT_PROJECT_SEARCH_RESULTS, chunk);
+                }
+            },
+        )
+        .unwrap_or(false);
+
+        let was_cancelled = cancelled.load(Ordering::Relaxed);
+        let last = SearchResultsChunk {
+            request_id: id,
+            files: if was_cancelled { Vec::new() } else { pending },
+            done: true,
+            cancelled: was_cancelled,
+            truncated,
+        };
+        emit_to_window(&app, &label, EVENT_PROJECT_SEARCH_RESULTS, last);
+    });
+
+    Ok(request_id)
+}
+
+#[tauri::command]
+pub(crate) fn cancel_project_search(window: tauri::Window) {
+    window
+        .app_handle()
+        .state::<SearchRegistry>()
+        .cancel(window.label());
+}
+
+/// Apply `regex` line by line, returning the new content, the number of
+/// replacements and the changed lines. Line endings are left untouched.
+fn replace_text(
+    content: &str,
+    regex: &Regex,
+    replacement: &str,
+    expand: bool,
+) -> (String, usize, Vec<ReplacementLine>) {
+    let mut output = String::with_capacity(content.len());
+    let mut count = 0;
+    let mut lines = Vec::new();
+
+    for (index, (line, ending)) in lines_with_endings(content).enumerate() {
+        let hits = regex.find_iter(line).filter(|m| !m.is_empty()).count();
+        if hits == 0 {
+            output.push_str(line);
+            output.push_str(ending);
+            continue;
+        }
+        let replaced = if expand {
+            regex.replace_all(line, replacement)
+        } else {
+            regex.replace_all(line, NoExpand(replacement))
+        };
+        count += hits;
+        lines.push(ReplacementLine {
+            line_number: index + 1,
+            before: line.to_string(),
+            after: replaced.to_string(),
+        });
+        output.push_str(&replaced);
+        output.push_str(ending);
+    }
+
+    (output, count, lines)
+}
+
+/// Preview or apply a project-wide replacement.
+///
+/// With `apply: false` nothing is written and the result lists every line
+/// that would change, with the version of each file. With `apply: true`
+/// each file is re-read and replaced atomically, with local history taken
+/// before and after; a failure in one file is reported on that file and
+/// does not stop the others. `versions` maps the paths of a preview to
+/// their versions: only those files are replaced, and any that changed
+/// since are skipped and reported. `paths` limits the operation to files
+/// picked from a preview. Regex searches expand `$1`-style groups in
+/// `replacement`.
+pub(crate) fn replace_in_project(
+    history: &LocalHistory,
+    root: &Path,
+    options: &SearchOptions,
+    replacement: &str,
+    paths: Option<&[String]>,
+    versions: Option<&HashMap<String, String>>,
+    apply: bool,
+) -> Result<ReplaceResult, String> {
+    if !root.is_dir() {
+        return Err("Selected path is not a folder".to_string());
+    }
+    let regex = build_search_regex(options)?;
+
+    let mut candidates = Vec::new();
+    search_project_files(
+        root,
+        &SearchOptions {
+            max_results: Some(usize::MAX),
+            ..options.clone()
+        },
+        || false,
+        |result| candidates.push(result),
+    )?;
+    if let Some(selected) = paths {
+        candidates.retain(|file| selected.contains(&file.path));
+    }
+    if let Some(versions) = versions {
+        candidates.retain(|file| versions.contains_key(&file.path));
+    }
+
+    let mut files = Vec::new();
+    let mut total_replacements = 0;
+    let mut current = Vec::new();
+    for candidate in candidates {
+        let path = PathBuf::from(&candidate.path);
+        let Some(content) = read_searchable_text(&path) else {
+            continue;
+        };
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue;
+        };
+        let mut version = file_version(content.as_bytes(), &metadata);
+        let (replaced, count, lines) =
+            replace_text(&content, &regex, replacement, options.is_regex);
+        let expected = versions.and_then(|versions| versions.get(&candidate.path));
+        if expected.is_some_and(|expected| !same_file_version(expected, &version)) {
+            continue;
+        }
+        current.push(candidate.path.clone());
+        if count == 0 {
+            continue;
+        }
+
+        let error = if apply {
+            history.record_file(&path, HistorySource::BeforeSave);
+            match write_file_atomically(&path, replaced.as_bytes()) {
+                Ok(()) => {
+                    history.record_file(&path, HistorySource::Save);
+                    if let Ok(metadata) = fs::metadata(&path) {
+                        version = file_version(replaced.as_bytes(), &metadata);
+                    }
+                    None
+                }
+                Err(e) => Some(format!("Unable to write file: {e}")),
+            }
+        } else {
+            None
+        };
+        if error.is_none() {
+            total_replacements += count;
+        }
+        files.push(FileReplacement {
+            path: candidate.path,
+            rel_path: candidate.rel_path,
+            replacements: count,
+            lines,
+            version,
+            error,
+        });
+    }
+
+    // Previewed files that changed, or no longer match, are left alone.
+    let mut changed: Vec<&String> = versions
+        .into_iter()
+        .flat_map(HashMap::keys)
+        .filter(|path| !current.contains(path))
+        .collect();
+    changed.sort();
+    for path in changed {
+        files.push(FileReplacement {
+            path: path.clone(),
+            rel_path: Path::new(path)
+                .strip_prefix(root)
+                .map(|relative| relative.to_string_lossy().to_string())
+                .unwrap_or_else(|_| path.clone()),
+            replacements: 0,
+            lines: Vec::new(),
+            version: String::new(),
+            error: Some("File changed since the preview".to_string()),
+        });
+    }
+
+    Ok(ReplaceResult {
+        files,
+        total_replacements,
+        applied: apply,
+    })
+}
+
+/// Preview or apply a project-wide replacement; see `replace_in_project`.
+#[tauri::command]
+pub(crate) fn replace_in_files(
+    history: tauri::State<LocalHistory>,
+    root: String,
+    options: SearchOptions,
+    replacement: String,
+    paths: Option<Vec<String>>,
+    versions: Option<HashMap<String, String>>,
+    apply: bool,
+) -> Result<ReplaceResult, String> {
+    replace_in_project(
+        &history,
+        Path::new(&root),
+        &options,
+        &replacement,
+        paths.as_deref(),
+        versions.as_ref(),
+        apply,
+    )
+}
@@ -0,0 +1,311 @@
+use super::*;
+use crate::project_ignore::{project_walk_builder, ProjectIgnore};
+use std::collections::HashSet;
+
+const DEFAULT_DIRECTORY_PAGE_SIZE: usize = 500;
+const DEFAULT_STREAM_CHUNK_SIZE: usize = 1000;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProjectEntry {
+    pub(crate) path: String,
+    pub(crate) rel_path: String,
+    pub(crate) is_dir: bool,
+    /// Type badge for binary files, e.g. `"png"`; `None` for text files
+    /// and folders.
+    pub(crate) binary_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DirectoryEntry {
+    pub(crate) path: String,
+    pub(crate) rel_path: String,
+    pub(crate) name: String,
+    pub(crate) is_dir: bool,
+    pub(crate) child_count: Option<usize>,
+    pub(crate) binary_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DirectoryPage {
+    pub(crate) entries: Vec<DirectoryEntry>,
+    pub(crate) total: usize,
+    pub(crate) offset: usize,
+    pub(crate) has_more: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProjectEntriesChunk {
+    pub(crate) request_id: String,
+    pub(crate) entries: Vec<ProjectEntry>,
+    pub(crate) done: bool,
+}
+
+fn project_entry(root: &Path, path: &Path, is_dir: bool) -> Option<ProjectEntry> {
+    let relative = path.strip_prefix(root).ok()?;
+    Some(ProjectEntry {
+        path: path_to_string(path),
+        rel_path: relative.to_string_lossy().to_string(),
+        is_dir,
+        binary_type: if is_dir {
+            None
+        } else {
+            project_file_binary_type_by_name(path).map(str::to_string)
+        },
+    })
+}
+
+/// Walk the project once, handing every visible file to `emit` as it is
+/// found, followed by directories that have no visible file descendants.
+///
+/// A directory is marked non-empty the first time one of its descendants is
+/// emitted, and marking stops at the first ancestor that was already marked,
+/// so empty-directory detection stays linear in the number of entries.
+fn walk_project_entries(
+    root: &Path,
+    show_hidden: bool,
+    show_ignored: bool,
+    mut emit: impl FnMut(ProjectEntry),
+) {
+    let mut all_dirs: Vec<PathBuf> = Vec::new();
+    let mut non_empty_dirs: HashSet<PathBuf> = HashSet::new();
+
+    for entry in project_walk_builder(root, show_hidden, show_ignored).build() {
+        let entry = match entry {
+            Ok(item) => item,
+            Err(_) => continue,
+        };
+
+        let path = entry.path();
+
+        if path.is_dir() {
+            if path != root {
+                all_dirs.push(path.to_path_buf());
+            }
+            continue;
+        }
+
+        if !is_visible_project_file(path, show_hidden) {
+            continue;
+        }
+
+        let Some(file_entry) = project_entry(root, path, false) else {
+            continue;
+        };
+
+        let mut ancestor = path.parent();
+        while let Some(dir) = ancestor {
+            if dir == root || !non_empty_dirs.insert(dir.to_path_buf()) {
+                break;
+            }
+            ancestor = dir.parent();
+        }
+
+        emit(file_entry);
+    }
+
+    // Include directories that have no visible file descendants
+    for dir in all_dirs {
+        if non_empty_dirs.contains(&dir) {
+            continue;
+        }
+        if let Some(dir_entry) = project_entry(root, &dir, true) {
+            emit(dir_entry);
+        }
+    }
+}
+
+#[tauri::command]
+pub(crate) fn list_project_entries(
+    root: String,
+    show_hidden: bool,
+    show_ignored: bool,
+) -> Result<Vec<ProjectEntry>, String> {
+    let root_path = PathBuf::from(root);
+
+    if !root_path.is_dir() {
+        return Err("Selected path is not a folder".to_string());
+    }
+
+    let mut entries = Vec::new();
+    walk_project_entries(&root_path, show_hidden, show_ignored, |entry| {
+        entries.push(entry)
+    });
+
+    entries.sort_by(|a, b| a.rel_path.cmp(&b.rel_path));
+
+    Ok(entries)
+}
+
+/// Stream the full project listing to the calling window in chunks of
+/// `ProjectEntriesChunk` events instead of returning one large Vec.
+///
+/// Returns the request id carried by every chunk; the last chunk has
+/// `done: true`. Entries arrive in walk order and are not sorted.
+#[tauri::command]
+pub(crate) fn stream_project_entries(
+    window: tauri::Window,
+    root: String,
+    show_hidden: bool,
+    show_ignored: bool,
+    chunk_size: Option<usize>,
+) -> Result<String, String> {
+    let root_path = PathBuf::from(root);
+    if !root_path.is_dir() {
+        return Err("Selected path is not a folder".to_string());
+    }
+
+    let request_id = format!(
+        "project-entries-{}",
+        NEXT_LISTING_REQUEST_ID.fetch_add(1, Ordering::Relaxed)
+    );
+    let chunk_size = chunk_size.unwrap_or(DEFAULT_STREAM_CHUNK_SIZE).max(1);
+    let app = window.app_handle().clone();
+    let label = window.label().to_string();
+    let id = request_id.clone();
+
+    std::thread::spawn(move || {
+        let mut pending = Vec::with_capacity(chunk_size);
+        walk_project_entries(&root_path, show_hidden, show_ignored, |entry| {
+            pending.push(entry);
+            if pending.len() >= chunk_size {
+                let chunk = ProjectEntriesChunk {
+                    request_id: id.clone(),
+                    entries: std::mem::take(&mut pending),
+                    done: false,
+                };
+                emit_to_window(&app, &label, EVENT_PROJECT_ENTRIES_CHUNK, chunk);
+            }
+        });
+        let last = ProjectEntriesChunk {
+            request_id: id,
+            entries: pending,
+            done: true,
+        };
+        emit_to_window(&app, &label, EVENT_PROJECT_ENTRIES_CHUNK, last);
+    });
+
+    Ok(request_id)
+}
+
+fn is_listable_dir(path: &Path, show_hidden: bool) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    !is_always_blocked_dir(name) && (show_hidden || !name.starts_with('.'))
+}
+
+/// Visible direct children of `dir`, as (path, is_dir) pairs.
+fn visible_children(
+    dir: &Path,
+    show_hidden: bool,
+    ignore: Option<&ProjectIgnore>,
+) -> Vec<(PathBuf, bool)> {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    read_dir
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            let is_dir = path.is_dir();
+            let visible = if is_dir {
+                is_listable_dir(&path, show_hidden)
+            } else {
+                is_visible_project_file(&path, show_hidden)
+            };
+            if !visible {
+                return None;
+            }
+            if ignore.is_some_and(|ignore| ignore.is_ignored(&path, is_dir)) {
+                return None;
+            }
+            Some((path, is_dir))
+        })
+        .collect()
+}
+
+/// List one directory of the project for expand-on-demand trees.
+///
+/// Children are sorted folders first, then by name, and paged with
+/// `offset`/`limit`. Folders carry the number of their own visible
+/// children so the tree can show an expander without walking deeper.
+#[tauri::command]
+pub(crate) fn list_directory(
+    root: String,
+    dir: Option<String>,
+    show_hidden: bool,
+    show_ignored: bool,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<DirectoryPage, String> {
+    let root_path = PathBuf::from(root);
+    if !root_path.is_dir() {
+        return Err("Selected path is not a folder".to_string());
+    }
+
+    let dir_path = dir.map(PathBuf::from).unwrap_or_else(|| root_path.clone());
+    if !dir_path.is_dir() {
+        return Err("Selected path is not a folder".to_string());
+    }
+    // Compare resolved paths so `..` and symlinks cannot step outside the
+    // root, then list the folder under the root as the caller spelled it.
+    let resolve =
+        |path: &Path| fs::canonicalize(path).map_err(|e| format!("Unable to resolve path: {e}"));
+    let canonical_root = resolve(&root_path)?;
+    let dir_path = match resolve(&dir_path)?.strip_prefix(&canonical_root) {
+        Ok(relative) => root_path.join(relative),
+        Err(_) => return Err("Folder is outside the project root".to_string()),
+    };
+
+    let ignore = (!show_ignored).then(|| ProjectIgnore::new(&root_path));
+    let mut children = visible_children(&dir_path, show_hidden, ignore.as_ref());
+    children.sort_by(|(a, a_is_dir), (b, b_is_dir)| {
+        b_is_dir.cmp(a_is_dir).then_with(|| {
+            let a_name = a.file_name().unwrap_or_default().to_string_lossy();
+            let b_name = b.file_name().unwrap_or_default().to_string_lossy();
+            a_name.to_lowercase().cmp(&b_name.to_lowercase())
+        })
+    });
+
+    let total = children.len();
+    let offset = offset.unwrap_or(0).min(total);
+    let limit = limit.unwrap_or(DEFAULT_DIRECTORY_PAGE_SIZE).max(1);
+    let end = offset.saturating_add(limit).min(total);
+
+    let entries = children[offset..end]
+        .iter()
+        .filter_map(|(path, is_dir)| {
+            let relative = path.strip_prefix(&root_path).ok()?;
+            let child_count =
+                is_dir.then(|| visible_children(path, show_hidden, ignore.as_ref()).len());
+            Some(DirectoryEntry {
+                path: path_to_string(path),
+                rel_path: relative.to_string_lossy().to_string(),
+                name: path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string(),
+                is_dir: *is_dir,
+                child_count,
+                binary_type: if *is_dir {
+                    None
+                } else {
+                    project_file_binary_type(path).map(str::to_string)
+                },
+            })
+        })
+        .collect();
+
+    Ok(DirectoryPage {
+        entries,
+        total,
+        offset,
+        has_more: end < total,
+    })
+}
@@ -0,0 +1,305 @@
+use super::*;
+use crate::git::{find_git_root, head_file_content};
+use crate::languages::language_for_path;
+use serde_json::{Map, Value};
+
+/// Languages whose files can be compared structurally.
+const STRUCTURED_LANGUAGES: &[&str] = &["json", "yaml", "toml"];
+
+/// One key or array element that differs between two documents.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SemanticChange {
+    /// Location such as `spec.containers[0].image`; empty for the whole
+    /// document. Keys that are not plain identifiers are written as
+    /// `["key.with.dots"]`.
+    pub(crate) path: String,
+    /// `"added"`, `"removed"` or `"changed"`.
+    pub(crate) change: &'static str,
+    pub(crate) old_value: Option<Value>,
+    pub(crate) new_value: Option<Value>,
+}
+
+/// `"json"`, `"yaml"` or `"toml"` when `path` names a structured file.
+pub(crate) fn structured_kind(path: &Path) -> Option<&'static str> {
+    let id = language_for_path(path)?.id;
+    STRUCTURED_LANGUAGES.contains(&id).then_some(id)
+}
+
+fn yaml_to_json(value: serde_yml::Value) -> Value {
+    match value {
+        serde_yml::Value::Sequence(items) => {
+            Value::Array(items.into_iter().map(yaml_to_json).collect())
+        }
+        serde_yml::Value::Mapping(mapping) => Value::Object(
+            mapping
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = match key {
+                        serde_yml::Value::String(key) => key,
+                        key => serde_json::to_string(&yaml_to_json(key)).unwrap_or_default(),
+                    };
+                    (key, yaml_to_json(value))
+                })
+                .collect(),
+        ),
+        serde_yml::Value::Tagged(tagged) => yaml_to_json(tagged.value),
+        scalar => serde_json::to_value(scalar).unwrap_or(Value::Null),
+    }
+}
+
+fn toml_to_json(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::from(i),
+        toml::Value::Float(f) => serde_json::Number::from_f64(f)
+            .map(Value::Number)
+            .unwrap_or_else(|| Value::String(f.to_string())),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(d) => Value::String(d.to_string()),
+        toml::Value::Array(items) => Value::Array(items.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(table) => Value::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, toml_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// Parse `content` of the given kind into one JSON value tree per
+/// document. Only YAML streams can hold more than one.
+pub(crate) fn parse_documents(content: &str, kind: &str) -> Result<Vec<Value>, String> {
+    match kind {
+        "json" => serde_json::from_str(content)
+            .map(|value| vec![value])
+            .map_err(|e| format!("Invalid JSON: {e}")),
+        "yaml" => {
+            let documents = serde_yml::Deserializer::from_str(content)
+                .map(|document| serde_yml::Value::deserialize(document).map(yaml_to_json))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("Invalid YAML: {e}"))?;
+            Ok(if documents.is_empty() {
+                vec![Value::Null]
+            } else {
+                documents
+            })
+        }
+        "toml" => toml::from_str(content)
+            .map(|value| vec![toml_to_json(value)])
+            .map_err(|e| format!("Invalid TOML: {e}")),
+        _ => Err(format!("Unsupported structured format: {kind}")),
+    }
+}
+
+/// Parse `content` of the given kind into a JSON value tree. Only the
+/// first document of a YAML stream is returned.
+pub(crate) fn parse_structured(content: &str, kind: &str) -> Result<Value, String> {
+    parse_documents(content, kind).map(|documents| documents.into_iter().next().unwrap_or_default())
+}
+
+fn is_plain_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$' || c == '-')
+}
+
+fn key_path(parent: &str, key: &str) -> String {
+    if is_plain_key(key) {
+        if parent.is_empty() {
+            key.to_string()
+        } else {
+            format!("{parent}.{key}")
+        }
+    } else {
+        format!("{parent}[{}]", Value::String(key.to_string()))
+    }
+}
+
+fn diff_objects(
+    path: &str,
+    old: &Map<String, Value>,
+    new: &Map<String, Value>,
+    out: &mut Vec<SemanticChange>,
+) {
+    let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    for key in keys {
+        let child = key_path(path, key);
+        match (old.get(key), new.get(key)) {
+            (Some(old), Some(new)) => diff_into(&child, old, new, out),
+            (Some(old), None) => out.push(removed(child, old)),
+            (None, Some(new)) => out.push(added(child, new)),
+            (None, None) => {}
+        }
+    }
+}
+
+fn added(path: String, value: &Value) -> SemanticChange {
+    SemanticChange {
+        path,
+        change: "added",
+        old_value: None,
+        new_value: Some(value.clone()),
+    }
+}
+
+fn removed(path: String, value: &Value) -> SemanticChange {
+    SemanticChange {
+        path,
+        change: "removed",
+        old_value: Some(value.clone()),
+        new_value: None,
+    }
+}
+
+fn diff_into(path: &str, old: &Value, new: &Value, out: &mut Vec<SemanticChange>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => diff_objects(path, old, new, out),
+        (Value::Array(old), Value::Array(new)) => {
+            for index in 0..old.len().max(new.len()) {
+                let child = format!("{path}[{index}]");
+                match (old.get(index), new.get(index)) {
+                    (Some(old), Some(new)) => diff_into(&child, old, new, out),
+                    (Some(old), None) => out.push(removed(child, old)),
+                    (None, Some(new)) => out.push(added(child, new)),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if old != new => out.push(SemanticChange {
+            path: path.to_string(),
+            change: "changed",
+            old_value: Some(old.clone()),
+            new_value: Some(new.clone()),
+        }),
+        _ => {}
+    }
+}
+
+/// Structural differences between two value trees. Object keys are
+/// compared regardless of order; array elements are compared by position.
+pub(crate) fn diff_values(old: &Value, new: &Value) -> Vec<SemanticChange> {
+    let mut out = Vec::new();
+    diff_into("", old, new, &mut out);
+    out
+}
+
+/// Compare two documents of the same kind. A missing side is reported as
+/// the whole document being added or removed.
+pub(crate) fn semantic_diff_texts(
+    old: Option<&str>,
+    new: Option<&str>,
+    kind: &str,
+) -> Result<Vec<SemanticChange>, String> {
+    let old = old.map(|text| parse_documents(text, kind)).transpose()?;
+    let new = new.map(|text| parse_documents(text, kind)).transpose()?;
+    // Once either side is a multi-document YAML stream, compare them as
+    // arrays so paths start with the document index, as in `[1].kind`.
+    let multi = [&old, &new].iter().any(|documents| {
+        documents
+            .as_ref()
+            .is_some_and(|documents| documents.len() > 1)
+    });
+    let to_value = |documents: Vec<Value>| {
+        if multi {
+            Value::Array(documents)
+        } else {
+            documents.into_iter().next().unwrap_or_default()
+        }
+    };
+    let old = old.map(to_value);
+    let new = new.map(to_value);
+    Ok(match (old, new) {
+        (Some(old), Some(new)) => diff_values(&old, &new),
+        (Some(old), None) => vec![removed(String::new(), &old)],
+        (None, Some(new)) => vec![added(String::new(), &new)],
+        (None, None) => Vec::new(),
+    })
+}
+
+fn read_optional(path: Option<&Path>) -> Result<Option<String>, String> {
+    path.map(|path| {
+        fs::read_to_string(path).map_err(|e| format!("Unable to read {}: {e}", path.display()))
+    })
+    .transpose()
+}
+
+/// Structurally compare two files, either of which may be missing. The
+/// format is taken from `new`, or `old` when `new` is missing.
+pub(crate) fn semantic_diff_files(
+    old: Option<&Path>,
+    new: Option<&Path>,
+) -> Result<Vec<SemanticChange>, String> {
+    let kind = new
+        .or(old)
+        .and_then(structured_kind)
+        .ok_or_else(|| "Only JSON, YAML and TOML files can be compared by structure".to_string())?;
+    semantic_diff_texts(
+        read_optional(old)?.as_deref(),
+        read_optional(new)?.as_deref(),
+        kind,
+    )
+}
+
+fn format_value(value: Option<&Value>) -> String {
+    value
+        .map(|value| serde_json::to_string(value).unwrap_or_default())
+        .unwrap_or_default()
+}
+
+/// One line per change: `+ path: value`, `- path: value` or
+/// `~ path: old -> new`.
+pub(crate) fn format_semantic_changes(changes: &[SemanticChange]) -> String {
+    let mut out = String::new();
+    for change in changes {
+        let path = if change.path.is_empty() {
+            "(document)"
+        } else {
+            &change.path
+        };
+        let line = match change.change {
+            "added" => format!("+ {path}: {}", format_value(change.new_value.as_ref())),
+            "removed" => format!("- {path}: {}", format_value(change.old_value.as_ref())),
+            _ => format!(
+                "~ {path}: {} -> {}",
+                format_value(change.old_value.as_ref()),
+                format_value(change.new_value.as_ref())
+            ),
+        };
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Structural diff of two JSON, YAML or TOML files.
+#[tauri::command]
+pub(crate) fn semantic_diff_paths(a: String, b: String) -> Result<Vec<SemanticChange>, String> {
+    semantic_diff_files(Some(Path::new(&a)), Some(Path::new(&b)))
+}
+
+/// Structural diff of a file in the repository containing `root` between
+/// `HEAD` and the working tree. `rel_path` is relative to the repository
+/// root, as in `git_diff_all`.
+#[tauri::command]
+pub(crate) fn git_semantic_diff(
+    root: String,
+    rel_path: String,
+) -> Result<Vec<SemanticChange>, String> {
+    let git_root =
+        find_git_root(Path::new(&root)).ok_or_else(|| "Not a git repository".to_string())?;
+    let full_path = git_root.join(&rel_path);
+    let kind = structured_kind(&full_path)
+        .ok_or_else(|| "Only JSON, YAML and TOML files can be compared by structure".to_string())?;
+    let new = full_path
+        .is_file()
+        .then(|| fs::read_to_string(&full_path))
+        .transpose()
+        .map_err(|e| format!("Unable to read file: {e}"))?;
+    let old = head_file_content(&git_root, &rel_path);
+    semantic_diff_texts(old.as_deref(), new.as_deref(), kind)
+}
@@ -0,0 +1,544 @@
+use super::*;
+use crate::files::{
+    file_mtime_nanos, file_version, write_text_file, WriteFileError, WriteTextFileResult,
+};
+use crate::text_encoding::{decode_text, DecodedText};
+use std::cmp::Ordering as CmpOrdering;
+use std::ops::Range;
+
+/// Rows read when guessing the delimiter, quote and header.
+const SNIFF_ROWS: usize = 64;
+const CANDIDATE_DELIMITERS: &[u8] = b",\t;|";
+/// Rows returned when a query does not set `limit`.
+const DEFAULT_PAGE_SIZE: usize = 200;
+/// Parsed files kept for paging before the cache is cleared.
+const MAX_CACHED_TABLES: usize = 8;
+
+/// How a delimited file is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Dialect {
+    pub(crate) delimiter: u8,
+    pub(crate) quote: u8,
+    pub(crate) has_header: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TableDialect {
+    pub(crate) delimiter: String,
+    pub(crate) quote: String,
+    pub(crate) has_header: bool,
+}
+
+impl From<Dialect> for TableDialect {
+    fn from(dialect: Dialect) -> Self {
+        Self {
+            delimiter: (dialect.delimiter as char).to_string(),
+            quote: (dialect.quote as char).to_string(),
+            has_header: dialect.has_header,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TableColumn {
+    pub(crate) name: String,
+    /// `"integer"`, `"number"`, `"boolean"`, `"date"` or `"string"`.
+    pub(crate) kind: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TableRow {
+    /// Position of the row among the data rows of the file, header
+    /// excluded. Pass it to `update_table_cell`.
+    pub(crate) index: usize,
+    pub(crate) cells: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TablePage {
+    pub(crate) version: String,
+    pub(crate) dialect: TableDialect,
+    pub(crate) columns: Vec<TableColumn>,
+    pub(crate) total_rows: usize,
+    /// Rows left after `filter`, before paging.
+    pub(crate) matched_rows: usize,
+    pub(crate) offset: usize,
+    pub(crate) rows: Vec<TableRow>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TableQuery {
+    #[serde(default)]
+    pub(crate) offset: usize,
+    pub(crate) limit: Option<usize>,
+    pub(crate) sort_column: Option<usize>,
+    #[serde(default)]
+    pub(crate) sort_descending: bool,
+    /// Case-insensitive text a row must contain.
+    pub(crate) filter: Option<String>,
+    /// Only match `filter` against this column.
+    pub(crate) filter_column: Option<usize>,
+}
+
+/// A delimited file parsed into cells.
+pub(crate) struct ParsedTable {
+    version: String,
+    dialect: Dialect,
+    columns: Vec<TableColumn>,
+    rows: Vec<Vec<String>>,
+}
+
+/// Parsed tables by path, so paging, sorting and filtering a large file
+/// does not parse it again until it changes.
+pub(crate) struct TableCache {
+    tables: Mutex<HashMap<PathBuf, (FileStamp, Arc<ParsedTable>)>>,
+}
+
+/// Modification time and size a cached table was read at.
+type FileStamp = (u128, u64);
+
+impl TableCache {
+    pub(crate) fn new() -> Self {
+        Self {
+            tables: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The parsed table for `path`. Only the metadata is read while the
+    /// file's modification time and size match the cached table.
+    fn load(&self, path: &Path) -> Result<Arc<ParsedTable>, String> {
+        let metadata =
+            fs::metadata(path).map_err(|e| format!("Unable to read file metadata: {e}"))?;
+        let stamp = (file_mtime_nanos(&metadata), metadata.len());
+        let cached = self
+            .tables
+            .lock()
+            .map_err(|_| "Unable to read table cache".to_string())?
+            .get(path)
+            .filter(|(cached_stamp, _)| *cached_stamp == stamp)
+            .map(|(_, table)| Arc::clone(table));
+        if let Some(table) = cached {
+            return Ok(table);
+        }
+
+        let (content, version) = read_table_text(path)?;
+        let table = Arc::new(parse_table(&content, path, version));
+        let mut tables = self
+            .tables
+            .lock()
+            .map_err(|_| "Unable to read table cache".to_string())?;
+        if tables.len() >= MAX_CACHED_TABLES {
+            tables.clear();
+        }
+        tables.insert(path.to_path_buf(), (stamp, Arc::clone(&table)));
+        Ok(table)
+    }
+}
+
+fn read_table_text(path: &Path) -> Result<(String, String), String> {
+    let bytes = fs::read(path).map_err(|e| format!("Unable to read file: {e}"))?;
+    let metadata = fs::metadata(path).map_err(|e| format!("Unable to read file metadata: {e}"))?;
+    let version = file_version(&bytes, &metadata);
+    let DecodedText { content, .. } = decode_text(&bytes, None)?;
+    Ok((content, version))
+}
+
+fn reader(content: &str, delimiter: u8, quote: u8) -> csv::Reader<&[u8]> {
+    csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(delimiter)
+        .quote(quote)
+        .from_reader(content.as_bytes())
+}
+
+fn sample_rows(content: &str, delimiter: u8, quote: u8) -> Vec<Vec<String>> {
+    reader(content, delimiter, quote)
+        .records()
+        .take(SNIFF_ROWS)
+        .map_while(Result::ok)
+        .map(|record| record.iter().map(str::to_string).collect())
+        .collect()
+}
+
+fn cell_kind(cell: &str) -> Option<&'static str> {
+    let cell = cell.trim();
+    if cell.is_empty() {
+        return None;
+    }
+    if cell.parse::<i64>().is_ok() {
+        return Some("integer");
+    }
+    if cell.parse::<f64>().is_ok_and(f64::is_finite) {
+        return Some("number");
+    }
+    if cell.eq_ignore_ascii_case("true") || cell.eq_ignore_ascii_case("false") {
+        return Some("boolean");
+    }
+    let bytes = cell.as_bytes();
+    let is_date = bytes.len() >= 10
+        && bytes[..10].iter().enumerate().all(|(i, b)| {
+            if i == 4 || i == 7 {
+                *b == b'-'
+            } else {
+                b.is_ascii_digit()
+            }
+        });
+    Some(if is_date { "date" } else { "string" })
+}
+
+/// The narrowest kind that fits every non-empty cell.
+fn column_kind<'a>(cells: impl Iterator<Item = &'a str>) -> &'static str {
+    let mut kind = None;
+    for cell_kind in cells.filter_map(cell_kind) {
+        kind = Some(match (kind, cell_kind) {
+            (None, next) => next,
+            (Some(current), next) if current == next => current,
+            (Some("integer" | "number"), "integer" | "number") => "number",
+            _ => return "string",
+        });
+    }
+    kind.unwrap_or("string")
+}
+
+/// Guess the delimiter, quote character and whether the first row is a
+/// header from the start of `content`. `.tsv` and `.tab` files prefer tabs
+/// when the content does not decide.
+pub(crate) fn detect_dialect(content: &str, path: &Path) -> Dialect {
+    let prefers_tab = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("tsv") || ext.eq_ignore_ascii_case("tab"));
+    let fallback = if prefers_tab { b'\t' } else { b',' };
+
+    // The delimiter that splits the most sample rows into the same number
+    // of fields, more than one.
+    let mut best = (fallback, 0);
+    for &delimiter in CANDIDATE_DELIMITERS {
+        let counts: Vec<usize> = sample_rows(content, delimiter, b'"')
+            .iter()
+            .map(Vec::len)
+            .collect();
+        let Some(&width) = counts.first().filter(|&&width| width > 1) else {
+            continue;
+        };
+        let consistent = counts.iter().take_while(|&&count| count == width).count();
+        let score = consistent * width;
+        if score > best.1 || (score == best.1 && delimiter == fallback) {
+            best = (delimiter, score);
+        }
+    }
+    let delimiter = best.0;
+
+    // Fields that open with the quote character, counted at line starts
+    // and after delimiters so apostrophes inside words are ignored.
+    let quoted_with = |quote: u8| {
+        content
+            .lines()
+            .take(SNIFF_ROWS)
+            .map(|line| {
+                let bytes = line.as_bytes();
+                (0..bytes.len())
+                    .filter(|&i| bytes[i] == quote && (i == 0 || bytes[i - 1] == delimiter))
+                    .count()
+            })
+            .sum::<usize>()
+    };
+    let quote = if quoted_with(b'\'') > quoted_with(b'"') {
+        b'\''
+    } else {
+        b'"'
+    };
+
+    let rows = sample_rows(content, delimiter, quote);
+    Dialect {
+        delimiter,
+        quote,
+        has_header: looks_like_header(&rows),
+    }
+}
+
+/// A first row of distinct labels sitting above columns of numbers, dates
+/// or booleans is a header. When every column is text, distinct non-empty
+/// labels are taken as a header too.
+fn looks_like_header(rows: &[Vec<String>]) -> bool {
+    let Some((first, data)) = rows.split_first() else {
+        return false;
+    };
+    if data.is_empty() || first.iter().any(|cell| cell.trim().is_empty()) {
+        return false;
+    }
+    let mut labels: Vec<&str> = first.iter().map(|cell| cell.trim()).collect();
+    labels.sort_unstable();
+    labels.dedup();
+    if labels.len() != first.len() {
+        return false;
+    }
+
+    let mut votes = 0i32;
+    for (column, label) in first.iter().enumerate() {
+        let kind = column_kind(
+            data.iter()
+                .filter_map(|row| row.get(column))
+                .map(String::as_str),
+        );
+        let label_kind = cell_kind(label).unwrap_or("string");
+        if kind != "string" {
+            votes += if label_kind == kind { -1 } else { 1 };
+        }
+    }
+    votes >= 0
+}
+
+pub(crate) fn parse_table(content: &str, path: &Path, version: String) -> ParsedTable {
+    let dialect = detect_dialect(content, path);
+    let mut rows: Vec<Vec<String>> = reader(content, dialect.delimiter, dialect.quote)
+        .records()
+        .filter_map(Result::ok)
+        .map(|record| record.iter().map(str::to_string).collect())
+        .collect();
+    let header = if dialect.has_header && !rows.is_empty() {
+        Some(rows.remove(0))
+    } else {
+        None
+    };
+    let width = rows
+        .iter()
+        .map(Vec::len)
+        .chain(header.as_ref().map(Vec::len))
+        .max()
+        .unwrap_or(0);
+    let columns = (0..width)
+        .map(|column| TableColumn {
+            name: header
+                .as_ref()
+                .and_then(|header| header.get(column))
+                .cloned()
+                .unwrap_or_else(|| format!("Column {}", column + 1)),
+            kind: column_kind(
+                rows.iter()
+                    .filter_map(|row| row.get(column))
+                    .map(String::as_str),
+            ),
+        })
+        .collect();
+    ParsedTable {
+        version,
+        dialect,
+        columns,
+        rows,
+    }
+}
+
+fn compare_cells(a: &str, b: &str, kind: &str) -> CmpOrdering {
+    let (a, b) = (a.trim(), b.trim());
+    // Empty cells are the smallest value of any kind.
+    match (a.is_empty(), b.is_empty()) {
+        (true, true) => return CmpOrdering::Equal,
+        (true, false) => return CmpOrdering::Less,
+        (false, true) => return CmpOrdering::Greater,
+        _ => {}
+    }
+    if matches!(kind, "integer" | "number") {
+        if let (Ok(a), Ok(b)) = (a.parse::<f64>(), b.parse::<f64>()) {
+            return a.total_cmp(&b);
+        }
+    }
+    a.to_lowercase().cmp(&b.to_lowercase())
+}
+
+impl ParsedTable {
+    /// One page of rows after filtering and sorting.
+    pub(crate) fn page(&self, query: &TableQuery) -> TablePage {
+        let needle = query
+            .filter
+            .as_deref()
+            .map(str::trim)
+            .filter(|needle| !needle.is_empty())
+            .map(str::to_lowercase);
+        let mut indices: Vec<usize> = (0..self.rows.len())
+            .filter(|&index| {
+                let Some(needle) = &needle else {
+                    return true;
+                };
+                let row = &self.rows[index];
+                let cells: Box<dyn Iterator<Item = &String>> = match query.filter_column {
+                    Some(column) => Box::new(row.get(column).into_iter()),
+                    None => Box::new(row.iter()),
+                };
+                cells
+                    .into_iter()
+                    .any(|cell| cell.to_lowercase().contains(needle.as_str()))
+            })
+            .collect();
+
+        if let Some(column) = query
+            .sort_column
+            .filter(|&column| column < self.columns.len())
+        {
+            let kind = self.columns[column].kind;
+            let cell = |index: usize| self.rows[index].get(column).map_or("", String::as_str);
+            indices.sort_by(|&a, &b| {
+                let order = compare_cells(cell(a), cell(b), kind);
+                if query.sort_descending {
+                    order.reverse()
+                } else {
+                    order
+                }
+            });
+        }
+
+        let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+        let width = self.columns.len();
+        let rows = indices
+            .iter()
+            .skip(query.offset)
+            .take(limit)
+            .map(|&index| {
+                let mut cells = self.rows[index].clone();
+                cells.resize(width, String::new());
+                TableRow { index, cells }
+            })
+            .collect();
+        TablePage {
+            version: self.version.clone(),
+            dialect: self.dialect.into(),
+            columns: self.columns.clone(),
+            total_rows: self.rows.len(),
+            matched_rows: indices.len(),
+            offset: query.offset,
+            rows,
+        }
+    }
+}
+
+/// Byte ranges of the raw fields of the record that starts at `start`.
+fn raw_field_spans(content: &str, start: usize, dialect: Dialect) -> Vec<Range<usize>> {
+    let bytes = content.as_bytes();
+    // After a CRLF the reader reports the record as starting at the `\n`.
+    let start = start
+        + bytes[start..]
+            .iter()
+            .take_while(|&&byte| byte == b'\r' || byte == b'\n')
+            .count();
+    let mut spans = Vec::new();
+    let mut field_start = start;
+    let mut in_quotes = false;
+    let mut index = start;
+    while index < bytes.len() {
+        let byte = bytes[index];
+        if in_quotes {
+            if byte == dialect.quote {
+                if bytes.get(index + 1) == Some(&dialect.quote) {
+                    index += 2;
+                    continue;
+                }
+                in_quotes = false;
+            }
+        } else if byte == dialect.quote && index == field_start {
+            in_quotes = true;
+        } else if byte == dialect.delimiter {
+            spans.push(field_start..index);
+            field_start = index + 1;
+        } else if byte == b'\n' || byte == b'\r' {
+            break;
+        }
+        index += 1;
+    }
+    spans.push(field_start..index);
+    spans
+}
+
+/// `value` written as a field. A field that was quoted stays quoted; an
+/// unquoted one is only quoted when the value needs it.
+fn encode_field(value: &str, original: &str, dialect: Dialect) -> String {
+    let quote = dialect.quote as char;
+    let was_quoted = original.starts_with(quote);
+    let needs_quotes = value.contains([dialect.delimiter as char, quote, '\n', '\r']);
+    if was_quoted || needs_quotes {
+        let doubled = format!("{quote}{quote}");
+        format!("{quote}{}{quote}", value.replace(quote, &doubled))
+    } else {
+        value.to_string()
+    }
+}
+
+/// `content` with one cell replaced. `row` counts data rows, header
+/// excluded; every other byte of the file is kept as it was.
+pub(crate) fn replace_table_cell(
+    content: &str,
+    path: &Path,
+    row: usize,
+    column: usize,
+    value: &str,
+) -> Result<String, String> {
+    let dialect = detect_dialect(content, path);
+    let record_index = row + usize::from(dialect.has_header);
+    let mut records = reader(content, dialect.delimiter, dialect.quote).into_records();
+    let record = records
+        .nth(record_index)
+        .ok_or_else(|| format!("Row {} does not exist", row + 1))?
+        .map_err(|e| format!("Unable to parse row {}: {e}", row + 1))?;
+    let start = record
+        .position()
+        .map(|position| position.byte() as usize)
+        .ok_or_else(|| format!("Unable to locate row {}", row + 1))?;
+
+    let spans = raw_field_spans(content, start, dialect);
+    let mut updated = String::with_capacity(content.len() + value.len());
+    match spans.get(column) {
+        Some(span) => {
+            updated.push_str(&content[..span.start]);
+            updated.push_str(&encode_field(value, &content[span.clone()], dialect));
+            updated.push_str(&content[span.end..]);
+        }
+        None => {
+            // Short rows grow to reach the column.
+            let end = spans.last().map_or(start, |span| span.end);
+            updated.push_str(&content[..end]);
+            for _ in spans.len()..=column {
+                updated.push(dialect.delimiter as char);
+            }
+            updated.push_str(&encode_field(value, "", dialect));
+            updated.push_str(&content[end..]);
+        }
+    }
+    Ok(updated)
+}
+
+/// A page of rows from a CSV or TSV file, with the detected dialect and
+/// column types. Sorting and filtering happen here so large files never
+/// have to be sent to the webview whole.
+#[tauri::command]
+pub(crate) fn read_table(
+    cache: tauri::State<TableCache>,
+    path: String,
+    query: Option<TableQuery>,
+) -> Result<TablePage, String> {
+    let table = cache.load(Path::new(&path))?;
+    Ok(table.page(&query.unwrap_or_default()))
+}
+
+/// Write one edited cell back to a CSV or TSV file, keeping the quoting of
+/// every other field. `expected_version` is the `version` of the page the
+/// edit was made on.
+#[tauri::command]
+pub(crate) fn update_table_cell(
+    app: tauri::AppHandle,
+    path: String,
+    row: usize,
+    column: usize,
+    value: String,
+    expected_version: Option<String>,
+) -> Result<WriteTextFileResult, WriteFileError> {
+    let io_error = |message: String| WriteFileError::Io { message };
+    let (content, _) = read_table_text(Path::new(&path)).map_err(io_error)?;
+    let updated =
+        replace_table_cell(&content, Path::new(&path), row, column, &value).map_err(io_error)?;
+    write_text_file(app, path, updated, None, None, None, expected_version)
+}
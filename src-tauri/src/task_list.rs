@@ -0,0 +1,179 @@
+use super::*;
+use crate::markdown_outline::{
+    is_markdown_path, line_of, line_starts, parse_outline, parser_options, read_markdown,
+};
+use pulldown_cmark::{Event as MdEvent, Parser, Tag, TagEnd};
+
+/// The progress cache is cleared once it tracks this many files.
+const MAX_TRACKED_PROGRESS: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TaskItem {
+    /// 1-based line of the `[ ]` marker.
+    pub(crate) line: usize,
+    pub(crate) text: String,
+    pub(crate) done: bool,
+    /// Text of the nearest heading above the item.
+    pub(crate) section: Option<String>,
+}
+
+/// What `teex tasks` prints.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TaskList {
+    pub(crate) path: String,
+    pub(crate) done: usize,
+    pub(crate) total: usize,
+    pub(crate) unchecked: Vec<TaskItem>,
+}
+
+/// Payload of `EVENT_TASK_PROGRESS`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TaskProgress {
+    pub(crate) path: String,
+    pub(crate) done: usize,
+    pub(crate) total: usize,
+}
+
+/// Done and total counts last emitted per canonical path. A save is seen
+/// both by `write_text_file` and by the file watcher; only the first one
+/// emits.
+pub(crate) struct TaskProgressCache {
+    counts: Mutex<HashMap<PathBuf, (usize, usize)>>,
+}
+
+impl TaskProgressCache {
+    pub(crate) fn new() -> Self {
+        Self {
+            counts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Remember `counts` for `path` and tell whether they differ from the
+    /// last ones emitted.
+    fn update(&self, path: &Path, counts: (usize, usize)) -> bool {
+        let Ok(mut tracked) = self.counts.lock() else {
+            return true;
+        };
+        if tracked.len() >= MAX_TRACKED_PROGRESS && !tracked.contains_key(path) {
+            tracked.clear();
+        }
+        tracked.insert(path.to_path_buf(), counts) != Some(counts)
+    }
+}
+
+/// Every task list item of a Markdown document, in order. An item's text
+/// is its first paragraph; nested lists are items of their own.
+pub(crate) fn parse_tasks(content: &str) -> Vec<TaskItem> {
+    let starts = line_starts(content);
+    let headings = parse_outline(content).outline(None).headings;
+    let mut items: Vec<TaskItem> = Vec::new();
+    let mut capturing = false;
+
+    for (event, range) in Parser::new_ext(content, parser_options()).into_offset_iter() {
+        match event {
+            MdEvent::TaskListMarker(done) => {
+                let line = line_of(&starts, range.start);
+                let section = headings
+                    .iter()
+                    .take_while(|heading| heading.line <= line)
+                    .last()
+                    .map(|heading| heading.text.clone());
+                items.push(TaskItem {
+                    line,
+                    text: String::new(),
+                    done,
+                    section,
+                });
+                capturing = true;
+            }
+            MdEvent::Start(Tag::List(_))
+            | MdEvent::End(TagEnd::Paragraph)
+            | MdEvent::End(TagEnd::Item) => capturing = false,
+            MdEvent::Text(text) | MdEvent::Code(text) | MdEvent::InlineMath(text) if capturing => {
+                if let Some(item) = items.last_mut() {
+                    item.text.push_str(&text);
+                }
+            }
+            MdEvent::SoftBreak | MdEvent::HardBreak if capturing => {
+                if let Some(item) = items.last_mut() {
+                    item.text.push(' ');
+                }
+            }
+            _ => {}
+        }
+    }
+    for item in &mut items {
+        item.text = item.text.trim().to_string();
+    }
+    items
+}
+
+pub(crate) fn task_list(path: &str, content: &str) -> TaskList {
+    let items = parse_tasks(content);
+    TaskList {
+        path: path.to_string(),
+        done: items.iter().filter(|item| item.done).count(),
+        total: items.len(),
+        unchecked: items.into_iter().filter(|item| !item.done).collect(),
+    }
+}
+
+/// Tell every window how many tasks of a Markdown file are done, after it
+/// was saved or changed on disk. The path is canonical, like the watcher's,
+/// and unchanged counts are not emitted again.
+pub(crate) fn emit_task_progress(app: &tauri::AppHandle, path: &Path) {
+    if !is_markdown_path(path) {
+        return;
+    }
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let Ok(content) = read_markdown(&path) else {
+        return;
+    };
+    let items = parse_tasks(&content);
+    let done = items.iter().filter(|item| item.done).count();
+    let total = items.len();
+    if !app
+        .state::<TaskProgressCache>()
+        .update(&path, (done, total))
+    {
+        return;
+    }
+    let _ = app.emit(
+        EVENT_TASK_PROGRESS,
+        TaskProgress {
+            path: path_to_string(&path),
+            done,
+            total,
+        },
+    );
+}
+
+/// Done and total task counts plus the unchecked items of a Markdown file.
+/// `path` comes back canonical, as task progress events report it.
+#[tauri::command]
+pub(crate) fn read_task_list(path: String) -> Result<TaskList, String> {
+    let path = fs::canonicalize(&path).unwrap_or_else(|_| PathBuf::from(path));
+    let content = read_markdown(&path)?;
+    Ok(task_list(&path_to_string(&path), &content))
+}
+
+/// `teex tasks FILE`: print the file's task list as JSON and return the
+/// exit code, 0 on success and 2 on errors.
+pub fn run_tasks_cli(path: &str) -> i32 {
+    let output = read_markdown(Path::new(path)).and_then(|content| {
+        serde_json::to_string_pretty(&task_list(path, &content)).map_err(|e| e.to_string())
+    });
+    match output {
+        Ok(json) => {
+            println!("{json}");
+            0
+        }
+        Err(err) => {
+            eprintln!("teex tasks: {err}");
+            2
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use super::common::TempTestDir;
+use crate::binary_files::{binary_type_of, read_binary_range};
+
+#[test]
+fn binary_type_of_uses_magic_numbers_and_nul_bytes() {
+    assert_eq!(binary_type_of(b"\x89PNG\r\n\x1a\n\x00\x00"), Some("png"));
+    assert_eq!(binary_type_of(b"\x7FELF\x02\x01\x01\x00"), Some("elf"));
+    assert_eq!(
+        binary_type_of(b"RIFF\x10\x00\x00\x00WEBPVP8 "),
+        Some("webp")
+    );
+    assert_eq!(binary_type_of(b"\x01\x02\x00\x03\xFF"), Some("binary"));
+
+    // Text that happens to start like a short signature stays text.
+    assert_eq!(binary_type_of(b"MZ notes\n"), None);
+    assert_eq!(binary_type_of(b"MZ caf\xE9\n"), None);
+    assert_eq!(binary_type_of(b"BM r\xE9sum\xE9\r\n"), None);
+    assert_eq!(binary_type_of(b"ID3 \x93quoted\x94\n"), None);
+    assert_eq!(binary_type_of(b"BM\x36\x00\x0C\x00"), Some("bmp"));
+    assert_eq!(binary_type_of(b"MZ\x90\x00\x03\x00"), Some("exe"));
+    assert_eq!(binary_type_of(b"BZh91AY&SY\x8F\x02"), Some("bzip2"));
+    assert_eq!(binary_type_of("caf\u{e9}\n".as_bytes()), None);
+    assert_eq!(binary_type_of(b"caf\xE9 au lait\n"), None);
+    assert_eq!(
+        binary_type_of(b"h\x00i\x00 \x00t\x00h\x00e\x00r\x00e\x00"),
+        None
+    );
+    assert_eq!(binary_type_of(b""), None);
+}
+
+#[test]
+fn read_binary_range_returns_hex_and_ascii_rows() {
+    let temp = TempTestDir::new();
+    let bytes: Vec<u8> = (0u8..40).chain(*b"Hello, hex!\x00").collect();
+    let file = temp.write_bytes("blob.bin", &bytes);
+    let path = file.to_string_lossy().to_string();
+
+    let range = read_binary_range(path.clone(), 0, Some(32)).expect("range read should succeed");
+    assert_eq!(range.total_size, 52);
+    assert_eq!(range.binary_type.as_deref(), Some("binary"));
+    assert_eq!(range.rows.len(), 2);
+    assert_eq!(range.rows[0].offset, 0);
+    assert_eq!(
+        range.rows[0].hex,
+        "00 01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f"
+    );
+    assert_eq!(range.rows[0].ascii, "................");
+    assert!(range.has_more);
+
+    // Offsets snap to the start of their row.
+    let tail = read_binary_range(path, 35, None).expect("range read should succeed");
+    assert_eq!(tail.offset, 32);
+    assert_eq!(tail.rows[0].ascii, " !\"#$%&'Hello, h");
+    assert_eq!(tail.rows[1].hex, "65 78 21 00");
+    assert_eq!(tail.rows[1].ascii, "ex!.");
+    assert!(!tail.has_more);
+}
@@ -0,0 +1,137 @@
+use super::common::TempTestDir;
+use super::*;
+use crate::file_index::{build_shared_index, update_shared_index, ProjectFileIndex};
+
+fn built_index(temp: &TempTestDir) -> ProjectFileIndex {
+    let mut index = ProjectFileIndex::new(temp.path(), true, false);
+    index.rebuild();
+    index
+}
+
+fn rel(path: &str) -> String {
+    Path::new(path)
+        .to_string_lossy()
+        .replace('/', std::path::MAIN_SEPARATOR_STR)
+}
+
+#[test]
+fn fuzzy_find_ranks_basename_and_boundary_matches_first() {
+    let temp = TempTestDir::new();
+    temp.write_text("src/main.rs", "");
+    temp.write_text("src/domain/manifest.rs", "");
+    temp.write_text("docs/maintenance.md", "");
+    temp.write_text("README.md", "");
+
+    let index = built_index(&temp);
+    let results = index.find("main", 10, &[]);
+
+    assert_eq!(results[0].rel_path, rel("src/main.rs"));
+    assert!(results.iter().all(|m| m.rel_path != "README.md"));
+    assert_eq!(results[0].ranges, vec![(4, 8)]);
+}
+
+#[test]
+fn fuzzy_find_reports_highlight_ranges_for_scattered_matches() {
+    let temp = TempTestDir::new();
+    temp.write_text("src/file_index.rs", "");
+
+    let index = built_index(&temp);
+    let results = index.find("fidx", 10, &[]);
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].ranges, vec![(4, 6), (11, 12), (13, 14)]);
+}
+
+#[test]
+fn fuzzy_find_boosts_recent_files_and_respects_limit() {
+    let temp = TempTestDir::new();
+    let a = temp.write_text("notes/a.md", "");
+    temp.write_text("notes/b.md", "");
+    temp.write_text("notes/c.md", "");
+
+    let index = built_index(&temp);
+    let recent = vec![path_to_string(&a)];
+    let results = index.find("md", 2, &recent);
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].path, path_to_string(&a));
+
+    let empty_query = index.find("", 1, &recent);
+    assert_eq!(empty_query[0].path, path_to_string(&a));
+}
+
+#[test]
+fn file_index_applies_watcher_changes_and_ignore_rules() {
+    let temp = TempTestDir::new();
+    temp.write_text(".gitignore", "*.log\n");
+    temp.write_text("keep.md", "");
+    temp.write_text("debug.log", "");
+
+    let mut index = built_index(&temp);
+    assert!(index.find("debug", 10, &[]).is_empty());
+
+    let added = temp.write_text("sub/new_note.md", "");
+    index.apply_changes(&[added]);
+    assert_eq!(index.find("newnote", 10, &[]).len(), 1);
+
+    fs::remove_dir_all(temp.path().join("sub")).expect("remove sub dir");
+    index.apply_changes(&[temp.path().join("sub")]);
+    assert!(index.find("newnote", 10, &[]).is_empty());
+
+    let gitignore = temp.write_text(".gitignore", "");
+    assert!(index.apply_changes(&[gitignore]));
+    index.rebuild();
+    assert_eq!(index.find("debug", 10, &[]).len(), 1);
+}
+
+#[test]
+fn file_index_leaves_hidden_files_out_unless_shown() {
+    let temp = TempTestDir::new();
+    temp.write_text("visible.md", "");
+    temp.write_text(".env", "");
+    temp.write_text(".config/settings.md", "");
+
+    let mut index = ProjectFileIndex::new(temp.path(), false, false);
+    index.rebuild();
+    assert_eq!(index.find("env", 10, &[]).len(), 0);
+    assert_eq!(index.find("settings", 10, &[]).len(), 0);
+
+    let hidden = temp.write_text(".config/more.md", "");
+    index.apply_changes(&[hidden]);
+    assert!(index.find("more", 10, &[]).is_empty());
+
+    let index = built_index(&temp);
+    assert_eq!(index.find("env", 10, &[]).len(), 1);
+    assert_eq!(index.find("settings", 10, &[]).len(), 1);
+}
+
+#[test]
+fn shared_index_rebuilds_in_the_background_after_ignore_changes() {
+    let temp = TempTestDir::new();
+    temp.write_text(".gitignore", "*.log\n");
+    temp.write_text("debug.log", "");
+    let index = build_shared_index(temp.path(), true, false);
+    let found = |query: &str| {
+        for _ in 0..200 {
+            let index = index.read().expect("index lock");
+            if !index.find(query, 10, &[]).is_empty() {
+                return true;
+            }
+            drop(index);
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        false
+    };
+    let note = temp.write_text("note.md", "");
+    update_shared_index(&index, &[note]);
+    assert!(found("note"));
+    assert!(index
+        .read()
+        .expect("index lock")
+        .find("debug", 10, &[])
+        .is_empty());
+
+    let gitignore = temp.write_text(".gitignore", "");
+    update_shared_index(&index, &[gitignore]);
+    assert!(found("debug"));
+}
@@ -0,0 +1,164 @@
+use super::common::TempTestDir;
+use crate::file_journal::{FileJournal, FileOperation, UndoneOperation};
+use std::path::PathBuf;
+
+fn undo_operation(operation: FileOperation) -> Result<UndoneOperation, String> {
+    let journal = FileJournal::new();
+    journal.record(operation);
+    journal
+        .undo_newest(|_| {})
+        .map(|undone| undone.expect("journal had an entry"))
+}
+
+fn moved(from: &str, to: &str) -> FileOperation {
+    FileOperation::Moved {
+        from: PathBuf::from(from),
+        to: PathBuf::from(to),
+    }
+}
+
+#[test]
+fn file_journal_pops_newest_first_and_drops_the_oldest_entries() {
+    let journal = FileJournal::new();
+    for i in 0..105 {
+        journal.record(moved(&format!("/a{i}"), &format!("/b{i}")));
+    }
+
+    assert_eq!(journal.pop(), Some(moved("/a104", "/b104")));
+    let mut remaining = 1;
+    let mut oldest = None;
+    while let Some(operation) = journal.pop() {
+        remaining += 1;
+        oldest = Some(operation);
+    }
+    assert_eq!(remaining, 100);
+    assert_eq!(oldest, Some(moved("/a5", "/b5")));
+}
+
+#[test]
+fn undo_operation_moves_a_renamed_entry_back() {
+    let temp = TempTestDir::new();
+    let renamed = temp.write_text("docs/new.md", "body");
+    let original = temp.path().join("old.md");
+
+    let undone = undo_operation(FileOperation::Moved {
+        from: original.clone(),
+        to: renamed.clone(),
+    })
+    .expect("undo should succeed");
+
+    assert_eq!(undone.kind, "move");
+    assert_eq!(std::fs::read_to_string(&original).unwrap(), "body");
+    assert!(!renamed.exists());
+}
+
+#[test]
+fn undo_operation_refuses_to_overwrite_or_restore_missing_entries() {
+    let temp = TempTestDir::new();
+    let original = temp.write_text("old.md", "new occupant");
+    let renamed = temp.write_text("new.md", "moved");
+
+    let err = undo_operation(FileOperation::Moved {
+        from: original.clone(),
+        to: renamed.clone(),
+    })
+    .expect_err("occupied source must not be overwritten");
+    assert_eq!(err, "\"old.md\" already exists");
+    assert_eq!(std::fs::read_to_string(&original).unwrap(), "new occupant");
+
+    let err = undo_operation(FileOperation::Moved {
+        from: temp.path().join("gone-from.md"),
+        to: temp.path().join("gone-to.md"),
+    })
+    .expect_err("missing destination cannot be moved back");
+    assert_eq!(err, "\"gone-to.md\" no longer exists");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn undo_operation_restores_a_trashed_file() {
+    use crate::file_journal::move_to_trash;
+
+    let temp = TempTestDir::new();
+    let file = temp.write_text("restore-me.md", "keep");
+
+    let operation = move_to_trash(&file).expect("trash should succeed");
+    assert!(!file.exists());
+    assert!(matches!(
+        &operation,
+        FileOperation::Trashed {
+            trash_id: Some(_),
+            ..
+        }
+    ));
+
+    let undone = undo_operation(operation).expect("restore should succeed");
+    assert_eq!(undone.kind, "restore");
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "keep");
+}
+
+#[test]
+fn undo_newest_drops_undos_that_can_never_succeed_and_keeps_conflicts() {
+    let temp = TempTestDir::new();
+    let renamed = temp.write_text("renamed.md", "body");
+    let original = temp.path().join("original.md");
+    let journal = FileJournal::new();
+    journal.record(FileOperation::Moved {
+        from: original.clone(),
+        to: renamed.clone(),
+    });
+    journal.record(moved("/gone/from.md", "/gone/to.md"));
+
+    let err = journal.undo_newest(|_| {}).expect_err("missing entry");
+    assert_eq!(err, "\"to.md\" no longer exists");
+    let undone = journal
+        .undo_newest(|_| {})
+        .expect("older undo is not blocked")
+        .expect("older entry");
+    assert_eq!(undone.kind, "move");
+    assert!(original.is_file());
+
+    let occupied = temp.write_text("occupied.md", "new occupant");
+    let kept = FileOperation::Moved {
+        from: occupied,
+        to: original.clone(),
+    };
+    journal.record(kept.clone());
+    let err = journal.undo_newest(|_| {}).expect_err("occupied source");
+    assert_eq!(err, "\"occupied.md\" already exists");
+    assert_eq!(journal.pop(), Some(kept));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn undoing_a_replacing_move_restores_the_replaced_file() {
+    use crate::file_ops::{move_in_root, ConflictPolicy};
+
+    let temp = TempTestDir::new();
+    let root = temp.path();
+    let source = temp.write_text("a.md", "new");
+    let target = temp.mkdir("target");
+    let replaced = temp.write_text("target/a.md", "old");
+
+    let placement =
+        move_in_root(root, &source, &target, ConflictPolicy::Replace).expect("replace move");
+    assert_eq!(std::fs::read_to_string(&replaced).unwrap(), "new");
+    let journal = FileJournal::new();
+    let mut steps: Vec<FileOperation> = placement.replaced.into_iter().collect();
+    steps.push(FileOperation::Moved {
+        from: source.clone(),
+        to: placement.path.clone(),
+    });
+    journal.record(FileOperation::steps(steps).expect("two steps"));
+
+    let mut reversed = Vec::new();
+    let undone = journal
+        .undo_newest(|step| reversed.push(step.clone()))
+        .expect("undo should succeed")
+        .expect("entry");
+    assert_eq!(undone.kind, "move");
+    assert_eq!(reversed.len(), 2);
+    assert_eq!(std::fs::read_to_string(&source).unwrap(), "new");
+    assert_eq!(std::fs::read_to_string(&replaced).unwrap(), "old");
+    assert_eq!(journal.pop(), None);
+}
@@ -0,0 +1,116 @@
+use super::common::TempTestDir;
+use crate::file_ops::{
+    create_file_in_root, create_folder_in_root, duplicate_in_root, move_in_root, rename_in_root,
+    ConflictPolicy,
+};
+
+#[test]
+fn create_file_applies_conflict_policy() {
+    let temp = TempTestDir::new();
+    let root = temp.path();
+    temp.write_text("notes.md", "# existing");
+
+    let err = create_file_in_root(root, root, "notes.md", ConflictPolicy::Fail)
+        .expect_err("existing file should conflict");
+    assert_eq!(err, "\"notes.md\" already exists");
+
+    let created = create_file_in_root(root, root, "notes.md", ConflictPolicy::KeepBoth)
+        .expect("keep both should pick a free name")
+        .path;
+    assert_eq!(created, root.join("notes 2.md"));
+    assert_eq!(
+        std::fs::read_to_string(root.join("notes.md")).unwrap(),
+        "# existing"
+    );
+
+    let folder = create_folder_in_root(root, root, "docs", ConflictPolicy::Fail)
+        .expect("create folder")
+        .path;
+    assert!(folder.is_dir());
+    let nested = create_file_in_root(root, &folder, ".env", ConflictPolicy::Fail)
+        .expect("create dotfile in subfolder")
+        .path;
+    assert_eq!(nested, folder.join(".env"));
+}
+
+#[test]
+fn file_ops_reject_paths_outside_the_root_and_invalid_names() {
+    let temp = TempTestDir::new();
+    let root = temp.mkdir("project");
+    let outside = temp.write_text("outside.md", "secret");
+    let inside = temp.write_text("project/a.md", "a");
+
+    assert!(create_file_in_root(&root, temp.path(), "x.md", ConflictPolicy::Fail).is_err());
+    assert!(rename_in_root(&root, &outside, "y.md", ConflictPolicy::Fail).is_err());
+    assert!(move_in_root(&root, &inside, temp.path(), ConflictPolicy::Fail).is_err());
+    assert!(duplicate_in_root(&root, &root).is_err());
+    assert!(rename_in_root(&root, &inside, "../escape.md", ConflictPolicy::Fail).is_err());
+    assert!(create_file_in_root(&root, &root, "..", ConflictPolicy::Fail).is_err());
+    assert!(inside.is_file());
+}
+
+#[test]
+fn rename_and_move_follow_conflict_policy() {
+    let temp = TempTestDir::new();
+    let root = temp.path();
+    let a = temp.write_text("a.md", "a");
+    temp.write_text("b.md", "b");
+    let target = temp.mkdir("target");
+    temp.write_text("target/a.md", "old");
+
+    assert!(rename_in_root(root, &a, "b.md", ConflictPolicy::Fail).is_err());
+    let renamed = rename_in_root(root, &a, "c.md", ConflictPolicy::Fail)
+        .expect("rename")
+        .path;
+    assert_eq!(renamed, root.join("c.md"));
+    assert!(!a.exists());
+
+    assert!(move_in_root(root, &renamed, &target, ConflictPolicy::Fail).is_ok());
+    let b_moved = rename_in_root(root, &target.join("c.md"), "a.md", ConflictPolicy::KeepBoth)
+        .expect("rename with keep both")
+        .path;
+    assert_eq!(b_moved, target.join("a 2.md"));
+    assert_eq!(std::fs::read_to_string(target.join("a.md")).unwrap(), "old");
+}
+
+#[test]
+fn move_refuses_to_nest_a_folder_inside_itself() {
+    let temp = TempTestDir::new();
+    let root = temp.path();
+    let folder = temp.mkdir("docs");
+    let child = temp.mkdir("docs/inner");
+    temp.write_text("docs/inner/x.md", "x");
+
+    let err = move_in_root(root, &folder, &child, ConflictPolicy::Fail)
+        .expect_err("moving into a descendant must fail");
+    assert_eq!(err, "Cannot move a folder into itself");
+
+    let unchanged = move_in_root(root, &child, &folder, ConflictPolicy::Fail)
+        .expect("moving into the current parent is a no-op")
+        .path;
+    assert_eq!(unchanged, child);
+
+    let moved = move_in_root(root, &child, root, ConflictPolicy::Fail)
+        .expect("move up")
+        .path;
+    assert_eq!(moved, root.join("inner"));
+    assert!(root.join("inner/x.md").is_file());
+}
+
+#[test]
+fn duplicate_picks_copy_names_and_copies_folders() {
+    let temp = TempTestDir::new();
+    let root = temp.path();
+    let file = temp.write_text("report.txt", "data");
+    temp.write_text("pkg/lib.rs", "fn main() {}");
+
+    let first = duplicate_in_root(root, &file).expect("first duplicate");
+    let second = duplicate_in_root(root, &file).expect("second duplicate");
+    assert_eq!(first, root.join("report copy.txt"));
+    assert_eq!(second, root.join("report copy 2.txt"));
+    assert_eq!(std::fs::read_to_string(&second).unwrap(), "data");
+
+    let folder_copy = duplicate_in_root(root, &root.join("pkg")).expect("duplicate folder");
+    assert_eq!(folder_copy, root.join("pkg copy"));
+    assert!(folder_copy.join("lib.rs").is_file());
+}
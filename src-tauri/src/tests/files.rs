@@ -1,5 +1,21 @@
 use super::common::TempTestDir;
 use super::*;
+use crate::file_journal::move_to_trash;
+use crate::files::{read_text_payload, save_text_file, FilePayload, WriteFileError};
+use crate::large_file::LineIndexCache;
+use crate::text_encoding::encoding_for_label;
+
+fn read_payload(path: String) -> Result<FilePayload, String> {
+    read_text_payload(&LineIndexCache::new(), path, None)
+}
+
+fn reopen_payload(path: String, encoding: String) -> Result<FilePayload, String> {
+    read_text_payload(
+        &LineIndexCache::new(),
+        path,
+        Some(encoding_for_label(&encoding)?),
+    )
+}
 
 #[test]
 fn list_project_entries_includes_empty_folders() {
@@ -9,7 +25,7 @@ fn list_project_entries_includes_empty_folders() {
     temp.mkdir("also_empty");
     temp.write_text("has_file/note.md", "# note");
 
-    let mut entries = list_project_entries(temp.path().to_string_lossy().to_string(), false)
+    let mut entries = list_project_entries(temp.path().to_string_lossy().to_string(), false, false)
         .expect("list project entries should succeed");
 
     entries.sort_by(|a, b| a.rel_path.cmp(&b.rel_path));
@@ -43,7 +59,7 @@ fn list_project_entries_includes_empty_folders() {
 }
 
 #[test]
-fn list_project_entries_filters_hidden_binary_and_build_artifacts() {
+fn list_project_entries_filters_hidden_and_build_artifacts_and_badges_binary_files() {
     let temp = TempTestDir::new();
     let root = temp.path().to_path_buf();
 
@@ -51,15 +67,16 @@ fn list_project_entries_filters_hidden_binary_and_build_artifacts() {
     temp.write_text("nested/b.txt", "text");
     temp.write_text("nested/c.JSON", "{}");
     temp.write_text(".hidden.md", "skip");
+    temp.write_text(".gitignore", "node_modules/\ntarget/\ndist/\nbuild/\n");
     temp.write_text(".git/ignored.md", "skip");
     temp.write_text("node_modules/ignored.js", "skip");
     temp.write_text("target/ignored.rs", "skip");
     temp.write_text("dist/ignored.txt", "skip");
     temp.write_text("build/ignored.txt", "skip");
     temp.write_text(".config/ignored.yaml", "skip");
-    temp.write_bytes("image.png", &[0x89, b'P', b'N', b'G']);
+    temp.write_bytes("image.png", b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR");
 
-    let mut entries = list_project_entries(root.to_string_lossy().to_string(), false)
+    let mut entries = list_project_entries(root.to_string_lossy().to_string(), false, false)
         .expect("list project entries should succeed");
 
     entries.sort_by(|a, b| a.rel_path.cmp(&b.rel_path));
@@ -76,8 +93,15 @@ fn list_project_entries_filters_hidden_binary_and_build_artifacts() {
     let rel_paths: Vec<String> = entries.iter().map(|e| e.rel_path.clone()).collect();
     assert_eq!(
         rel_paths,
-        vec!["a.md".to_string(), rel_nested_b, rel_nested_c]
+        vec![
+            "a.md".to_string(),
+            "image.png".to_string(),
+            rel_nested_b,
+            rel_nested_c
+        ]
     );
+    let badges: Vec<Option<&str>> = entries.iter().map(|e| e.binary_type.as_deref()).collect();
+    assert_eq!(badges, vec![None, Some("png"), None, None]);
 
     assert!(entries
         .iter()
@@ -92,14 +116,14 @@ fn list_project_entries_shows_hidden_when_flag_true() {
     temp.write_text("visible.md", "# visible");
     temp.write_text(".hidden.md", "# hidden");
     temp.write_text(".secret.yaml", "key: val");
-    temp.write_text(".gitignore", "*.log");
+    temp.write_text(".gitignore", "*.log\nnode_modules/\ntarget/\n");
     temp.write_text(".editorconfig", "root = true");
     temp.write_text(".github/workflow.yaml", "on: push");
     temp.write_text(".git/config.txt", "skip");
     temp.write_text("node_modules/pkg.js", "skip");
     temp.write_text("target/debug.rs", "skip");
 
-    let mut entries = list_project_entries(root.to_string_lossy().to_string(), true)
+    let mut entries = list_project_entries(root.to_string_lossy().to_string(), true, false)
         .expect("list project entries should succeed");
 
     entries.sort_by(|a, b| a.rel_path.cmp(&b.rel_path));
@@ -156,7 +180,7 @@ fn list_project_entries_excludes_extensionless_dotfiles_when_hidden_off() {
     temp.write_text(".gitignore", "*.log");
     temp.write_text(".editorconfig", "root = true");
 
-    let entries = list_project_entries(temp.path().to_string_lossy().to_string(), false)
+    let entries = list_project_entries(temp.path().to_string_lossy().to_string(), false, false)
         .expect("list project entries should succeed");
     let rel_paths: Vec<String> = entries.iter().map(|e| e.rel_path.clone()).collect();
 
@@ -182,7 +206,7 @@ fn list_project_entries_excludes_ds_store_even_when_show_hidden_true() {
     temp.write_text(".gitignore", "*.log");
     temp.write_bytes(".DS_Store", &[0x00, 0x01, 0x02]);
 
-    let entries = list_project_entries(temp.path().to_string_lossy().to_string(), true)
+    let entries = list_project_entries(temp.path().to_string_lossy().to_string(), true, false)
         .expect("list project entries should succeed");
     let rel_paths: Vec<String> = entries.iter().map(|e| e.rel_path.clone()).collect();
 
@@ -196,12 +220,69 @@ fn list_project_entries_excludes_ds_store_even_when_show_hidden_true() {
     );
 }
 
+#[test]
+fn list_project_entries_honors_gitignore_and_teexignore() {
+    let temp = TempTestDir::new();
+
+    temp.write_text(".gitignore", "*.log\ncache/\n");
+    temp.write_text(".teexignore", "drafts/\n");
+    temp.write_text("keep.md", "# keep");
+    temp.write_text("debug.log", "noise");
+    temp.write_text("cache/data.json", "{}");
+    temp.write_text("drafts/wip.md", "# wip");
+    temp.write_text("nested/.gitignore", "local.txt\n");
+    temp.write_text("nested/local.txt", "skip");
+    temp.write_text("nested/shared.txt", "keep");
+    temp.write_text("node_modules/pkg.js", "no longer blocked by name");
+
+    let entries = list_project_entries(temp.path().to_string_lossy().to_string(), false, false)
+        .expect("list project entries should succeed");
+    let rel_paths: Vec<String> = entries.iter().map(|e| e.rel_path.clone()).collect();
+
+    let rel_shared = Path::new("nested")
+        .join("shared.txt")
+        .to_string_lossy()
+        .to_string();
+    let rel_pkg = Path::new("node_modules")
+        .join("pkg.js")
+        .to_string_lossy()
+        .to_string();
+    assert_eq!(rel_paths, vec!["keep.md".to_string(), rel_shared, rel_pkg]);
+}
+
+#[test]
+fn list_project_entries_shows_ignored_when_flag_true() {
+    let temp = TempTestDir::new();
+
+    temp.write_text(".gitignore", "*.log\n");
+    temp.write_text(".teexignore", "drafts/\n");
+    temp.write_text("keep.md", "# keep");
+    temp.write_text("debug.log", "noise");
+    temp.write_text("drafts/wip.md", "# wip");
+    temp.write_text(".git/config.txt", "skip");
+
+    let entries = list_project_entries(temp.path().to_string_lossy().to_string(), false, true)
+        .expect("list project entries should succeed");
+    let rel_paths: Vec<String> = entries.iter().map(|e| e.rel_path.clone()).collect();
+
+    let rel_wip = Path::new("drafts")
+        .join("wip.md")
+        .to_string_lossy()
+        .to_string();
+    assert!(rel_paths.contains(&"debug.log".to_string()));
+    assert!(rel_paths.contains(&rel_wip));
+    assert!(
+        !rel_paths.iter().any(|p| p.starts_with(".git")),
+        "should still exclude .git/ when showing ignored files"
+    );
+}
+
 #[test]
 fn list_project_entries_errors_when_root_is_not_directory() {
     let temp = TempTestDir::new();
     let file = temp.write_text("just-a-file.txt", "hi");
 
-    let error = list_project_entries(file.to_string_lossy().to_string(), false).unwrap_err();
+    let error = list_project_entries(file.to_string_lossy().to_string(), false, false).unwrap_err();
     assert!(error.contains("not a folder"));
 }
 
@@ -211,26 +292,268 @@ fn read_and_write_text_file_round_trip_preserves_content_and_kind() {
     let file = temp.path().join("draft.md");
     let file_string = file.to_string_lossy().to_string();
 
-    write_text_file(file_string.clone(), "# Title\n\nBody".to_string())
-        .expect("write text file should succeed");
-
-    let payload = read_text_file(file_string.clone()).expect("read text file should succeed");
+    save_text_file(
+        file_string.clone(),
+        "# Title\n\nBody".to_string(),
+        None,
+        None,
+        None,
+        None,
+    )
+    .expect("write text file should succeed");
+
+    let payload = read_payload(file_string.clone()).expect("read text file should succeed");
     assert_eq!(payload.path, file_string);
     assert_eq!(payload.content, "# Title\n\nBody");
     assert_eq!(payload.kind, "markdown");
+    assert_eq!(payload.language, "markdown");
     assert!(payload.writable);
 }
 
 #[test]
-fn read_text_file_returns_error_for_missing_or_non_utf8_files() {
+fn read_text_file_reports_language_for_extensionless_scripts() {
+    let temp = TempTestDir::new();
+    let script = temp.write_text("bin/deploy", "#!/usr/bin/env python3\nprint('hi')\n");
+
+    let payload = read_payload(script.to_string_lossy().to_string()).expect("read should succeed");
+    assert_eq!(payload.kind, "code");
+    assert_eq!(payload.language, "python");
+}
+
+#[test]
+fn write_text_file_restores_crlf_and_bom_recorded_on_read() {
+    let temp = TempTestDir::new();
+    let file = temp.write_bytes("notes.txt", b"\xEF\xBB\xBFone\r\ntwo\r\n");
+    let file_string = file.to_string_lossy().to_string();
+
+    let payload = read_payload(file_string.clone()).expect("read text file should succeed");
+    assert_eq!(payload.content, "one\r\ntwo\r\n");
+    assert_eq!(payload.line_ending, "crlf");
+    assert!(payload.has_bom);
+
+    // The editor hands back LF-normalized text without the BOM.
+    save_text_file(
+        file_string.clone(),
+        "one\ntwo\nthree\n".to_string(),
+        None,
+        None,
+        None,
+        None,
+    )
+    .expect("write text file should succeed");
+    assert_eq!(
+        fs::read(&file).unwrap(),
+        b"\xEF\xBB\xBFone\r\ntwo\r\nthree\r\n"
+    );
+
+    save_text_file(
+        file_string,
+        "one\r\n".to_string(),
+        Some("lf".to_string()),
+        Some(false),
+        None,
+        None,
+    )
+    .expect("write text file should succeed");
+    assert_eq!(fs::read(&file).unwrap(), b"one\n");
+}
+
+#[test]
+fn write_text_file_with_stale_version_reports_conflict_and_keeps_disk_content() {
+    let temp = TempTestDir::new();
+    let file = temp.write_text("plan.md", "original\n");
+    let file_string = file.to_string_lossy().to_string();
+    let opened = read_payload(file_string.clone()).expect("read text file should succeed");
+
+    let saved = save_text_file(
+        file_string.clone(),
+        "ours v1\n".to_string(),
+        None,
+        None,
+        None,
+        Some(opened.version.clone()),
+    )
+    .expect("write with current version should succeed");
+    assert_ne!(saved.version, opened.version);
+
+    fs::write(&file, "agent rewrite\n").unwrap();
+    let error = save_text_file(
+        file_string.clone(),
+        "ours v2\n".to_string(),
+        None,
+        None,
+        None,
+        Some(saved.version.clone()),
+    )
+    .unwrap_err();
+    match error {
+        WriteFileError::Conflict(conflict) => {
+            assert_eq!(conflict.ours, "ours v2\n");
+            assert_eq!(conflict.theirs, "agent rewrite\n");
+            assert_eq!(conflict.expected_version, saved.version);
+            assert_ne!(conflict.current_version, saved.version);
+        }
+        other => panic!("expected conflict, got {other:?}"),
+    }
+    assert_eq!(fs::read_to_string(&file).unwrap(), "agent rewrite\n");
+
+    let reloaded = read_payload(file_string.clone()).expect("read text file should succeed");
+    save_text_file(
+        file_string,
+        "merged\n".to_string(),
+        None,
+        None,
+        None,
+        Some(reloaded.version),
+    )
+    .expect("write with refreshed version should succeed");
+    assert_eq!(fs::read_to_string(&file).unwrap(), "merged\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn write_text_file_follows_symlinks_and_keeps_mode_bits() {
+    use std::os::unix::fs::{symlink, PermissionsExt};
+
+    let temp = TempTestDir::new();
+    let target = temp.write_text("real/script.sh", "echo old\n");
+    fs::set_permissions(&target, fs::Permissions::from_mode(0o755)).unwrap();
+    let link = temp.path().join("script.sh");
+    symlink(&target, &link).unwrap();
+
+    save_text_file(
+        link.to_string_lossy().to_string(),
+        "echo new\n".to_string(),
+        None,
+        None,
+        None,
+        None,
+    )
+    .expect("write through symlink should succeed");
+
+    assert!(fs::symlink_metadata(&link)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert_eq!(fs::read_to_string(&target).unwrap(), "echo new\n");
+    let mode = fs::metadata(&target).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o755);
+    let leftovers: Vec<_> = fs::read_dir(target.parent().unwrap())
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().ends_with(".tmp"))
+        .collect();
+    assert!(leftovers.is_empty());
+}
+
+#[test]
+fn read_text_file_returns_error_for_missing_binary_or_malformed_files() {
     let temp = TempTestDir::new();
     let missing = temp.path().join("missing.txt");
-    let missing_error = read_text_file(missing.to_string_lossy().to_string()).unwrap_err();
+    let missing_error = read_payload(missing.to_string_lossy().to_string()).unwrap_err();
     assert!(missing_error.contains("not found"));
 
-    let binary = temp.write_bytes("bad.txt", &[0xFF, 0xFE, 0x00]);
-    let utf8_error = read_text_file(binary.to_string_lossy().to_string()).unwrap_err();
-    assert!(utf8_error.contains("UTF-8"));
+    let truncated = temp.write_bytes("bad.txt", &[0xFF, 0xFE, 0x00]);
+    let utf16_error = read_payload(truncated.to_string_lossy().to_string()).unwrap_err();
+    assert!(utf16_error.contains("UTF-16LE"));
+
+    let binary = temp.write_bytes(
+        "image.dat",
+        &[
+            0x89, b'P', b'N', b'G', 0x00, 0x00, 0x00, 0x0D, 0xFF, 0x00, 0x10, 0x80,
+        ],
+    );
+    let binary_error = read_payload(binary.to_string_lossy().to_string()).unwrap_err();
+    assert!(binary_error.contains("binary"));
+}
+
+#[test]
+fn read_and_write_text_file_round_trip_legacy_single_byte_encoding() {
+    let temp = TempTestDir::new();
+    let file = temp.write_bytes(
+        "legacy.csv",
+        b"name,dish\r\nRen\xE9e,cr\xE8me br\xFBl\xE9e\r\nFran\xE7ois,p\xE2t\xE9\r\n",
+    );
+    let file_string = file.to_string_lossy().to_string();
+
+    let payload = read_payload(file_string.clone()).expect("read text file should succeed");
+    assert_eq!(payload.encoding, "windows-1252");
+    assert_eq!(
+        payload.content,
+        "name,dish\r\nRen\u{e9}e,cr\u{e8}me br\u{fb}l\u{e9}e\r\nFran\u{e7}ois,p\u{e2}t\u{e9}\r\n"
+    );
+
+    save_text_file(
+        file_string.clone(),
+        "name,dish\nZo\u{eb},na\u{ef}ve\n".to_string(),
+        None,
+        None,
+        None,
+        Some(payload.version),
+    )
+    .expect("write text file should succeed");
+    assert_eq!(
+        fs::read(&file).unwrap(),
+        b"name,dish\r\nZo\xEB,na\xEFve\r\n"
+    );
+
+    let error = save_text_file(
+        file_string,
+        "\u{65e5}\u{672c}\n".to_string(),
+        None,
+        None,
+        None,
+        None,
+    )
+    .unwrap_err();
+    match error {
+        WriteFileError::Io { message } => assert!(message.contains("cannot be saved")),
+        other => panic!("expected io error, got {other:?}"),
+    }
+    assert_eq!(
+        fs::read(&file).unwrap(),
+        b"name,dish\r\nZo\xEB,na\xEFve\r\n"
+    );
+}
+
+#[test]
+fn read_and_write_text_file_round_trip_utf16_with_bom() {
+    let temp = TempTestDir::new();
+    let mut bytes = vec![0xFF, 0xFE];
+    bytes.extend("a=1\r\n".encode_utf16().flat_map(|u| u.to_le_bytes()));
+    let file = temp.write_bytes("settings.ini", &bytes);
+    let file_string = file.to_string_lossy().to_string();
+
+    let payload = read_payload(file_string.clone()).expect("read text file should succeed");
+    assert_eq!(payload.encoding, "UTF-16LE");
+    assert!(payload.has_bom);
+    assert_eq!(payload.content, "a=1\r\n");
+
+    save_text_file(file_string, "a=2\n".to_string(), None, None, None, None)
+        .expect("write text file should succeed");
+    let mut expected = vec![0xFF, 0xFE];
+    expected.extend("a=2\r\n".encode_utf16().flat_map(|u| u.to_le_bytes()));
+    assert_eq!(fs::read(&file).unwrap(), expected);
+}
+
+#[test]
+fn reopen_with_encoding_overrides_detection() {
+    let temp = TempTestDir::new();
+    // "日本語" in Shift_JIS.
+    let file = temp.write_bytes("notes.txt", &[0x93, 0xFA, 0x96, 0x7B, 0x8C, 0xEA]);
+    let file_string = file.to_string_lossy().to_string();
+
+    let payload = reopen_payload(file_string.clone(), "shift_jis".to_string())
+        .expect("reopen should succeed");
+    assert_eq!(payload.encoding, "Shift_JIS");
+    assert_eq!(payload.content, "\u{65e5}\u{672c}\u{8a9e}");
+
+    let latin1 =
+        reopen_payload(file_string.clone(), "latin1".to_string()).expect("reopen should succeed");
+    assert_eq!(latin1.encoding, "windows-1252");
+
+    let error = reopen_payload(file_string, "klingon".to_string()).unwrap_err();
+    assert!(error.contains("Unknown encoding"));
 }
 
 #[test]
@@ -239,7 +562,7 @@ fn trash_file_moves_file_to_trash() {
     let file = temp.write_text("delete-me.md", "bye");
     let file_string = file.to_string_lossy().to_string();
 
-    trash_file(file_string).expect("trash file should succeed");
+    move_to_trash(Path::new(&file_string)).expect("trash file should succeed");
     assert!(!file.exists(), "file should no longer exist on disk");
 }
 
@@ -248,15 +571,18 @@ fn trash_file_returns_error_for_missing_file() {
     let temp = TempTestDir::new();
     let missing = temp.path().join("nope.txt");
 
-    let error = trash_file(missing.to_string_lossy().to_string()).unwrap_err();
+    let error = move_to_trash(&missing).unwrap_err();
     assert!(error.contains("not found"));
 }
 
 #[test]
 fn format_structured_text_formats_json_input() {
-    let result =
-        format_structured_text("{\"name\":\"teex\"}".to_string(), Some("json".to_string()))
-            .expect("format structured text should succeed");
+    let result = format_structured_text(
+        "{\"name\":\"teex\"}".to_string(),
+        Some("json".to_string()),
+        None,
+    )
+    .expect("format structured text should succeed");
 
     assert_eq!(result.detected_kind.as_deref(), Some("json"));
     assert!(result.changed);
@@ -266,19 +592,19 @@ fn format_structured_text_formats_json_input() {
 #[test]
 fn format_structured_text_formats_yaml_input() {
     let input = "root:\n    child: yes\n    count: 1".to_string();
-    let result = format_structured_text(input, Some("yaml".to_string()))
+    let result = format_structured_text(input, Some("yaml".to_string()), None)
         .expect("format structured text should succeed");
 
     assert_eq!(result.detected_kind.as_deref(), Some("yaml"));
     assert!(result.changed);
-    assert_eq!(result.formatted, "root:\n  child: 'yes'\n  count: 1");
+    assert_eq!(result.formatted, "root:\n  child: yes\n  count: 1");
 }
 
 #[test]
 fn format_structured_text_returns_unchanged_for_plain_text() {
     let input = "hello world".to_string();
-    let result =
-        format_structured_text(input.clone(), None).expect("format structured text should succeed");
+    let result = format_structured_text(input.clone(), None, None)
+        .expect("format structured text should succeed");
 
     assert_eq!(result.detected_kind, None);
     assert!(!result.changed);
@@ -288,7 +614,7 @@ fn format_structured_text_returns_unchanged_for_plain_text() {
 #[test]
 fn format_structured_text_formats_toml_input() {
     let input = "name=\"teex\"\nversion=\"0.1.0\"\n[dependencies]\nserde=\"1\"".to_string();
-    let result = format_structured_text(input, Some("toml".to_string()))
+    let result = format_structured_text(input, Some("toml".to_string()), None)
         .expect("format structured text should succeed");
 
     assert_eq!(result.detected_kind.as_deref(), Some("toml"));
@@ -301,7 +627,7 @@ fn format_structured_text_formats_toml_input() {
 #[test]
 fn format_structured_text_formats_xml_input() {
     let input = "<root><child>text</child><other/></root>".to_string();
-    let result = format_structured_text(input, Some("xml".to_string()))
+    let result = format_structured_text(input, Some("xml".to_string()), None)
         .expect("format structured text should succeed");
 
     assert_eq!(result.detected_kind.as_deref(), Some("xml"));
@@ -313,7 +639,7 @@ fn format_structured_text_formats_xml_input() {
 #[test]
 fn format_structured_text_indents_xml_with_existing_linebreaks() {
     let input = "<note>\n<to>Tove</to>\n<from>Jani</from>\n<heading>Reminder</heading>\n<body>Don't forget me this weekend!</body>\n</note>".to_string();
-    let result = format_structured_text(input, Some("xml".to_string()))
+    let result = format_structured_text(input, Some("xml".to_string()), None)
         .expect("format structured text should succeed");
 
     assert_eq!(result.detected_kind.as_deref(), Some("xml"));
@@ -325,7 +651,7 @@ fn format_structured_text_indents_xml_with_existing_linebreaks() {
 #[test]
 fn format_structured_text_formats_csv_input() {
     let input = "name,age,city\nAlice,30,NYC\nBob,25,LA".to_string();
-    let result = format_structured_text(input, Some("csv".to_string()))
+    let result = format_structured_text(input, Some("csv".to_string()), None)
         .expect("format structured text should succeed");
 
     assert_eq!(result.detected_kind.as_deref(), Some("csv"));
@@ -339,7 +665,7 @@ fn format_structured_text_formats_csv_input() {
 #[test]
 fn format_structured_text_returns_unchanged_for_already_formatted_toml() {
     let input = "name = \"teex\"\nversion = \"0.1.0\"".to_string();
-    let result = format_structured_text(input.clone(), Some("toml".to_string()))
+    let result = format_structured_text(input.clone(), Some("toml".to_string()), None)
         .expect("format structured text should succeed");
 
     assert_eq!(result.detected_kind.as_deref(), Some("toml"));
@@ -349,7 +675,7 @@ fn format_structured_text_returns_unchanged_for_already_formatted_toml() {
 #[test]
 fn format_structured_text_returns_unchanged_for_already_formatted_json() {
     let input = "{\n  \"name\": \"teex\"\n}".to_string();
-    let result = format_structured_text(input.clone(), Some("json".to_string()))
+    let result = format_structured_text(input.clone(), Some("json".to_string()), None)
         .expect("format structured text should succeed");
 
     assert_eq!(result.detected_kind.as_deref(), Some("json"));
@@ -359,9 +685,8 @@ fn format_structured_text_returns_unchanged_for_already_formatted_json() {
 
 #[test]
 fn format_structured_text_returns_unchanged_for_already_formatted_yaml() {
-    // serde_yml normalizes "0.1.0" → quoted string, so use values that roundtrip cleanly
     let input = "name: teex\ncount: 1".to_string();
-    let result = format_structured_text(input.clone(), Some("yaml".to_string()))
+    let result = format_structured_text(input.clone(), Some("yaml".to_string()), None)
         .expect("format structured text should succeed");
 
     assert_eq!(result.detected_kind.as_deref(), Some("yaml"));
@@ -372,7 +697,7 @@ fn format_structured_text_returns_unchanged_for_already_formatted_yaml() {
 #[test]
 fn format_structured_text_returns_unchanged_for_already_formatted_xml() {
     let input = "<note>\n  <to>Tove</to>\n</note>".to_string();
-    let result = format_structured_text(input.clone(), Some("xml".to_string()))
+    let result = format_structured_text(input.clone(), Some("xml".to_string()), None)
         .expect("format structured text should succeed");
 
     assert_eq!(result.detected_kind.as_deref(), Some("xml"));
@@ -383,7 +708,7 @@ fn format_structured_text_returns_unchanged_for_already_formatted_xml() {
 #[test]
 fn format_structured_text_returns_unchanged_for_already_formatted_csv() {
     let input = "name ,age\nAlice,30".to_string();
-    let result = format_structured_text(input.clone(), Some("csv".to_string()))
+    let result = format_structured_text(input.clone(), Some("csv".to_string()), None)
         .expect("format structured text should succeed");
 
     assert_eq!(result.detected_kind.as_deref(), Some("csv"));
@@ -397,7 +722,7 @@ fn format_structured_text_returns_unchanged_for_already_formatted_csv() {
 fn format_structured_text_auto_detects_json() {
     let input = "{\"a\":1}".to_string();
     let result =
-        format_structured_text(input, None).expect("format structured text should succeed");
+        format_structured_text(input, None, None).expect("format structured text should succeed");
 
     assert_eq!(result.detected_kind.as_deref(), Some("json"));
     assert!(result.changed);
@@ -408,13 +733,13 @@ fn format_structured_text_auto_detects_json() {
 fn format_structured_text_auto_detects_yaml_mapping() {
     let input = "{host: localhost, port: 8080}".to_string();
     let result =
-        format_structured_text(input, None).expect("format structured text should succeed");
+        format_structured_text(input, None, None).expect("format structured text should succeed");
 
-    // JSON is tried first but this isn't valid JSON, so YAML picks it up
+    // JSON is tried first but this isn't valid JSON, so YAML picks it up.
+    // Flow collections are kept as written.
     assert_eq!(result.detected_kind.as_deref(), Some("yaml"));
-    assert!(result.changed);
-    assert!(result.formatted.contains("host: localhost"));
-    assert!(result.formatted.contains("port: 8080"));
+    assert!(!result.changed);
+    assert_eq!(result.formatted, "{host: localhost, port: 8080}");
 }
 
 // --- Nested structures ---
@@ -422,7 +747,7 @@ fn format_structured_text_auto_detects_yaml_mapping() {
 #[test]
 fn format_structured_text_formats_nested_json() {
     let input = "{\"a\":{\"b\":{\"c\":1}}}".to_string();
-    let result = format_structured_text(input, Some("json".to_string()))
+    let result = format_structured_text(input, Some("json".to_string()), None)
         .expect("format structured text should succeed");
 
     assert!(result.changed);
@@ -435,7 +760,7 @@ fn format_structured_text_formats_nested_json() {
 #[test]
 fn format_structured_text_formats_nested_xml() {
     let input = "<a><b><c>deep</c></b></a>".to_string();
-    let result = format_structured_text(input, Some("xml".to_string()))
+    let result = format_structured_text(input, Some("xml".to_string()), None)
         .expect("format structured text should succeed");
 
     assert!(result.changed);
@@ -445,7 +770,7 @@ fn format_structured_text_formats_nested_xml() {
 #[test]
 fn format_structured_text_formats_toml_with_nested_tables() {
     let input = "[server]\nhost=\"localhost\"\nport=8080".to_string();
-    let result = format_structured_text(input, Some("toml".to_string()))
+    let result = format_structured_text(input, Some("toml".to_string()), None)
         .expect("format structured text should succeed");
 
     assert!(result.changed);
@@ -459,7 +784,7 @@ fn format_structured_text_formats_toml_with_nested_tables() {
 #[test]
 fn format_structured_text_formats_tsv_input() {
     let input = "name\tage\nAlice\t30\nBob\t25".to_string();
-    let result = format_structured_text(input, Some("csv".to_string()))
+    let result = format_structured_text(input, Some("csv".to_string()), None)
         .expect("format structured text should succeed");
 
     assert_eq!(result.detected_kind.as_deref(), Some("csv"));
@@ -473,7 +798,7 @@ fn format_structured_text_formats_tsv_input() {
 #[test]
 fn format_structured_text_formats_json_array() {
     let input = "[1,2,3]".to_string();
-    let result = format_structured_text(input, Some("json".to_string()))
+    let result = format_structured_text(input, Some("json".to_string()), None)
         .expect("format structured text should succeed");
 
     assert!(result.changed);
@@ -485,12 +810,64 @@ fn format_structured_text_formats_json_array() {
 #[test]
 fn format_structured_text_formats_yaml_sequence() {
     let input = "[1, 2, 3]".to_string();
-    let result = format_structured_text(input, Some("yaml".to_string()))
+    let result = format_structured_text(input, Some("yaml".to_string()), None)
+        .expect("format structured text should succeed");
+
+    assert_eq!(result.detected_kind.as_deref(), Some("yaml"));
+    assert!(!result.changed);
+    assert_eq!(result.formatted, "[1, 2, 3]");
+}
+
+#[test]
+fn format_structured_text_formats_multi_document_yaml() {
+    let input =
+        "kind: Service\nspec:\n    port: 80\n---\nkind: Deployment\nspec:\n    replicas: 2\n"
+            .to_string();
+    let result = format_structured_text(input, Some("yaml".to_string()), None)
         .expect("format structured text should succeed");
 
     assert_eq!(result.detected_kind.as_deref(), Some("yaml"));
     assert!(result.changed);
-    assert!(result.formatted.contains("- 1"));
+    assert_eq!(
+        result.formatted,
+        "kind: Service\nspec:\n  port: 80\n---\nkind: Deployment\nspec:\n  replicas: 2"
+    );
+}
+
+#[test]
+fn format_structured_text_keeps_yaml_comments_while_reindenting() {
+    let input = "# Deployment\napiVersion: apps/v1   \nspec:\n    replicas:   2 # scaled by hand\n    containers:\n    -   name: web\n        # pinned\n        image: \"web:1\"\n        args: [--port, '80']\n        script: |\n            echo one\n              indented\n".to_string();
+    let result = format_structured_text(input, Some("yaml".to_string()), None)
+        .expect("format structured text should succeed");
+
+    assert_eq!(result.detected_kind.as_deref(), Some("yaml"));
+    assert_eq!(
+        result.formatted,
+        "# Deployment\napiVersion: apps/v1\nspec:\n  replicas: 2 # scaled by hand\n  containers:\n  - name: web\n    # pinned\n    image: \"web:1\"\n    args: [--port, '80']\n    script: |\n      echo one\n        indented"
+    );
+}
+
+#[test]
+fn format_structured_text_refuses_yaml_it_cannot_reindent_safely() {
+    let input = "text: |2\n     leading spaces\n".to_string();
+    let result = format_structured_text(input.clone(), Some("yaml".to_string()), None)
+        .expect("format structured text should succeed");
+
+    assert!(!result.changed);
+    assert_eq!(result.formatted, input);
+}
+
+#[test]
+fn format_structured_text_keeps_toml_comments_and_layout() {
+    let input = "# Package\n[package]\n  name=\"teex\"   # the app\n\n\n\nversion = \"0.1.0\"\n[dependencies]\n# serialization\nserde = { version = \"1\", features = [\"derive\"] }\nlist = [\n    1, # one\n    2,\n]\n".to_string();
+    let result = format_structured_text(input, Some("toml".to_string()), None)
+        .expect("format structured text should succeed");
+
+    assert_eq!(result.detected_kind.as_deref(), Some("toml"));
+    assert_eq!(
+        result.formatted,
+        "# Package\n[package]\nname = \"teex\" # the app\n\nversion = \"0.1.0\"\n\n[dependencies]\n# serialization\nserde = { version = \"1\", features = [\"derive\"] }\nlist = [\n    1, # one\n    2,\n]"
+    );
 }
 
 // --- XML with attributes ---
@@ -498,7 +875,7 @@ fn format_structured_text_formats_yaml_sequence() {
 #[test]
 fn format_structured_text_formats_xml_with_attributes() {
     let input = "<root attr=\"val\"><child id=\"1\">text</child></root>".to_string();
-    let result = format_structured_text(input, Some("xml".to_string()))
+    let result = format_structured_text(input, Some("xml".to_string()), None)
         .expect("format structured text should succeed");
 
     assert!(result.changed);
@@ -510,7 +887,7 @@ fn format_structured_text_formats_xml_with_attributes() {
 #[test]
 fn format_structured_text_returns_unchanged_for_invalid_json() {
     let input = "{not valid json".to_string();
-    let result = format_structured_text(input.clone(), Some("json".to_string()))
+    let result = format_structured_text(input.clone(), Some("json".to_string()), None)
         .expect("format structured text should succeed");
 
     assert!(!result.changed);
@@ -520,7 +897,7 @@ fn format_structured_text_returns_unchanged_for_invalid_json() {
 #[test]
 fn format_structured_text_returns_unchanged_for_invalid_toml() {
     let input = "[[[invalid toml".to_string();
-    let result = format_structured_text(input.clone(), Some("toml".to_string()))
+    let result = format_structured_text(input.clone(), Some("toml".to_string()), None)
         .expect("format structured text should succeed");
 
     assert!(!result.changed);
@@ -530,7 +907,7 @@ fn format_structured_text_returns_unchanged_for_invalid_toml() {
 #[test]
 fn format_structured_text_returns_unchanged_for_invalid_xml() {
     let input = "not xml at all {{{".to_string();
-    let result = format_structured_text(input.clone(), Some("xml".to_string()))
+    let result = format_structured_text(input.clone(), Some("xml".to_string()), None)
         .expect("format structured text should succeed");
 
     assert!(!result.changed);
@@ -542,7 +919,7 @@ fn format_structured_text_returns_unchanged_for_invalid_xml() {
 #[test]
 fn format_structured_text_formats_csv_with_uneven_columns() {
     let input = "a,bb,ccc\n1111,22,3".to_string();
-    let result = format_structured_text(input, Some("csv".to_string()))
+    let result = format_structured_text(input, Some("csv".to_string()), None)
         .expect("format structured text should succeed");
 
     assert!(result.changed);
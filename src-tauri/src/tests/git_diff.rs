@@ -248,6 +248,43 @@ diff --git a/b.txt b/b.txt
         .any(|l| l.line_type == "removed" && l.content == "bar"));
 }
 
+#[test]
+fn full_diff_reports_file_status() {
+    let diff = "\
+diff --git a/new.txt b/new.txt
+new file mode 100644
+--- /dev/null
++++ b/new.txt
+@@ -0,0 +1 @@
++hello
+diff --git a/old.txt b/old.txt
+deleted file mode 100644
+--- a/old.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-bye
+diff --git a/same.txt b/same.txt
+--- a/same.txt
++++ b/same.txt
+@@ -1 +1 @@
+-a
++b
+";
+    let result = parse_full_unified_diff(diff);
+    let statuses: Vec<(&str, &str)> = result
+        .iter()
+        .map(|f| (f.rel_path.as_str(), f.status.as_str()))
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            ("new.txt", "added"),
+            ("old.txt", "removed"),
+            ("same.txt", "changed")
+        ]
+    );
+}
+
 #[test]
 fn full_diff_line_type_classification() {
     let diff = "\
@@ -0,0 +1,95 @@
+use crate::files::format_structured_text;
+use crate::json_format::{format_json_document, format_json_lines, JsonStyle};
+
+const SETTINGS: &str = r#"// VS Code settings
+{
+    "editor.tabSize": 2, // two spaces
+  /* theme */ "workbench.colorTheme":"Default Dark",
+    "files.exclude": {"**/.git": true,},
+}"#;
+
+#[test]
+fn jsonc_keeps_comments_and_trailing_commas() {
+    let formatted = format_json_document(SETTINGS, JsonStyle::Pretty, false).unwrap();
+    assert_eq!(
+        formatted,
+        "// VS Code settings\n{\n  \"editor.tabSize\": 2, // two spaces\n  /* theme */\n  \"workbench.colorTheme\": \"Default Dark\",\n  \"files.exclude\": {\n    \"**/.git\": true,\n  },\n}"
+    );
+    assert!(format_json_document(SETTINGS, JsonStyle::Pretty, true).is_none());
+
+    let result =
+        format_structured_text(SETTINGS.to_string(), Some("json".to_string()), None).unwrap();
+    assert_eq!(result.detected_kind.as_deref(), Some("jsonc"));
+    assert!(result.formatted.contains("// two spaces"));
+}
+
+#[test]
+fn json5_keeps_unquoted_keys_and_literals() {
+    let input = "{unquoted:'single', hex:0xFF, inf:+Infinity, list:[.5,],}";
+    let formatted = format_json_document(input, JsonStyle::Pretty, false).unwrap();
+    assert_eq!(
+        formatted,
+        "{\n  unquoted: 'single',\n  hex: 0xFF,\n  inf: +Infinity,\n  list: [\n    .5,\n  ],\n}"
+    );
+    assert!(format_json_document("{a: }", JsonStyle::Pretty, false).is_none());
+}
+
+#[test]
+fn sort_keys_moves_comments_with_their_entries() {
+    let input = "{\n  // about b\n  \"b\": 1,\n  \"a\": {\"z\": 1, \"y\": 2}\n}";
+    let formatted = format_json_document(input, JsonStyle::SortKeys, false).unwrap();
+    assert_eq!(
+        formatted,
+        "{\n  \"a\": {\n    \"y\": 2,\n    \"z\": 1\n  },\n  // about b\n  \"b\": 1\n}"
+    );
+}
+
+#[test]
+fn minify_drops_layout_comments_and_trailing_commas() {
+    let formatted = format_json_document(SETTINGS, JsonStyle::Minify, false).unwrap();
+    assert_eq!(
+        formatted,
+        r#"{"editor.tabSize":2,"workbench.colorTheme":"Default Dark","files.exclude":{"**/.git":true}}"#
+    );
+}
+
+#[test]
+fn jsonl_is_formatted_line_by_line_and_reports_invalid_lines() {
+    let input = "{ \"b\": 1, \"a\": [1, 2] }\nnot json\n\n{\"ok\" : true}\n{\"broken\":";
+    let (formatted, invalid) = format_json_lines(input, JsonStyle::Pretty).unwrap();
+    assert_eq!(
+        formatted,
+        "{\"b\":1,\"a\":[1,2]}\nnot json\n\n{\"ok\":true}\n{\"broken\":"
+    );
+    assert_eq!(invalid, vec![2, 5]);
+
+    let (sorted, _) = format_json_lines(input, JsonStyle::SortKeys).unwrap();
+    assert!(sorted.starts_with("{\"a\":[1,2],\"b\":1}\n"));
+    assert!(format_json_lines("nothing here", JsonStyle::Pretty).is_none());
+}
+
+#[test]
+fn format_structured_text_applies_styles_to_json_only() {
+    let result = format_structured_text(
+        "{\"b\":1,\"a\":2}".to_string(),
+        Some("json".to_string()),
+        Some("sortKeys".to_string()),
+    )
+    .unwrap();
+    assert_eq!(result.formatted, "{\n  \"a\": 2,\n  \"b\": 1\n}");
+
+    let result = format_structured_text(
+        "a: 1\n".to_string(),
+        Some("yaml".to_string()),
+        Some("minify".to_string()),
+    )
+    .unwrap();
+    assert!(!result.changed);
+    assert_eq!(result.detected_kind, None);
+
+    let result =
+        format_structured_text("{\"a\":1}\n{".to_string(), Some("jsonl".to_string()), None)
+            .unwrap();
+    assert_eq!(result.detected_kind.as_deref(), Some("jsonl"));
+    assert_eq!(result.invalid_lines, vec![2]);
+}
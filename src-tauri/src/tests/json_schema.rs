@@ -0,0 +1,178 @@
+use super::common::TempTestDir;
+use crate::json_format::{relaxed_json_pointer_offset, relaxed_json_value};
+use crate::json_schema::{resolve_schema, schema_diagnostics, SchemaSource};
+use crate::validation::validate_structured_text;
+use crate::yaml_layout::yaml_pointer_position;
+use serde_json::json;
+use std::path::Path;
+
+fn pointer(tokens: &[&str]) -> Vec<String> {
+    tokens.iter().map(|token| token.to_string()).collect()
+}
+
+fn locations(path: &Path, content: &str, kind: &str) -> Vec<(usize, usize)> {
+    schema_diagnostics(path, content, kind)
+        .into_iter()
+        .map(|diagnostic| {
+            assert_eq!(diagnostic.severity, "warning");
+            (diagnostic.line, diagnostic.column)
+        })
+        .collect()
+}
+
+#[test]
+fn relaxed_json_values_and_pointers_follow_the_source() {
+    let content = "{\n  // comment\n  name: 'app',\n  \"list\": [1, {\"a\": true},],\n}";
+    assert_eq!(
+        relaxed_json_value(content),
+        Some(json!({"name": "app", "list": [1, {"a": true}]}))
+    );
+    let offset = relaxed_json_pointer_offset(content, &pointer(&["list", "1", "a"])).unwrap();
+    assert!(content[offset..].starts_with("\"a\""));
+    let offset = relaxed_json_pointer_offset(content, &pointer(&["list", "1"])).unwrap();
+    assert!(content[offset..].starts_with("{\"a\""));
+    let offset = relaxed_json_pointer_offset(content, &pointer(&["missing"])).unwrap();
+    assert_eq!(offset, 0);
+}
+
+#[test]
+fn yaml_pointers_are_found_by_indentation() {
+    let content = "\
+# CI
+jobs:
+  build:
+    steps:
+      - uses: actions/checkout@v4
+        with:
+          depth: 1
+      - run: make
+        bogus: true
+list:
+- a
+- b
+";
+    let at = |tokens: &[&str]| yaml_pointer_position(content, &pointer(tokens));
+    assert_eq!(at(&["jobs", "build", "steps", "1", "bogus"]), (9, 9));
+    assert_eq!(
+        at(&["jobs", "build", "steps", "0", "with", "depth"]),
+        (7, 11)
+    );
+    assert_eq!(at(&["jobs", "build", "steps", "1"]), (8, 7));
+    assert_eq!(at(&["list", "1"]), (12, 1));
+    assert_eq!(at(&["jobs", "build", "missing"]), (3, 3));
+    assert_eq!(at(&[]), (1, 1));
+}
+
+#[test]
+fn bundled_schemas_check_package_json_and_jsonc_tsconfig() {
+    let temp = TempTestDir::new();
+    let package = "{\n  \"name\": \"My Package\",\n  \"private\": \"yes\",\n  \"scripts\": {\"test\": \"node --test\"}\n}";
+    let path = temp.write_text("package.json", package);
+    assert_eq!(locations(&path, package, "json"), vec![(2, 3), (3, 3)]);
+
+    let tsconfig =
+        "{\n  // Node\n  \"compilerOptions\": {\n    \"strict\": \"true\",\n    \"outDir\": \"dist\",\n  },\n}";
+    let path = temp.write_text("web/tsconfig.json", tsconfig);
+    assert_eq!(locations(&path, tsconfig, "json"), vec![(4, 5)]);
+
+    let valid = "{\"name\": \"@scope/pkg\", \"version\": \"1.0.0\"}";
+    let path = temp.write_text("lib/package.json", valid);
+    assert!(schema_diagnostics(&path, valid, "json").is_empty());
+}
+
+#[test]
+fn github_workflows_are_checked_by_path() {
+    let temp = TempTestDir::new();
+    let workflow = "\
+name: CI
+on: [push]
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: cargo test
+        timeout: 10
+";
+    let path = temp.write_text(".github/workflows/ci.yml", workflow);
+    let diagnostics = schema_diagnostics(&path, workflow, "yaml");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!((diagnostics[0].line, diagnostics[0].column), (9, 9));
+    assert!(diagnostics[0].message.contains("timeout"));
+
+    let missing_jobs = "on: push\n";
+    let path = temp.write_text(".github/workflows/empty.yaml", missing_jobs);
+    assert_eq!(locations(&path, missing_jobs, "yaml"), vec![(1, 1)]);
+
+    let elsewhere = temp.write_text("ci.yml", workflow);
+    assert!(schema_diagnostics(&elsewhere, workflow, "yaml").is_empty());
+}
+
+#[test]
+fn local_schemas_resolve_from_the_document_and_teex_toml() {
+    let temp = TempTestDir::new();
+    temp.write_text(
+        "schemas/server.json",
+        r##"{
+  "type": "object",
+  "properties": {
+    "server": {"$ref": "defs.json#/definitions/server"}
+  },
+  "required": ["server"]
+}"##,
+    );
+    temp.write_text(
+        "schemas/defs.json",
+        r#"{"definitions": {"server": {"properties": {"port": {"type": "integer"}}}}}"#,
+    );
+
+    let toml = "\"$schema\" = \"../schemas/server.json\"\n\n[server]\nport = \"80\"\n";
+    let path = temp.write_text("config/app.toml", toml);
+    assert_eq!(
+        resolve_schema(&path, toml, &json!({"$schema": "../schemas/server.json"})),
+        Some(SchemaSource::File(
+            temp.path().join("config").join("../schemas/server.json")
+        ))
+    );
+    assert_eq!(locations(&path, toml, "toml"), vec![(4, 1)]);
+
+    temp.write_text(
+        ".teex.toml",
+        "[schemas]\n\"deploy/*.yaml\" = \"schemas/server.json\"\n\"**/*.yaml\" = \"https://example.com/other.json\"\n",
+    );
+    let yaml = "server:\n  port: eighty\n";
+    let path = temp.write_text("deploy/prod.yaml", yaml);
+    assert_eq!(locations(&path, yaml, "yaml"), vec![(2, 3)]);
+
+    let stream = "server:\n  port: 80\n---\n# second\nserver:\n  port: eighty\n";
+    let path = temp.write_text("deploy/stream.yaml", stream);
+    assert_eq!(locations(&path, stream, "yaml"), vec![(6, 3)]);
+
+    let path = temp.write_text("notes/other.yaml", yaml);
+    let diagnostics = schema_diagnostics(&path, yaml, "yaml");
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("not available offline"));
+}
+
+#[test]
+fn schemas_are_checked_against_bundled_meta_schemas() {
+    let temp = TempTestDir::new();
+    let schema = "{\n  \"$schema\": \"http://json-schema.org/draft-07/schema#\",\n  \"type\": 5\n}";
+    let path = temp.write_text("thing.schema.json", schema);
+    assert_eq!(locations(&path, schema, "json"), vec![(3, 3)]);
+}
+
+#[test]
+fn schema_checks_run_only_for_documents_that_parse() {
+    let temp = TempTestDir::new();
+    let path = temp.write_text("package.json", "");
+    let path = Some(path.to_string_lossy().to_string());
+
+    let broken = validate_structured_text("{\"name\": 1".into(), Some("json".into()), path.clone());
+    assert_eq!(broken.len(), 1);
+    assert_eq!(broken[0].severity, "error");
+
+    let invalid = validate_structured_text("{\"name\": 1}".into(), Some("json".into()), path);
+    assert_eq!(invalid.len(), 1);
+    assert_eq!(invalid[0].severity, "warning");
+}
@@ -0,0 +1,69 @@
+use super::*;
+use crate::languages::detect_language;
+
+fn language_of(name: &str, content: &str) -> &'static str {
+    detect_language(Path::new(name), content).id
+}
+
+#[test]
+fn detect_language_uses_names_and_extensions_from_file_associations() {
+    assert_eq!(language_of("App.vue", ""), "vue");
+    assert_eq!(language_of("Page.svelte", ""), "svelte");
+    assert_eq!(language_of("solve.jl", ""), "julia");
+    assert_eq!(language_of("flake.nix", ""), "nix");
+    assert_eq!(language_of("fix.diff", ""), "diff");
+    assert_eq!(language_of("rows.tsv", ""), "tsv");
+    assert_eq!(language_of("setup.PS1", ""), "powershell");
+    assert_eq!(language_of("Gemfile", ""), "ruby");
+    assert_eq!(language_of("GNUmakefile", ""), "makefile");
+    assert_eq!(language_of("Dockerfile.prod", ""), "dockerfile");
+    assert_eq!(language_of("CMakeLists.txt", ""), "cmake");
+    assert_eq!(language_of(".env.local", ""), "dotenv");
+    assert_eq!(language_of("notes", ""), "plaintext");
+}
+
+#[test]
+fn detect_language_reads_shebangs_for_extensionless_scripts() {
+    assert_eq!(
+        language_of("deploy", "#!/usr/bin/env python3\nprint()\n"),
+        "python"
+    );
+    assert_eq!(language_of("build", "#!/bin/bash -e\n"), "shellscript");
+    assert_eq!(
+        language_of("serve", "#!/usr/bin/env -S deno run\n"),
+        "typescript"
+    );
+    assert_eq!(language_of("report", "#!/usr/local/bin/ruby3.2\n"), "ruby");
+    // The extension wins over the interpreter.
+    assert_eq!(
+        language_of("tool.js", "#!/usr/bin/env bash\n"),
+        "javascript"
+    );
+    assert_eq!(
+        language_of("notes", "# !/usr/bin/env python\n"),
+        "plaintext"
+    );
+}
+
+#[test]
+fn detect_language_honors_vim_and_emacs_modelines() {
+    assert_eq!(
+        language_of("config.txt", "key = 1\n# vim: set ts=2 ft=toml :\n"),
+        "toml"
+    );
+    assert_eq!(language_of("script", "# vi: filetype=sh\n"), "shellscript");
+    assert_eq!(
+        language_of("hook", "#!/bin/sh\n# -*- mode: python; coding: utf-8 -*-\n"),
+        "python"
+    );
+    assert_eq!(language_of("init", ";; -*- emacs-lisp -*-\n"), "elisp");
+    assert_eq!(
+        language_of("data.json", "// -*- coding: utf-8 -*-\n"),
+        "json"
+    );
+
+    let long_file = format!("vim: ft=lua\n{}", "x\n".repeat(20));
+    assert_eq!(language_of("long", &long_file), "lua");
+    let buried = format!("{}vim: ft=lua\n{}", "x\n".repeat(10), "x\n".repeat(10));
+    assert_eq!(language_of("buried", &buried), "plaintext");
+}
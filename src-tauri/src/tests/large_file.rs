@@ -0,0 +1,72 @@
+use super::common::TempTestDir;
+use crate::files::read_text_payload;
+use crate::large_file::{
+    search_file_lines, text_range, LineIndexCache, LARGE_FILE_THRESHOLD_BYTES,
+};
+use crate::project_search::SearchOptions;
+
+fn numbered_lines(count: usize) -> String {
+    (1..=count).map(|n| format!("line {n}\n")).collect()
+}
+
+#[test]
+fn read_text_range_serves_lines_across_index_checkpoints() {
+    let temp = TempTestDir::new();
+    let file = temp.write_text("app.log", &numbered_lines(3000));
+    let cache = LineIndexCache::new();
+
+    let range = text_range(&cache, &file, 1020, 10).expect("range read should succeed");
+    assert_eq!(range.total_lines, 3000);
+    assert_eq!(range.lines.first().map(String::as_str), Some("line 1020"));
+    assert_eq!(range.lines.last().map(String::as_str), Some("line 1029"));
+    assert!(range.has_more);
+
+    let tail = text_range(&cache, &file, 2998, 50).expect("range read should succeed");
+    assert_eq!(tail.lines, vec!["line 2998", "line 2999", "line 3000"]);
+    assert!(!tail.has_more);
+
+    let past_end = text_range(&cache, &file, 5000, 10).expect("range read should succeed");
+    assert!(past_end.lines.is_empty());
+}
+
+#[test]
+fn search_text_file_reports_line_numbers_without_loading_content() {
+    let temp = TempTestDir::new();
+    let file = temp.write_text(
+        "events.jsonl",
+        "{\"level\":\"info\"}\r\n{\"level\":\"error\",\"id\":7}\r\n{\"level\":\"ERROR\"}\r\n",
+    );
+
+    let matches = search_file_lines(
+        &LineIndexCache::new(),
+        &file,
+        &SearchOptions {
+            query: "error".to_string(),
+            ..SearchOptions::default()
+        },
+    )
+    .expect("search should succeed");
+    let lines: Vec<usize> = matches.iter().map(|m| m.line_number).collect();
+    assert_eq!(lines, vec![2, 3]);
+    assert_eq!(matches[0].line_text, "{\"level\":\"error\",\"id\":7}");
+}
+
+#[test]
+fn read_text_file_opens_files_above_threshold_read_only_with_line_index() {
+    let temp = TempTestDir::new();
+    let line = format!("{}\n", "x".repeat(1023));
+    let line_count = (LARGE_FILE_THRESHOLD_BYTES as usize / line.len()) + 10;
+    let file = temp.write_text("huge.log", &line.repeat(line_count));
+
+    let payload = read_text_payload(
+        &LineIndexCache::new(),
+        file.to_string_lossy().to_string(),
+        None,
+    )
+    .expect("read text file should succeed");
+    let info = payload.large_file.expect("large file info should be set");
+    assert!(!payload.writable);
+    assert_eq!(info.line_count, line_count);
+    assert_eq!(info.line_index[1], 1024 * line.len() as u64);
+    assert_eq!(payload.content.lines().count(), 1000);
+}
@@ -0,0 +1,120 @@
+use super::common::TempTestDir;
+use crate::local_history::{HistorySource, LocalHistory};
+
+#[test]
+fn record_deduplicates_consecutive_identical_content() {
+    let temp = TempTestDir::new();
+    let history = LocalHistory::new(temp.path().join("history"));
+    let file = temp.write_text("notes.md", "one");
+
+    let first = history
+        .record(&file, b"one", HistorySource::Save)
+        .expect("record should succeed");
+    assert!(first.is_some());
+    let repeat = history
+        .record(&file, b"one", HistorySource::External)
+        .expect("record should succeed");
+    assert_eq!(repeat, None);
+    history
+        .record(&file, b"two", HistorySource::External)
+        .expect("record should succeed");
+    history
+        .record(&file, b"one", HistorySource::Save)
+        .expect("record should succeed");
+
+    let entries = history.list(&file);
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[1].source, HistorySource::External);
+    assert!(entries[0].id > entries[1].id, "list is newest first");
+    assert_eq!(entries[0].hash, entries[2].hash);
+
+    let objects = std::fs::read_dir(temp.path().join("history/objects"))
+        .unwrap()
+        .count();
+    assert_eq!(objects, 2, "identical content is stored once");
+    assert_eq!(history.read(&file, entries[1].id).unwrap(), b"two");
+}
+
+#[test]
+fn record_keeps_at_most_fifty_snapshots_and_drops_unused_objects() {
+    let temp = TempTestDir::new();
+    let history = LocalHistory::new(temp.path().join("history"));
+    let file = temp.write_text("log.txt", "");
+
+    for i in 0..55 {
+        history
+            .record(
+                &file,
+                format!("version {i}").as_bytes(),
+                HistorySource::Save,
+            )
+            .expect("record should succeed");
+    }
+
+    let entries = history.list(&file);
+    assert_eq!(entries.len(), 50);
+    assert_eq!(history.read(&file, entries[49].id).unwrap(), b"version 5");
+    let count_objects = || {
+        std::fs::read_dir(temp.path().join("history/objects"))
+            .unwrap()
+            .count()
+    };
+    // A handful of prunes leaves collection for later, off the save path.
+    assert_eq!(count_objects(), 55);
+
+    history.collect_garbage();
+    assert_eq!(count_objects(), 50);
+}
+
+#[test]
+fn diff_compares_snapshots_and_the_file_on_disk() {
+    let temp = TempTestDir::new();
+    let history = LocalHistory::new(temp.path().join("history"));
+    let file = temp.write_text("a.txt", "one\ntwo\n");
+    history.record_file(&file, HistorySource::Save);
+    std::fs::write(&file, "one\nthree\n").unwrap();
+    history.record_file(&file, HistorySource::External);
+
+    let entries = history.list(&file);
+    let (newest, oldest) = (entries[0].id, entries[1].id);
+    let diff = history
+        .diff(&file, oldest, Some(newest))
+        .expect("diff should succeed")
+        .expect("versions differ");
+    assert_eq!(diff.rel_path, "a.txt");
+    let changed: Vec<(&str, &str)> = diff.hunks[0]
+        .lines
+        .iter()
+        .map(|line| (line.line_type.as_str(), line.content.as_str()))
+        .collect();
+    assert_eq!(
+        changed,
+        vec![("context", "one"), ("removed", "two"), ("added", "three")]
+    );
+
+    assert_eq!(history.diff(&file, newest, None).unwrap(), None);
+}
+
+#[test]
+fn restore_writes_the_snapshot_and_keeps_the_replaced_content() {
+    let temp = TempTestDir::new();
+    let history = LocalHistory::new(temp.path().join("history"));
+    let file = temp.write_text("draft.md", "good");
+    history.record_file(&file, HistorySource::Save);
+    let good = history.list(&file)[0].id;
+    std::fs::write(&file, "overwritten by an agent").unwrap();
+
+    history
+        .restore(&file, good)
+        .expect("restore should succeed");
+
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "good");
+    let entries = history.list(&file);
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[1].source, HistorySource::BeforeRestore);
+    assert_eq!(
+        history.read(&file, entries[1].id).unwrap(),
+        b"overwritten by an agent"
+    );
+    assert!(history.restore(&file, 1).is_err());
+}
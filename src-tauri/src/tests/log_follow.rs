@@ -0,0 +1,69 @@
+use super::common::TempTestDir;
+use super::*;
+use crate::log_follow::FollowState;
+use std::io::Write;
+
+fn append(path: &Path, bytes: impl AsRef<[u8]>) {
+    let mut file = fs::OpenOptions::new().append(true).open(path).unwrap();
+    file.write_all(bytes.as_ref()).unwrap();
+}
+
+#[test]
+fn follow_state_reports_only_appended_text_and_detects_truncation() {
+    let temp = TempTestDir::new();
+    let log = temp.write_text("agent.log", "old line\n");
+    let mut follow = FollowState::new(&log, None, true).expect("follow should start");
+    assert!(follow.poll().is_none());
+
+    append(&log, "first\nsecond\n");
+    let update = follow.poll().expect("appended text should be reported");
+    assert_eq!(update.text, "first\nsecond\n");
+    assert!(!update.truncated && !update.rotated);
+    assert_eq!(update.offset, 22);
+
+    // A multi-byte character split across writes is held back until whole.
+    append(&log, b"caf\xC3");
+    assert_eq!(follow.poll().unwrap().text, "caf");
+    append(&log, b"\xA9\n");
+    assert_eq!(follow.poll().unwrap().text, "\u{e9}\n");
+
+    fs::write(&log, "restarted\n").unwrap();
+    let update = follow.poll().expect("truncation should be reported");
+    assert!(update.truncated);
+    assert_eq!(update.text, "restarted\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn follow_state_restarts_from_new_file_after_rotation() {
+    let temp = TempTestDir::new();
+    let log = temp.write_text("server.log", "before rotation\n");
+    let mut follow = FollowState::new(&log, None, true).expect("follow should start");
+
+    fs::rename(&log, temp.path().join("server.log.1")).unwrap();
+    assert!(follow.poll().is_none());
+
+    fs::write(&log, "fresh log with more content than before\n").unwrap();
+    let update = follow.poll().expect("rotation should be reported");
+    assert!(update.rotated);
+    assert_eq!(update.text, "fresh log with more content than before\n");
+}
+
+#[test]
+fn follow_state_filters_complete_lines_by_regex() {
+    let temp = TempTestDir::new();
+    let log = temp.write_text("build.log", "");
+    let mut follow =
+        FollowState::new(&log, Some(r"^(WARN|ERROR)"), false).expect("follow should start");
+
+    append(&log, "INFO start\nERROR disk full\nWARN slow");
+    assert_eq!(follow.poll().unwrap().text, "ERROR disk full\n");
+
+    append(&log, " request\nINFO done\n");
+    assert_eq!(follow.poll().unwrap().text, "WARN slow request\n");
+
+    append(&log, "INFO quiet\n");
+    assert!(follow.poll().is_none());
+
+    assert!(FollowState::new(&log, Some("("), false).is_err());
+}
@@ -0,0 +1,111 @@
+use super::common::TempTestDir;
+use crate::markdown_links::check_links;
+
+#[test]
+fn local_links_anchors_and_images_are_resolved() {
+    let dir = TempTestDir::new();
+    dir.write_text("docs/guide.md", "# Guide\n\n## Set up\n");
+    dir.write_text("docs/img/logo png.png", "png");
+    dir.write_text("src/main.rs", "fn main() {}\n");
+    let plan = dir.write_text(
+        "docs/plan.md",
+        "\
+# Plan
+
+See [the guide](guide.md#set-up), [setup](./guide.md#install) and
+[code](../src/main.rs#L1) or [missing](notes.md).
+Jump to [plan](#plan), [nowhere](#nowhere) and [self](plan.md#Plan).
+![logo](img/logo%20png.png) ![gone](img/gone.png) ![dir](img)
+Read [docs](https://example.com/docs), <mailto:a@b.c> and [ref][site].
+Broken [ref][nope] but [maybe] is prose.
+
+`[not](a-link.md)`
+
+[site]: https://example.com
+",
+    );
+    let content = std::fs::read_to_string(&plan).unwrap();
+    let report = check_links(&content, &plan);
+
+    let summary: Vec<_> = report
+        .links
+        .iter()
+        .map(|link| {
+            (
+                link.line,
+                link.kind,
+                link.target.as_str(),
+                link.status,
+                link.message.as_deref(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (3, "link", "guide.md#set-up", "ok", None),
+            (
+                3,
+                "link",
+                "./guide.md#install",
+                "broken",
+                Some("No heading #install in guide.md")
+            ),
+            (4, "link", "../src/main.rs#L1", "ok", None),
+            (
+                4,
+                "link",
+                "notes.md",
+                "broken",
+                Some("notes.md does not exist")
+            ),
+            (5, "link", "#plan", "ok", None),
+            (
+                5,
+                "link",
+                "#nowhere",
+                "broken",
+                Some("No heading #nowhere in this file")
+            ),
+            (5, "link", "plan.md#Plan", "ok", None),
+            (6, "image", "img/logo%20png.png", "ok", None),
+            (
+                6,
+                "image",
+                "img/gone.png",
+                "broken",
+                Some("gone.png does not exist")
+            ),
+            (6, "image", "img", "broken", Some("img is not a file")),
+            (7, "link", "https://example.com/docs", "external", None),
+            (7, "link", "mailto:a@b.c", "external", None),
+            (7, "link", "https://example.com", "external", None),
+            (
+                8,
+                "link",
+                "nope",
+                "broken",
+                Some("Undefined link reference [nope]")
+            ),
+        ]
+    );
+    assert_eq!((report.broken, report.external), (6, 3));
+    assert_eq!(
+        report.links[2].path.as_deref(),
+        Some(dir.path().join("src/main.rs").to_string_lossy().as_ref())
+    );
+    assert_eq!(report.links[1].column, 35);
+}
+
+#[test]
+fn email_autolinks_are_external() {
+    let dir = TempTestDir::new();
+    let readme = dir.write_text("README.md", "Mail <user@example.com> for help.\n");
+    let content = std::fs::read_to_string(&readme).unwrap();
+    let report = check_links(&content, &readme);
+
+    assert_eq!(report.links.len(), 1);
+    assert_eq!(report.links[0].target, "user@example.com");
+    assert_eq!(report.links[0].status, "external");
+    assert_eq!((report.broken, report.external), (0, 1));
+}
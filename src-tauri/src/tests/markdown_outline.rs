@@ -0,0 +1,94 @@
+use crate::markdown_outline::{heading_slug, parse_outline, reparse_outline, TaskCounts};
+
+const PLAN: &str = "\
+---
+title: Plan
+---
+# Migration plan
+
+Intro with [a link](https://example.com).
+
+## Phase 1: `schema` changes
+- [x] add columns
+- [ ] backfill
+
+### Rollback
+- [ ] script
+
+```md
+# not a heading
+- [ ] not a task
+```
+
+Phase 2
+-------
+> ## Quoted
+- [X] ship
+
+## Phase 1: `schema` changes
+";
+
+#[test]
+fn headings_carry_lines_slugs_and_section_task_counts() {
+    let outline = parse_outline(PLAN).outline(Some("v1".to_string()));
+    assert_eq!(outline.version.as_deref(), Some("v1"));
+    let summary: Vec<_> = outline
+        .headings
+        .iter()
+        .map(|h| (h.level, h.text.as_str(), h.line, h.slug.as_str(), h.tasks))
+        .collect();
+    let tasks = |total, done| TaskCounts { total, done };
+    assert_eq!(
+        summary,
+        vec![
+            (1, "Migration plan", 4, "migration-plan", tasks(4, 2)),
+            (
+                2,
+                "Phase 1: schema changes",
+                8,
+                "phase-1-schema-changes",
+                tasks(3, 1)
+            ),
+            (3, "Rollback", 12, "rollback", tasks(1, 0)),
+            (2, "Phase 2", 20, "phase-2", tasks(0, 0)),
+            (2, "Quoted", 22, "quoted", tasks(1, 1)),
+            (
+                2,
+                "Phase 1: schema changes",
+                25,
+                "phase-1-schema-changes-1",
+                tasks(0, 0)
+            ),
+        ]
+    );
+    assert_eq!(outline.tasks, tasks(4, 2));
+
+    assert_eq!(heading_slug("Überblick & Ziele"), "überblick--ziele");
+    assert_eq!(heading_slug("設計 (v2)"), "設計-v2");
+    let empty = parse_outline("# !!!\n# ???\n").outline(None);
+    assert_eq!(empty.headings[0].slug, "section");
+    assert_eq!(empty.headings[1].slug, "section-1");
+}
+
+#[test]
+fn reparsing_after_edits_matches_a_full_parse() {
+    let edits: Vec<String> = vec![
+        PLAN.replace("- [ ] script", "- [x] script\n- [ ] verify"),
+        PLAN.replace("### Rollback", "### Roll back\n#### Steps"),
+        PLAN.replace("```md\n# not a heading", "# now a heading"),
+        PLAN.replace("Phase 2\n-------", "Phase 2"),
+        PLAN.replace("## Phase 1: `schema` changes\n- [x]", "text\n- [x]"),
+        PLAN.replace("# Migration plan", "#Migration plan"),
+        format!("{PLAN}\n[a]: https://example.com\n# [a]\n"),
+        PLAN.replacen("---\ntitle: Plan\n---\n", "", 1),
+        PLAN.to_string(),
+    ];
+
+    let mut previous = PLAN.to_string();
+    let mut parsed = parse_outline(&previous);
+    for edit in edits {
+        parsed = reparse_outline(&parsed, &previous, &edit);
+        assert_eq!(parsed, parse_outline(&edit), "after editing to:\n{edit}");
+        previous = edit;
+    }
+}
@@ -0,0 +1,111 @@
+use crate::files::format_structured_text;
+use crate::markdown_table::{
+    csv_to_markdown, display_width, format_markdown_tables, markdown_table_to_csv, markdown_to_csv,
+};
+use std::path::Path;
+
+#[test]
+fn pipe_tables_are_aligned_with_their_alignment_colons() {
+    let content = "\
+# Report
+
+Name|Score|Notes|Done
+:--|--:|:-:|---
+Ada|7|first run|yes
+Grace|12.5|a \\| b
+Linus|3|x|no|extra
+
+After the table.
+";
+    assert_eq!(
+        format_markdown_tables(content).unwrap(),
+        "\
+# Report
+
+| Name  | Score |   Notes   | Done |       |
+| :---- | ----: | :-------: | ---- | ----- |
+| Ada   |     7 | first run | yes  |       |
+| Grace |  12.5 |  a \\| b   |      |       |
+| Linus |     3 |     x     | no   | extra |
+
+After the table.
+"
+    );
+
+    let result = format_structured_text(content.into(), Some("markdown".into()), None).unwrap();
+    assert_eq!(result.detected_kind.as_deref(), Some("markdown"));
+    assert!(result.changed);
+}
+
+#[test]
+fn wide_characters_count_as_two_columns() {
+    assert_eq!(display_width("表格"), 4);
+    assert_eq!(display_width("e\u{301}"), 1);
+    let content = "| 名前 | n |\n|---|---|\n| 山田太郎 | 1 |\n| Bob | 22 |\r\n";
+    assert_eq!(
+        format_markdown_tables(content).unwrap(),
+        "| 名前     | n   |\n| -------- | --- |\n| 山田太郎 | 1   |\n| Bob      | 22  |\r\n"
+    );
+}
+
+#[test]
+fn code_blocks_and_quoted_tables_are_handled() {
+    let content = "\
+```md
+a|b
+-|-
+```
+> a|b
+> -|-
+> 1|2
+not | a table
+";
+    assert_eq!(
+        format_markdown_tables(content).unwrap(),
+        "\
+```md
+a|b
+-|-
+```
+> | a   | b   |
+> | --- | --- |
+> | 1   | 2   |
+not | a table
+"
+    );
+    let plain = "No tables here.\n\n---\n";
+    assert_eq!(format_markdown_tables(plain).unwrap(), plain);
+}
+
+#[test]
+fn csv_and_markdown_tables_convert_both_ways() {
+    let csv = "item,qty,note\nwidget,3,a|b\ngizmo,10,\"two\nlines\"\n";
+    let markdown = csv_to_markdown(csv, Path::new("stock.csv")).unwrap();
+    assert_eq!(
+        markdown,
+        "\
+| item   | qty | note         |
+| ------ | --: | ------------ |
+| widget |   3 | a\\|b         |
+| gizmo  |  10 | two<br>lines |
+"
+    );
+    assert_eq!(markdown_to_csv(&markdown, None, b',').unwrap(), csv);
+
+    let tsv = "1\t2\n3\t4\n";
+    assert!(csv_to_markdown(tsv, Path::new("n.tsv"))
+        .unwrap()
+        .starts_with("| Column 1 | Column 2 |\n| -------: | -------: |"));
+
+    let document = "| a | b |\n|---|---|\n| 1 | 2 |\n\ntext\n\n| x |\n|---|\n| y; z |\n";
+    assert_eq!(
+        markdown_table_to_csv(document.into(), Some(7), Some(";".into())).unwrap(),
+        "x\n\"y; z\"\n"
+    );
+    assert_eq!(
+        markdown_table_to_csv(document.into(), Some(5), None).unwrap(),
+        "a,b\n1,2\n"
+    );
+    assert!(markdown_table_to_csv("no table".into(), None, None).is_err());
+    assert!(markdown_table_to_csv(document.into(), None, Some("::".into())).is_err());
+}
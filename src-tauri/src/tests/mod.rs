@@ -2,16 +2,36 @@
 use super::macos::cli_install::{
     ensure_cli_source_path_is_stable, path_contains_dir, preferred_cli_install_dir,
 };
-use super::path_utils::{is_code, is_markdown};
 use super::*;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+mod binary_files;
 #[cfg(target_os = "macos")]
 mod cli_install;
 mod common;
+mod file_index;
+mod file_journal;
+mod file_ops;
 mod files;
 mod git_diff;
 mod git_status;
+mod json_format;
+mod json_schema;
+mod languages;
+mod large_file;
 mod launch;
+mod local_history;
+mod log_follow;
+mod markdown_links;
+mod markdown_outline;
+mod markdown_table;
+mod path_diff;
+mod project_ignore;
+mod project_search;
+mod project_tree;
 mod recent_files;
+mod semantic_diff;
+mod table_view;
+mod task_list;
 mod utils;
+mod validation;
@@ -0,0 +1,103 @@
+use super::common::TempTestDir;
+use crate::path_diff::{diff_path_pair, format_unified_diff, text_diff_hunks};
+
+#[test]
+fn text_diff_hunks_match_git_hunk_shape() {
+    let hunks = text_diff_hunks("a\nb\nc\n", "a\nB\nc\nd\n");
+
+    assert_eq!(hunks.len(), 1);
+    assert_eq!(hunks[0].header, "@@ -1,3 +1,4 @@");
+    let lines: Vec<(&str, &str)> = hunks[0]
+        .lines
+        .iter()
+        .map(|line| (line.line_type.as_str(), line.content.as_str()))
+        .collect();
+    assert_eq!(
+        lines,
+        vec![
+            ("context", "a"),
+            ("removed", "b"),
+            ("added", "B"),
+            ("context", "c"),
+            ("added", "d"),
+        ]
+    );
+    assert!(text_diff_hunks("same\n", "same\n").is_empty());
+}
+
+#[test]
+fn diff_path_pair_compares_two_files() {
+    let temp = TempTestDir::new();
+    let old = temp.write_text("plan-v1.md", "# Plan\n\nStep one\n");
+    let new = temp.write_text("plan-v2.md", "# Plan\n\nStep two\n");
+
+    let diffs = diff_path_pair(&old, &new).expect("diff should succeed");
+    assert_eq!(diffs.len(), 1);
+    assert_eq!(diffs[0].rel_path, "plan-v2.md");
+    assert_eq!(diffs[0].status, "changed");
+
+    let same = temp.write_text("copy.md", "# Plan\n\nStep two\n");
+    assert!(diff_path_pair(&new, &same).unwrap().is_empty());
+    assert!(diff_path_pair(&new, temp.path()).is_err());
+}
+
+#[test]
+fn diff_path_pair_lists_added_removed_and_changed_files_in_folders() {
+    let temp = TempTestDir::new();
+    temp.write_text("a/same.txt", "same\n");
+    temp.write_text("a/changed.txt", "old\n");
+    temp.write_text("a/removed.txt", "gone\n");
+    temp.write_bytes("a/image.png", b"\x89PNG\r\n\x1a\n\x00\x01");
+    temp.write_text("b/same.txt", "same\n");
+    temp.write_text("b/changed.txt", "new\n");
+    temp.write_text("b/nested/added.txt", "hello\n");
+    temp.write_bytes("b/image.png", b"\x89PNG\r\n\x1a\n\x00\x02");
+    temp.write_text("b/.git/HEAD", "ref: refs/heads/main\n");
+
+    let diffs = diff_path_pair(&temp.path().join("a"), &temp.path().join("b"))
+        .expect("diff should succeed");
+    let summary: Vec<(&str, &str, usize)> = diffs
+        .iter()
+        .map(|d| (d.rel_path.as_str(), d.status.as_str(), d.hunks.len()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("changed.txt", "changed", 1),
+            ("image.png", "changed", 0),
+            ("nested/added.txt", "added", 1),
+            ("removed.txt", "removed", 1),
+        ]
+    );
+
+    let text = format_unified_diff(&diffs);
+    assert!(text.contains("--- a/changed.txt\n+++ b/changed.txt\n@@ -1 +1 @@\n-old\n+new\n"));
+    assert!(text.contains("Binary files a/image.png and b/image.png differ\n"));
+    assert!(text.contains("--- /dev/null\n+++ b/nested/added.txt\n"));
+    assert!(text.contains("--- a/removed.txt\n+++ /dev/null\n"));
+}
+
+#[test]
+fn format_unified_diff_keeps_headers_for_text_changes_without_hunks() {
+    let temp = TempTestDir::new();
+    temp.write_bytes("a/bom.txt", b"\xEF\xBB\xBFhello\n");
+    temp.write_text("b/bom.txt", "hello\n");
+    temp.write_text("b/empty.txt", "");
+
+    let diffs = diff_path_pair(&temp.path().join("a"), &temp.path().join("b"))
+        .expect("diff should succeed");
+    let summary: Vec<(&str, &str, bool)> = diffs
+        .iter()
+        .map(|d| (d.rel_path.as_str(), d.status.as_str(), d.binary))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![("bom.txt", "changed", false), ("empty.txt", "added", false)]
+    );
+
+    let text = format_unified_diff(&diffs);
+    assert_eq!(
+        text,
+        "--- a/bom.txt\n+++ b/bom.txt\n--- /dev/null\n+++ b/empty.txt\n"
+    );
+}
@@ -0,0 +1,38 @@
+use super::common::TempTestDir;
+use super::*;
+use crate::project_ignore::ProjectIgnore;
+
+#[test]
+fn project_ignore_matches_nested_rules_and_ignored_parents() {
+    let temp = TempTestDir::new();
+    let root = temp.path();
+
+    temp.write_text(".gitignore", "*.log\nbuild/\n");
+    temp.write_text("src/.gitignore", "generated.rs\n");
+    temp.write_text(".teexignore", "!keep.log\n");
+
+    let ignore = ProjectIgnore::new(root);
+
+    assert!(ignore.is_ignored(&root.join("debug.log"), false));
+    assert!(!ignore.is_ignored(&root.join("keep.log"), false));
+    assert!(ignore.is_ignored(&root.join("build"), true));
+    assert!(ignore.is_ignored(&root.join("build/out/app.js"), false));
+    assert!(ignore.is_ignored(&root.join("src/generated.rs"), false));
+    assert!(!ignore.is_ignored(&root.join("src/main.rs"), false));
+    assert!(!ignore.is_ignored(&root.join("generated.rs"), false));
+    assert!(ignore.is_ignored(&root.join(".git/index"), false));
+}
+
+#[test]
+fn project_ignore_picks_up_rule_changes_after_invalidate() {
+    let temp = TempTestDir::new();
+    let root = temp.path();
+
+    let gitignore = temp.write_text(".gitignore", "*.tmp\n");
+    let ignore = ProjectIgnore::new(root);
+    assert!(!ignore.is_ignored(&root.join("notes.bak"), false));
+
+    fs::write(&gitignore, "*.tmp\n*.bak\n").expect("rewrite gitignore");
+    ignore.invalidate(&gitignore);
+    assert!(ignore.is_ignored(&root.join("notes.bak"), false));
+}
@@ -0,0 +1,201 @@
+use super::common::TempTestDir;
+use super::*;
+use crate::local_history::{HistorySource, LocalHistory};
+use crate::project_search::{
+    replace_in_project, search_project_files, FileSearchResult, SearchOptions,
+};
+
+fn search(temp: &TempTestDir, options: SearchOptions) -> Vec<FileSearchResult> {
+    let mut results = Vec::new();
+    search_project_files(temp.path(), &options, || false, |r| results.push(r))
+        .expect("search should succeed");
+    results.sort_by(|a, b| a.rel_path.cmp(&b.rel_path));
+    results
+}
+
+fn query(text: &str) -> SearchOptions {
+    SearchOptions {
+        query: text.to_string(),
+        ..SearchOptions::default()
+    }
+}
+
+#[test]
+fn search_project_files_respects_case_whole_word_and_gitignore() {
+    let temp = TempTestDir::new();
+    temp.write_text(".gitignore", "target/\n");
+    temp.write_text("src/main.rs", "let Value = 1;\nlet values = 2;\n");
+    temp.write_text("notes.md", "value é value\n");
+    temp.write_text("target/out.rs", "value\n");
+
+    let results = search(&temp, query("value"));
+    let paths: Vec<&str> = results.iter().map(|r| r.rel_path.as_str()).collect();
+    assert_eq!(paths, vec!["notes.md", "src/main.rs"]);
+    assert_eq!(results[0].matches[0].ranges, vec![(0, 5), (8, 13)]);
+    assert_eq!(results[1].matches.len(), 2);
+
+    let whole_word = search(
+        &temp,
+        SearchOptions {
+            whole_word: true,
+            case_sensitive: true,
+            ..query("Value")
+        },
+    );
+    assert_eq!(whole_word.len(), 1);
+    assert_eq!(whole_word[0].matches[0].line_number, 1);
+}
+
+#[test]
+fn search_project_files_applies_include_exclude_globs_and_regex() {
+    let temp = TempTestDir::new();
+    temp.write_text("src/lib.rs", "fn alpha() {}\nfn beta() {}\n");
+    temp.write_text("src/generated/api.rs", "fn gamma() {}\n");
+    temp.write_text("README.md", "fn delta() {}\n");
+
+    let results = search(
+        &temp,
+        SearchOptions {
+            is_regex: true,
+            include: vec!["*.rs".to_string()],
+            exclude: vec!["generated".to_string()],
+            ..query(r"fn \w+\(")
+        },
+    );
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].rel_path, "src/lib.rs");
+    assert_eq!(results[0].matches.len(), 2);
+
+    assert!(search_project_files(temp.path(), &query("("), || false, |_| {}).is_ok());
+    assert!(search_project_files(
+        temp.path(),
+        &SearchOptions {
+            is_regex: true,
+            ..query("(")
+        },
+        || false,
+        |_| {}
+    )
+    .is_err());
+}
+
+#[test]
+fn search_project_files_stops_when_cancelled() {
+    let temp = TempTestDir::new();
+    temp.write_text("a.txt", "needle\n");
+    temp.write_text("b.txt", "needle\n");
+
+    let mut count = 0;
+    search_project_files(temp.path(), &query("needle"), || true, |_| count += 1)
+        .expect("search should succeed");
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn replace_in_files_previews_then_applies_preserving_line_endings() {
+    let temp = TempTestDir::new();
+    let crlf = temp.write_text("a.txt", "foo = 1\r\nbar = foo\r\n");
+    let other = temp.write_text("b.txt", "nothing here\n");
+    let options = SearchOptions {
+        is_regex: true,
+        ..query(r"(\w+) = (\w+)")
+    };
+
+    let history_dir = TempTestDir::new();
+    let history = LocalHistory::new(history_dir.path().to_path_buf());
+    let preview = replace_in_project(
+        &history,
+        temp.path(),
+        &options,
+        "$2 = $1",
+        None,
+        None,
+        false,
+    )
+    .expect("preview should succeed");
+    assert!(!preview.applied);
+    assert_eq!(preview.total_replacements, 2);
+    assert_eq!(preview.files[0].lines[1].after, "foo = bar");
+    assert_eq!(
+        fs::read_to_string(&crlf).unwrap(),
+        "foo = 1\r\nbar = foo\r\n"
+    );
+
+    let versions: HashMap<String, String> = preview
+        .files
+        .iter()
+        .map(|file| (file.path.clone(), file.version.clone()))
+        .collect();
+    let applied = replace_in_project(
+        &history,
+        temp.path(),
+        &options,
+        "$2 = $1",
+        None,
+        Some(&versions),
+        true,
+    )
+    .expect("apply should succeed");
+    assert!(applied.applied);
+    assert!(applied.files.iter().all(|f| f.error.is_none()));
+    assert_eq!(
+        fs::read_to_string(&crlf).unwrap(),
+        "1 = foo\r\nfoo = bar\r\n"
+    );
+    assert_eq!(fs::read_to_string(&other).unwrap(), "nothing here\n");
+    let sources: Vec<HistorySource> = history.list(&crlf).iter().map(|e| e.source).collect();
+    assert_eq!(
+        sources,
+        vec![HistorySource::Save, HistorySource::BeforeSave]
+    );
+}
+
+#[test]
+fn replace_in_project_skips_files_changed_since_the_preview() {
+    let temp = TempTestDir::new();
+    let kept = temp.write_text("a.txt", "old\n");
+    let edited = temp.write_text("b.txt", "old\n");
+    let history_dir = TempTestDir::new();
+    let history = LocalHistory::new(history_dir.path().to_path_buf());
+    let options = query("old");
+
+    let preview = replace_in_project(&history, temp.path(), &options, "new", None, None, false)
+        .expect("preview should succeed");
+    let versions: HashMap<String, String> = preview
+        .files
+        .iter()
+        .map(|file| (file.path.clone(), file.version.clone()))
+        .collect();
+    fs::write(&edited, "old and edited\n").unwrap();
+    temp.write_text("c.txt", "old\n");
+
+    let applied = replace_in_project(
+        &history,
+        temp.path(),
+        &options,
+        "new",
+        None,
+        Some(&versions),
+        true,
+    )
+    .expect("apply should succeed");
+    let summary: Vec<(&str, Option<&str>)> = applied
+        .files
+        .iter()
+        .map(|file| (file.rel_path.as_str(), file.error.as_deref()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("a.txt", None),
+            ("b.txt", Some("File changed since the preview"))
+        ]
+    );
+    assert_eq!(applied.total_replacements, 1);
+    assert_eq!(fs::read_to_string(&kept).unwrap(), "new\n");
+    assert_eq!(fs::read_to_string(&edited).unwrap(), "old and edited\n");
+    assert_eq!(
+        fs::read_to_string(temp.path().join("c.txt")).unwrap(),
+        "old\n"
+    );
+}
@@ -0,0 +1,194 @@
+use super::common::TempTestDir;
+use super::*;
+
+#[test]
+fn list_directory_sorts_folders_first_and_reports_child_counts() {
+    let temp = TempTestDir::new();
+
+    temp.write_text("b.md", "# b");
+    temp.write_text("A.md", "# a");
+    temp.write_text("docs/one.md", "# one");
+    temp.write_text("docs/two.md", "# two");
+    temp.write_bytes("docs/image.png", b"\x89PNG\r\n\x1a\n\x00\x00");
+    temp.mkdir("empty");
+
+    let page = list_directory(
+        temp.path().to_string_lossy().to_string(),
+        None,
+        false,
+        false,
+        None,
+        None,
+    )
+    .expect("list directory should succeed");
+
+    let names: Vec<&str> = page.entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["docs", "empty", "A.md", "b.md"]);
+    assert_eq!(page.total, 4);
+    assert!(!page.has_more);
+    assert_eq!(page.entries[0].child_count, Some(3));
+    assert_eq!(page.entries[1].child_count, Some(0));
+    assert_eq!(page.entries[2].child_count, None);
+}
+
+#[test]
+fn list_directory_pages_results_and_lists_nested_folders() {
+    let temp = TempTestDir::new();
+    for i in 0..5 {
+        temp.write_text(&format!("notes/{i}.md"), "# note");
+    }
+
+    let notes = temp.path().join("notes");
+    let first = list_directory(
+        temp.path().to_string_lossy().to_string(),
+        Some(notes.to_string_lossy().to_string()),
+        false,
+        false,
+        Some(0),
+        Some(2),
+    )
+    .expect("list directory should succeed");
+    assert_eq!(first.total, 5);
+    assert!(first.has_more);
+    assert_eq!(first.entries.len(), 2);
+
+    let last = list_directory(
+        temp.path().to_string_lossy().to_string(),
+        Some(notes.to_string_lossy().to_string()),
+        false,
+        false,
+        Some(4),
+        Some(2),
+    )
+    .expect("list directory should succeed");
+    assert!(!last.has_more);
+    assert_eq!(last.offset, 4);
+    assert_eq!(last.entries.len(), 1);
+    assert_eq!(
+        last.entries[0].rel_path,
+        Path::new("notes").join("4.md").to_string_lossy()
+    );
+}
+
+#[test]
+fn list_directory_applies_ignore_rules_from_project_root() {
+    let temp = TempTestDir::new();
+
+    temp.write_text(".gitignore", "*.log\n");
+    temp.write_text("logs/keep.md", "# keep");
+    temp.write_text("logs/app.log", "noise");
+
+    let logs = temp.path().join("logs");
+    let page = list_directory(
+        temp.path().to_string_lossy().to_string(),
+        Some(logs.to_string_lossy().to_string()),
+        false,
+        false,
+        None,
+        None,
+    )
+    .expect("list directory should succeed");
+    let names: Vec<&str> = page.entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["keep.md"]);
+
+    let with_ignored = list_directory(
+        temp.path().to_string_lossy().to_string(),
+        Some(logs.to_string_lossy().to_string()),
+        false,
+        true,
+        None,
+        None,
+    )
+    .expect("list directory should succeed");
+    assert_eq!(with_ignored.total, 2);
+}
+
+#[test]
+fn list_directory_rejects_folders_outside_root() {
+    let project = TempTestDir::new();
+    let other = TempTestDir::new();
+
+    let error = list_directory(
+        project.path().to_string_lossy().to_string(),
+        Some(other.path().to_string_lossy().to_string()),
+        false,
+        false,
+        None,
+        None,
+    )
+    .unwrap_err();
+    assert!(error.contains("outside the project root"));
+
+    // Lexically inside the root, but `..` walks out of it.
+    project.mkdir("sub");
+    let escape = project.path().join("sub").join("..").join("..");
+    let error = list_directory(
+        project.path().to_string_lossy().to_string(),
+        Some(escape.to_string_lossy().to_string()),
+        false,
+        false,
+        None,
+        None,
+    )
+    .unwrap_err();
+    assert!(error.contains("outside the project root"));
+
+    #[cfg(unix)]
+    {
+        let link = project.path().join("link");
+        std::os::unix::fs::symlink(other.path(), &link).expect("create symlink");
+        let error = list_directory(
+            project.path().to_string_lossy().to_string(),
+            Some(link.to_string_lossy().to_string()),
+            false,
+            false,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert!(error.contains("outside the project root"));
+    }
+}
+
+#[test]
+fn list_project_entries_reports_only_folders_without_visible_files() {
+    let temp = TempTestDir::new();
+
+    temp.write_text("a/b/c/deep.md", "# deep");
+    temp.mkdir("a/empty_sibling");
+    temp.mkdir("x/y");
+    temp.write_text("z/.DS_Store", "finder metadata");
+
+    let entries = list_project_entries(temp.path().to_string_lossy().to_string(), false, false)
+        .expect("list project entries should succeed");
+    let dirs: Vec<String> = entries
+        .iter()
+        .filter(|e| e.is_dir)
+        .map(|e| e.rel_path.replace('\\', "/"))
+        .collect();
+
+    assert_eq!(dirs, vec!["a/empty_sibling", "x", "x/y", "z"]);
+}
+
+#[test]
+fn only_list_directory_sniffs_files_with_unknown_extensions() {
+    let temp = TempTestDir::new();
+    temp.write_bytes("blob.dat", b"\x01\x02\x00\x03\xFF");
+    temp.write_bytes("photo.JPG", b"not really a jpeg");
+    let root = temp.path().to_string_lossy().to_string();
+
+    let mut walked = list_project_entries(root.clone(), false, false)
+        .expect("list project entries should succeed");
+    walked.sort_by(|a, b| a.rel_path.cmp(&b.rel_path));
+    let badges: Vec<Option<&str>> = walked.iter().map(|e| e.binary_type.as_deref()).collect();
+    assert_eq!(badges, vec![None, Some("jpeg")]);
+
+    let page = list_directory(root, None, false, false, None, None)
+        .expect("list directory should succeed");
+    let badges: Vec<Option<&str>> = page
+        .entries
+        .iter()
+        .map(|e| e.binary_type.as_deref())
+        .collect();
+    assert_eq!(badges, vec![Some("binary"), None]);
+}
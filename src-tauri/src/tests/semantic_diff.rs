@@ -0,0 +1,94 @@
+use super::common::TempTestDir;
+use crate::semantic_diff::{
+    format_semantic_changes, semantic_diff_files, semantic_diff_texts, SemanticChange,
+};
+use serde_json::json;
+
+fn summary(changes: &[SemanticChange]) -> Vec<(&str, &str)> {
+    changes
+        .iter()
+        .map(|change| (change.change, change.path.as_str()))
+        .collect()
+}
+
+#[test]
+fn yaml_changes_are_reported_by_path_ignoring_order_and_formatting() {
+    let old = "spec:\n  replicas: 2\n  containers:\n    - name: web\n      image: web:1\n";
+    let new =
+        "spec:\n  containers:\n  - image: web:2\n    name: web\n  replicas: 2\n  paused: false\n";
+
+    let changes = semantic_diff_texts(Some(old), Some(new), "yaml").unwrap();
+    assert_eq!(
+        summary(&changes),
+        vec![
+            ("changed", "spec.containers[0].image"),
+            ("added", "spec.paused"),
+        ]
+    );
+    assert_eq!(changes[0].old_value, Some(json!("web:1")));
+    assert_eq!(changes[0].new_value, Some(json!("web:2")));
+
+    let reformatted = "spec: {replicas: 2, containers: [{name: web, image: 'web:1'}]}";
+    assert!(semantic_diff_texts(Some(old), Some(reformatted), "yaml")
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn yaml_streams_are_compared_document_by_document() {
+    let old = "kind: Service\nport: 80\n---\nkind: Deployment\nreplicas: 1\n";
+    let new = "kind: Service\nport: 80\n---\nkind: Deployment\nreplicas: 3\n";
+    let changes = semantic_diff_texts(Some(old), Some(new), "yaml").unwrap();
+    assert_eq!(summary(&changes), vec![("changed", "[1].replicas")]);
+
+    let single = "kind: Service\nport: 80\n";
+    let changes = semantic_diff_texts(Some(single), Some(new), "yaml").unwrap();
+    assert_eq!(summary(&changes), vec![("added", "[1]")]);
+}
+
+#[test]
+fn json_and_toml_arrays_and_odd_keys() {
+    let changes = semantic_diff_texts(
+        Some(r#"{"a.b": 1, "list": [1, 2, 3]}"#),
+        Some(r#"{"list": [1, 5], "a.b": 1, "new key": null}"#),
+        "json",
+    )
+    .unwrap();
+    assert_eq!(
+        summary(&changes),
+        vec![
+            ("changed", "list[1]"),
+            ("removed", "list[2]"),
+            ("added", r#"["new key"]"#),
+        ]
+    );
+
+    let changes = semantic_diff_texts(
+        Some("[server]\nport = 80\nstarted = 1979-05-27T07:32:00Z\n"),
+        Some("[server]\nstarted = 1979-05-27T07:32:00Z\nport = 8080\n"),
+        "toml",
+    )
+    .unwrap();
+    assert_eq!(summary(&changes), vec![("changed", "server.port")]);
+    assert_eq!(
+        format_semantic_changes(&changes),
+        "~ server.port: 80 -> 8080\n"
+    );
+}
+
+#[test]
+fn semantic_diff_files_handles_missing_sides_and_rejects_other_formats() {
+    let temp = TempTestDir::new();
+    let config = temp.write_text("config.json", "{\"debug\": true}");
+    let notes = temp.write_text("notes.md", "# Notes\n");
+
+    let changes = semantic_diff_files(None, Some(&config)).unwrap();
+    assert_eq!(summary(&changes), vec![("added", "")]);
+    assert_eq!(changes[0].new_value, Some(json!({"debug": true})));
+
+    assert!(semantic_diff_files(Some(&notes), Some(&notes)).is_err());
+    let broken = temp.write_text("broken.json", "{");
+    assert!(semantic_diff_files(Some(&config), Some(&broken))
+        .unwrap_err()
+        .starts_with("Invalid JSON"));
+}
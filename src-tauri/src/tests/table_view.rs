@@ -0,0 +1,171 @@
+use crate::table_view::{detect_dialect, parse_table, replace_table_cell, TableQuery};
+use std::path::Path;
+
+fn kinds(content: &str, path: &str) -> Vec<(String, &'static str)> {
+    parse_table(content, Path::new(path), String::new())
+        .page(&TableQuery::default())
+        .columns
+        .into_iter()
+        .map(|column| (column.name, column.kind))
+        .collect()
+}
+
+fn first_cells(content: &str, query: TableQuery) -> Vec<String> {
+    parse_table(content, Path::new("data.csv"), String::new())
+        .page(&query)
+        .rows
+        .into_iter()
+        .map(|row| row.cells[0].clone())
+        .collect()
+}
+
+#[test]
+fn dialects_are_sniffed_from_the_content() {
+    let semicolons = "name;price;note\nApple;1,50;\"a; b\"\nPear;2,00;x\n";
+    let dialect = detect_dialect(semicolons, Path::new("prices.csv"));
+    assert_eq!(dialect.delimiter, b';');
+    assert_eq!(dialect.quote, b'"');
+    assert!(dialect.has_header);
+
+    let tabs = "id\tname\n1\tAda, Countess\n2\tGrace\n";
+    assert_eq!(
+        detect_dialect(tabs, Path::new("people.tsv")).delimiter,
+        b'\t'
+    );
+    assert_eq!(
+        detect_dialect(tabs, Path::new("people.csv")).delimiter,
+        b'\t'
+    );
+    assert_eq!(
+        detect_dialect("single\n", Path::new("a.tsv")).delimiter,
+        b'\t'
+    );
+
+    let single_quoted = "'a,b',c\n'd,e',f\n";
+    assert_eq!(
+        detect_dialect(single_quoted, Path::new("q.csv")).quote,
+        b'\''
+    );
+
+    let numbers = "1,2,3\n4,5,6\n";
+    assert!(!detect_dialect(numbers, Path::new("n.csv")).has_header);
+    let labels = "north,south\neast,west\n";
+    assert!(detect_dialect(labels, Path::new("l.csv")).has_header);
+    let repeated = "a,a\nb,c\n";
+    assert!(!detect_dialect(repeated, Path::new("r.csv")).has_header);
+}
+
+#[test]
+fn column_kinds_are_inferred_from_non_empty_cells() {
+    let content = "\
+id,score,active,joined,name
+1,2.5,true,2024-01-31,Ada
+2,,FALSE,2024-02-01T09:00,Grace
+3,7,false,,
+";
+    assert_eq!(
+        kinds(content, "people.csv"),
+        vec![
+            ("id".to_string(), "integer"),
+            ("score".to_string(), "number"),
+            ("active".to_string(), "boolean"),
+            ("joined".to_string(), "date"),
+            ("name".to_string(), "string"),
+        ]
+    );
+    assert_eq!(
+        kinds("1,x\n2,y,extra\n", "nohead.csv"),
+        vec![
+            ("Column 1".to_string(), "integer"),
+            ("Column 2".to_string(), "string"),
+            ("Column 3".to_string(), "string"),
+        ]
+    );
+}
+
+#[test]
+fn pages_are_sorted_and_filtered_on_the_backend() {
+    let content = "name,qty\nbeta,10\nAlpha,9\ngamma,\ndelta,100\n";
+    assert_eq!(
+        first_cells(
+            content,
+            TableQuery {
+                sort_column: Some(1),
+                ..TableQuery::default()
+            }
+        ),
+        vec!["gamma", "Alpha", "beta", "delta"]
+    );
+    assert_eq!(
+        first_cells(
+            content,
+            TableQuery {
+                sort_column: Some(0),
+                sort_descending: true,
+                ..TableQuery::default()
+            }
+        ),
+        vec!["gamma", "delta", "beta", "Alpha"]
+    );
+
+    let table = parse_table(content, Path::new("data.csv"), "v1".to_string());
+    let page = table.page(&TableQuery {
+        offset: 1,
+        limit: Some(1),
+        filter: Some("A".to_string()),
+        filter_column: Some(0),
+        ..TableQuery::default()
+    });
+    assert_eq!(page.version, "v1");
+    assert_eq!(page.total_rows, 4);
+    assert_eq!(page.matched_rows, 4);
+    assert_eq!(page.rows.len(), 1);
+    assert_eq!(page.rows[0].index, 1);
+    assert_eq!(page.rows[0].cells, vec!["Alpha", "9"]);
+
+    let page = table.page(&TableQuery {
+        filter: Some("10".to_string()),
+        ..TableQuery::default()
+    });
+    assert_eq!(page.matched_rows, 2);
+    assert_eq!(
+        page.rows.iter().map(|row| row.index).collect::<Vec<_>>(),
+        vec![0, 3]
+    );
+}
+
+#[test]
+fn cell_edits_keep_the_rest_of_the_file_as_written() {
+    let content = "name,\"note\",qty\r\n\"Ada\",\"says \"\"hi\"\"\",1\r\nGrace,plain,2\r\n";
+    let path = Path::new("data.csv");
+
+    assert_eq!(
+        replace_table_cell(content, path, 0, 0, "Ada L.").unwrap(),
+        "name,\"note\",qty\r\n\"Ada L.\",\"says \"\"hi\"\"\",1\r\nGrace,plain,2\r\n"
+    );
+    assert_eq!(
+        replace_table_cell(content, path, 1, 1, "a, \"b\"").unwrap(),
+        "name,\"note\",qty\r\n\"Ada\",\"says \"\"hi\"\"\",1\r\nGrace,\"a, \"\"b\"\"\",2\r\n"
+    );
+    assert_eq!(
+        replace_table_cell(content, path, 1, 2, "3").unwrap(),
+        "name,\"note\",qty\r\n\"Ada\",\"says \"\"hi\"\"\",1\r\nGrace,plain,3\r\n"
+    );
+    assert_eq!(
+        replace_table_cell(content, path, 1, 4, "x").unwrap(),
+        "name,\"note\",qty\r\n\"Ada\",\"says \"\"hi\"\"\",1\r\nGrace,plain,2,,x\r\n"
+    );
+    assert!(replace_table_cell(content, path, 2, 0, "x").is_err());
+
+    let multiline = "id,text\n1,\"line one\nline two\"\n2,last\n";
+    assert_eq!(
+        replace_table_cell(multiline, path, 1, 1, "final").unwrap(),
+        "id,text\n1,\"line one\nline two\"\n2,final\n"
+    );
+
+    let tabs = "a\tb\n1\t2\n";
+    assert_eq!(
+        replace_table_cell(tabs, Path::new("data.tsv"), 0, 1, "x\ty").unwrap(),
+        "a\tb\n1\t\"x\ty\"\n"
+    );
+}
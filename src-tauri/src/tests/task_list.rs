@@ -0,0 +1,57 @@
+use crate::task_list::{parse_tasks, task_list, TaskItem};
+
+#[test]
+fn task_items_are_read_with_their_sections() {
+    let plan = "\
+Intro
+- [x] agree on scope
+
+## Build
+1. [ ] write the *parser*
+   - [X] nested `step`
+2. [ ] wire the
+   event
+
+- plain item
+* [ ]not a task
+
+## Ship
+
+- [ ] release
+
+  notes for the release
+
+```md
+- [ ] example only
+```
+";
+    let item = |line, text: &str, done, section: Option<&str>| TaskItem {
+        line,
+        text: text.to_string(),
+        done,
+        section: section.map(str::to_string),
+    };
+    assert_eq!(
+        parse_tasks(plan),
+        vec![
+            item(2, "agree on scope", true, None),
+            item(5, "write the parser", false, Some("Build")),
+            item(6, "nested step", true, Some("Build")),
+            item(7, "wire the event", false, Some("Build")),
+            item(15, "release", false, Some("Ship")),
+        ]
+    );
+
+    let list = task_list("plan.md", plan);
+    assert_eq!((list.done, list.total), (2, 5));
+    assert_eq!(
+        list.unchecked
+            .iter()
+            .map(|item| item.line)
+            .collect::<Vec<_>>(),
+        vec![5, 7, 15]
+    );
+    let json = serde_json::to_value(&list).unwrap();
+    assert_eq!(json["path"], "plan.md");
+    assert_eq!(json["unchecked"][0]["section"], "Build");
+}
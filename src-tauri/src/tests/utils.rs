@@ -1,39 +1,54 @@
 use super::common::TempTestDir;
 use super::*;
-use walkdir::WalkDir;
+use crate::languages::detect_language;
+use ignore::WalkBuilder;
+
+fn top_level_entries(root: &Path) -> Vec<DirEntry> {
+    WalkBuilder::new(root)
+        .standard_filters(false)
+        .max_depth(Some(1))
+        .build()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.depth() == 1)
+        .collect()
+}
+
+fn kind_of(name: &str) -> &'static str {
+    detect_language(Path::new(name), "").kind
+}
 
 #[test]
 fn file_type_helpers_are_case_insensitive_for_supported_extensions() {
-    assert!(is_markdown(Path::new("README.MD")));
-    assert!(is_markdown(Path::new("notes.Markdown")));
-    assert!(!is_markdown(Path::new("notes.txt")));
+    assert_eq!(kind_of("README.MD"), "markdown");
+    assert_eq!(kind_of("notes.Markdown"), "markdown");
+    assert_ne!(kind_of("notes.txt"), "markdown");
 
     assert!(is_text_like(Path::new("data.JSON")));
     assert!(is_text_like(Path::new("script.TSX")));
     assert!(!is_text_like(Path::new("archive.zip")));
     assert!(!is_text_like(Path::new("no_extension")));
 
-    assert_eq!(file_kind(Path::new("post.md")), "markdown");
-    assert_eq!(file_kind(Path::new("post.txt")), "text");
-    assert_eq!(file_kind(Path::new("main.rs")), "code");
-    assert_eq!(file_kind(Path::new("app.swift")), "code");
-    assert_eq!(file_kind(Path::new("data.json")), "code");
-    assert_eq!(file_kind(Path::new("main.cpp")), "code");
-    assert_eq!(file_kind(Path::new("style.CSS")), "code");
-    assert_eq!(file_kind(Path::new(".gitignore")), "code");
-    assert_eq!(file_kind(Path::new(".dockerignore")), "code");
-    assert_eq!(file_kind(Path::new("Dockerfile")), "code");
-    assert_eq!(file_kind(Path::new("Dockerfile.dev")), "code");
-    assert_eq!(file_kind(Path::new("api.Dockerfile")), "code");
+    assert_eq!(kind_of("post.md"), "markdown");
+    assert_eq!(kind_of("post.txt"), "text");
+    assert_eq!(kind_of("main.rs"), "code");
+    assert_eq!(kind_of("app.swift"), "code");
+    assert_eq!(kind_of("data.json"), "code");
+    assert_eq!(kind_of("main.cpp"), "code");
+    assert_eq!(kind_of("style.CSS"), "code");
+    assert_eq!(kind_of(".gitignore"), "code");
+    assert_eq!(kind_of(".dockerignore"), "code");
+    assert_eq!(kind_of("Dockerfile"), "code");
+    assert_eq!(kind_of("Dockerfile.dev"), "code");
+    assert_eq!(kind_of("api.Dockerfile"), "code");
 
     assert!(is_text_like(Path::new("Dockerfile")));
     assert!(is_text_like(Path::new("Dockerfile.dev")));
     assert!(is_text_like(Path::new("api.Dockerfile")));
 
-    assert!(is_code(Path::new("lib.rs")));
-    assert!(is_code(Path::new("index.JS")));
-    assert!(!is_code(Path::new("readme.txt")));
-    assert!(!is_code(Path::new("notes.md")));
+    assert_eq!(kind_of("lib.rs"), "code");
+    assert_eq!(kind_of("index.JS"), "code");
+    assert_eq!(kind_of("readme.txt"), "text");
+    assert_eq!(kind_of("notes.md"), "markdown");
 }
 
 #[test]
@@ -55,23 +70,23 @@ fn utility_helpers_build_expected_strings_and_ids() {
 }
 
 #[test]
-fn should_traverse_blocks_known_build_dirs() {
+fn should_traverse_blocks_git_dir_but_not_build_dirs() {
     let temp = TempTestDir::new();
     for dir in &[".git", "node_modules", "target", "dist", "build"] {
         temp.mkdir(dir);
     }
 
-    for entry in WalkDir::new(temp.path()).min_depth(1).max_depth(1) {
-        let entry = entry.unwrap();
+    for entry in top_level_entries(temp.path()) {
         let name = entry.file_name().to_str().unwrap().to_string();
-        if [".git", "node_modules", "target", "dist", "build"].contains(&name.as_str()) {
+        if name == ".git" {
             assert!(
                 !should_traverse_with_hidden(&entry, true),
-                "{name} should not be traversed even when show_hidden=true"
+                ".git should not be traversed even when show_hidden=true"
             );
+        } else {
             assert!(
-                !should_traverse_with_hidden(&entry, false),
-                "{name} should not be traversed when show_hidden=false"
+                should_traverse_with_hidden(&entry, false),
+                "{name} should be left to ignore rules instead of being blocked by name"
             );
         }
     }
@@ -83,8 +98,7 @@ fn should_traverse_respects_show_hidden_for_dotdirs() {
     temp.mkdir(".hidden_config");
     temp.mkdir("visible");
 
-    for entry in WalkDir::new(temp.path()).min_depth(1).max_depth(1) {
-        let entry = entry.unwrap();
+    for entry in top_level_entries(temp.path()) {
         let name = entry.file_name().to_str().unwrap();
         match name {
             ".hidden_config" => {
@@ -111,8 +125,7 @@ fn should_traverse_non_dirs_always_traverse() {
     let temp = TempTestDir::new();
     temp.write_text("file.md", "content");
 
-    for entry in WalkDir::new(temp.path()).min_depth(1).max_depth(1) {
-        let entry = entry.unwrap();
+    for entry in top_level_entries(temp.path()) {
         if entry.file_name().to_str().unwrap() == "file.md" {
             assert!(should_traverse_with_hidden(&entry, false));
             assert!(should_traverse_with_hidden(&entry, true));
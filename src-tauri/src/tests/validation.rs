@@ -0,0 +1,78 @@
+use crate::validation::{line_column, validate_structured, validate_structured_text};
+
+fn locations(content: &str, kind: &str) -> Vec<(usize, usize, &'static str)> {
+    validate_structured(content, kind)
+        .into_iter()
+        .map(|diagnostic| (diagnostic.line, diagnostic.column, diagnostic.severity))
+        .collect()
+}
+
+#[test]
+fn line_column_counts_characters_from_one() {
+    let content = "ab\ncdé\nf";
+    assert_eq!(line_column(content, 0), (1, 1));
+    assert_eq!(line_column(content, 4), (2, 2));
+    assert_eq!(line_column(content, content.len()), (3, 2));
+}
+
+#[test]
+fn valid_documents_have_no_diagnostics() {
+    assert!(validate_structured("{\"a\": [1, 2]}", "json").is_empty());
+    assert!(validate_structured("{\n  // comment\n  \"a\": 1,\n}", "json").is_empty());
+    assert!(validate_structured("a: 1\n---\nb: 2\n", "yaml").is_empty());
+    assert!(validate_structured("[package]\nname = \"x\"\n", "toml").is_empty());
+    assert!(validate_structured("<a><b/></a>", "xml").is_empty());
+    assert!(validate_structured("a,b\n1,2\n", "csv").is_empty());
+    assert!(validate_structured_text("not anything".into(), None, None).is_empty());
+}
+
+#[test]
+fn json_errors_point_at_the_offending_token() {
+    let diagnostics = validate_structured("{\n  \"a\": 1\n  \"b\": 2\n}", "json");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!((diagnostics[0].line, diagnostics[0].column), (3, 3));
+    assert!(!diagnostics[0].message.contains(" at line "));
+
+    assert_eq!(
+        locations("{\"ok\": 1}\n{bad}\n\n[1]\n", "jsonl"),
+        vec![(2, 2, "error")]
+    );
+    assert_eq!(locations("{\n  a: 1,\n  b: ]\n}", "json5")[0].0, 3);
+}
+
+#[test]
+fn yaml_and_toml_errors_carry_their_location() {
+    let yaml = validate_structured("a: 1\nb: [1, 2\nc: 3\n", "yaml");
+    assert_eq!(yaml.len(), 1);
+    assert!(yaml[0].line >= 2);
+
+    assert_eq!(
+        locations("[a]\nx = 1\ny = \n", "toml"),
+        vec![(3, 5, "error")]
+    );
+    assert_eq!(
+        locations("[a]\nx = 1\n\n[a]\n", "toml")[0].0,
+        4,
+        "duplicate tables are reported where they are redefined"
+    );
+}
+
+#[test]
+fn xml_reports_mismatched_and_unclosed_elements() {
+    assert_eq!(locations("<a>\n  <b></c>\n</a>", "xml")[0].0, 2);
+    let unclosed = validate_structured("<root>\n  <item>\n  </item>\n", "xml");
+    assert_eq!((unclosed[0].line, unclosed[0].column), (1, 1));
+    assert!(unclosed[0].message.contains("<root>"));
+}
+
+#[test]
+fn csv_ragged_rows_are_warnings() {
+    assert_eq!(
+        locations("a,b,c\n1,2,3\n4,5\n", "csv"),
+        vec![(3, 1, "warning")]
+    );
+    assert_eq!(locations("a\tb\n1\t2\t3\n", "csv"), vec![(2, 1, "warning")]);
+    // A tab inside a field does not make a comma-separated file a TSV.
+    assert!(locations("name,note\nada,\"a\tb\"\nbob,c\n", "csv").is_empty());
+    assert_eq!(locations("a;b\n1;2\n3\n", "csv"), vec![(3, 1, "warning")]);
+}
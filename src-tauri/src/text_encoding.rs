@@ -0,0 +1,151 @@
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+
+/// How many leading bytes are inspected when sniffing for binary content.
+const SNIFF_LEN: usize = 8192;
+
+pub(crate) struct DecodedText {
+    pub(crate) content: String,
+    pub(crate) encoding: &'static Encoding,
+    pub(crate) has_bom: bool,
+}
+
+pub(crate) fn encoding_for_label(label: &str) -> Result<&'static Encoding, String> {
+    Encoding::for_label(label.trim().as_bytes()).ok_or_else(|| format!("Unknown encoding: {label}"))
+}
+
+/// Recognize UTF-16 without a BOM by the zero high bytes of ASCII text:
+/// nearly every code unit has a NUL on one side and none on the other.
+pub(crate) fn guess_bomless_utf16(bytes: &[u8]) -> Option<&'static Encoding> {
+    let sample = &bytes[..bytes.len().min(SNIFF_LEN) & !1];
+    if sample.len() < 4 {
+        return None;
+    }
+    let pairs = sample.len() / 2;
+    let even_nuls = sample.iter().step_by(2).filter(|b| **b == 0).count();
+    let odd_nuls = sample
+        .iter()
+        .skip(1)
+        .step_by(2)
+        .filter(|b| **b == 0)
+        .count();
+    if odd_nuls * 10 >= pairs * 7 && even_nuls * 10 <= pairs {
+        Some(UTF_16LE)
+    } else if even_nuls * 10 >= pairs * 7 && odd_nuls * 10 <= pairs {
+        Some(UTF_16BE)
+    } else {
+        None
+    }
+}
+
+fn detect_encoding(bytes: &[u8]) -> Result<&'static Encoding, String> {
+    if std::str::from_utf8(bytes).is_ok() {
+        return Ok(UTF_8);
+    }
+    if let Some(utf16) = guess_bomless_utf16(bytes) {
+        return Ok(utf16);
+    }
+    if bytes[..bytes.len().min(SNIFF_LEN)].contains(&0) {
+        return Err("Unable to read binary file as text".to_string());
+    }
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(bytes, true);
+    Ok(detector.guess(None, true))
+}
+
+/// Pick an encoding from the leading bytes of a file that is too large to
+/// decode whole. Returns the encoding and the BOM length to skip. Only
+/// ASCII-compatible encodings are accepted, since callers split lines on
+/// raw `\n` bytes.
+pub(crate) fn sniff_encoding(sample: &[u8]) -> Result<(&'static Encoding, usize), String> {
+    let (encoding, bom_len) = match Encoding::for_bom(sample) {
+        Some(found) => found,
+        None => match std::str::from_utf8(sample) {
+            Ok(_) => (UTF_8, 0),
+            // The sample may end in the middle of a multi-byte character.
+            Err(e) if e.error_len().is_none() => (UTF_8, 0),
+            Err(_) => (detect_encoding(sample)?, 0),
+        },
+    };
+    if !encoding.is_ascii_compatible() {
+        return Err(format!("Large {} files are not supported", encoding.name()));
+    }
+    Ok((encoding, bom_len))
+}
+
+/// Decode file bytes for display.
+///
+/// A byte order mark wins, then valid UTF-8, then a statistical guess
+/// (Windows-1252, Shift_JIS, GBK, ...). `forced` skips detection, which is
+/// how "reopen with encoding" overrides a wrong guess. Bytes that are not
+/// valid in the chosen encoding are reported as an error rather than
+/// silently replaced, since saving would then corrupt the file.
+pub(crate) fn decode_text(
+    bytes: &[u8],
+    forced: Option<&'static Encoding>,
+) -> Result<DecodedText, String> {
+    let bom = Encoding::for_bom(bytes);
+    let (encoding, bom_len) = match (forced, bom) {
+        (Some(forced), Some((bom_encoding, len))) if forced == bom_encoding => (forced, len),
+        (Some(forced), _) => (forced, 0),
+        (None, Some((bom_encoding, len))) => (bom_encoding, len),
+        (None, None) => (detect_encoding(bytes)?, 0),
+    };
+
+    let (content, had_errors) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+    if had_errors {
+        return Err(format!("Unable to read file as {} text", encoding.name()));
+    }
+    Ok(DecodedText {
+        content: content.into_owned(),
+        encoding,
+        has_bom: bom_len > 0,
+    })
+}
+
+fn bom_for(encoding: &'static Encoding) -> &'static [u8] {
+    if encoding == UTF_8 {
+        b"\xEF\xBB\xBF"
+    } else if encoding == UTF_16LE {
+        b"\xFF\xFE"
+    } else if encoding == UTF_16BE {
+        b"\xFE\xFF"
+    } else {
+        b""
+    }
+}
+
+/// Encode `content` for saving in `encoding`, refusing when a character
+/// has no representation there instead of writing a substitute.
+pub(crate) fn encode_text(
+    content: &str,
+    encoding: &'static Encoding,
+    has_bom: bool,
+) -> Result<Vec<u8>, String> {
+    let mut output = Vec::with_capacity(content.len() + 3);
+    if has_bom {
+        output.extend_from_slice(bom_for(encoding));
+    }
+
+    // encoding_rs only decodes UTF-16; its encoder falls back to UTF-8.
+    if encoding == UTF_16LE || encoding == UTF_16BE {
+        for unit in content.encode_utf16() {
+            let bytes = if encoding == UTF_16LE {
+                unit.to_le_bytes()
+            } else {
+                unit.to_be_bytes()
+            };
+            output.extend_from_slice(&bytes);
+        }
+        return Ok(output);
+    }
+
+    let (encoded, _, had_unmappable) = encoding.encode(content);
+    if had_unmappable {
+        return Err(format!(
+            "Some characters cannot be saved as {}",
+            encoding.name()
+        ));
+    }
+    output.extend_from_slice(&encoded);
+    Ok(output)
+}
@@ -0,0 +1,245 @@
+use super::*;
+use crate::json_format::relaxed_json_error_offset;
+use crate::json_schema::schema_diagnostics;
+use crate::table_view::detect_dialect;
+
+const SEVERITY_ERROR: &str = "error";
+const SEVERITY_WARNING: &str = "warning";
+
+/// A problem found in a structured file, in the shape the editor gutter
+/// shows.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Diagnostic {
+    pub(crate) message: String,
+    /// 1-based line number.
+    pub(crate) line: usize,
+    /// 1-based column, counted in characters.
+    pub(crate) column: usize,
+    /// `"error"` or `"warning"`.
+    pub(crate) severity: &'static str,
+}
+
+impl Diagnostic {
+    pub(crate) fn error(message: impl Into<String>, line: usize, column: usize) -> Self {
+        Self {
+            message: message.into(),
+            line,
+            column,
+            severity: SEVERITY_ERROR,
+        }
+    }
+
+    pub(crate) fn warning(message: impl Into<String>, line: usize, column: usize) -> Self {
+        Self {
+            message: message.into(),
+            line,
+            column,
+            severity: SEVERITY_WARNING,
+        }
+    }
+
+    /// An error at byte `offset` of `content`.
+    pub(crate) fn error_at(message: impl Into<String>, content: &str, offset: usize) -> Self {
+        let (line, column) = line_column(content, offset);
+        Self::error(message, line, column)
+    }
+}
+
+/// 1-based line and character column of byte `offset` in `content`.
+pub(crate) fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let mut offset = offset.min(content.len());
+    while !content.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &content[..offset];
+    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// Parser messages repeat the location the diagnostic already carries.
+fn strip_location(message: String) -> String {
+    match message.rfind(" at line ") {
+        Some(index) => message[..index].to_string(),
+        None => message,
+    }
+}
+
+fn validate_json(content: &str) -> Vec<Diagnostic> {
+    match serde_json::from_str::<serde_json::Value>(content) {
+        Ok(_) => Vec::new(),
+        // `tsconfig.json` and VS Code settings are JSONC in `.json` files.
+        Err(_) if relaxed_json_error_offset(content).is_none() => Vec::new(),
+        Err(e) => vec![Diagnostic::error(
+            strip_location(e.to_string()),
+            e.line().max(1),
+            e.column().max(1),
+        )],
+    }
+}
+
+fn validate_relaxed_json(content: &str, kind: &str) -> Vec<Diagnostic> {
+    relaxed_json_error_offset(content)
+        .map(|offset| {
+            Diagnostic::error_at(format!("Invalid {}", kind.to_uppercase()), content, offset)
+        })
+        .into_iter()
+        .collect()
+}
+
+fn validate_json_lines(content: &str) -> Vec<Diagnostic> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(index, line)| {
+            let e = serde_json::from_str::<serde_json::Value>(line).err()?;
+            Some(Diagnostic::error(
+                strip_location(e.to_string()),
+                index + 1,
+                e.column().max(1),
+            ))
+        })
+        .collect()
+}
+
+fn validate_yaml(content: &str) -> Vec<Diagnostic> {
+    for document in serde_yml::Deserializer::from_str(content) {
+        if let Err(e) = serde_yml::Value::deserialize(document) {
+            let (line, column) = e
+                .location()
+                .map_or((1, 1), |location| (location.line(), location.column()));
+            return vec![Diagnostic::error(
+                strip_location(e.to_string()),
+                line,
+                column,
+            )];
+        }
+    }
+    Vec::new()
+}
+
+fn validate_toml(content: &str) -> Vec<Diagnostic> {
+    match toml::from_str::<toml::Value>(content) {
+        Ok(_) => Vec::new(),
+        Err(e) => {
+            let offset = e.span().map_or(0, |span| span.start);
+            vec![Diagnostic::error_at(
+                e.message().to_string(),
+                content,
+                offset,
+            )]
+        }
+    }
+}
+
+fn validate_xml(content: &str) -> Vec<Diagnostic> {
+    use quick_xml::events::Event;
+    use quick_xml::reader::Reader;
+
+    let mut reader = Reader::from_str(content);
+    let mut open: Vec<(String, usize)> = Vec::new();
+    loop {
+        let start = reader.buffer_position() as usize;
+        match reader.read_event() {
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(tag)) => {
+                let name = String::from_utf8_lossy(tag.name().as_ref()).to_string();
+                let offset = content[start..].find('<').map_or(start, |i| start + i);
+                open.push((name, offset));
+            }
+            Ok(Event::End(_)) => {
+                open.pop();
+            }
+            Ok(_) => {}
+            Err(e) => {
+                return vec![Diagnostic::error_at(
+                    e.to_string(),
+                    content,
+                    reader.error_position() as usize,
+                )];
+            }
+        }
+    }
+    open.pop()
+        .map(|(name, offset)| {
+            Diagnostic::error_at(format!("Element <{name}> is never closed"), content, offset)
+        })
+        .into_iter()
+        .collect()
+}
+
+/// Rows whose field count differs from the first row are warnings, since
+/// many tools accept them.
+fn validate_csv(content: &str) -> Vec<Diagnostic> {
+    let dialect = detect_dialect(content, Path::new(""));
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .delimiter(dialect.delimiter)
+        .quote(dialect.quote)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+
+    let mut diagnostics = Vec::new();
+    let mut expected = None;
+    for record in reader.records() {
+        match record {
+            Ok(record) => {
+                let line = record.position().map_or(1, |p| p.line() as usize);
+                let expected = *expected.get_or_insert(record.len());
+                if record.len() != expected {
+                    diagnostics.push(Diagnostic::warning(
+                        format!("Row has {} fields, expected {expected}", record.len()),
+                        line,
+                        1,
+                    ));
+                }
+            }
+            Err(e) => {
+                let line = e.position().map_or(1, |p| p.line() as usize);
+                diagnostics.push(Diagnostic::error(e.to_string(), line, 1));
+                break;
+            }
+        }
+    }
+    diagnostics
+}
+
+/// Parse `content` as `kind` and report why it does not parse. Unknown
+/// kinds have no diagnostics.
+pub(crate) fn validate_structured(content: &str, kind: &str) -> Vec<Diagnostic> {
+    match kind {
+        "json" => validate_json(content),
+        "jsonc" | "json5" => validate_relaxed_json(content, kind),
+        "jsonl" => validate_json_lines(content),
+        "yaml" => validate_yaml(content),
+        "toml" => validate_toml(content),
+        "xml" => validate_xml(content),
+        "csv" => validate_csv(content),
+        _ => Vec::new(),
+    }
+}
+
+/// Diagnostics for the editor gutter. `kind` is the same format name
+/// `format_structured_text` takes. When `path` is given and the content
+/// parses, it is also checked against the file's JSON Schema.
+#[tauri::command]
+pub(crate) fn validate_structured_text(
+    content: String,
+    kind: Option<String>,
+    path: Option<String>,
+) -> Vec<Diagnostic> {
+    let Some(kind) = kind.map(|kind| kind.trim().to_ascii_lowercase()) else {
+        return Vec::new();
+    };
+    let diagnostics = validate_structured(&content, &kind);
+    match path {
+        Some(path) if diagnostics.is_empty() => {
+            schema_diagnostics(Path::new(&path), &content, &kind)
+        }
+        _ => diagnostics,
+    }
+}
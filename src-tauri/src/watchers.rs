@@ -1,4 +1,9 @@
 use super::*;
+use crate::file_index::{build_shared_index, update_shared_index};
+use crate::local_history::{HistorySource, LocalHistory};
+use crate::markdown_outline::emit_outline_for_changed_file;
+use crate::project_ignore::{is_ignore_file, ProjectIgnore};
+use crate::task_list::emit_task_progress;
 
 fn should_emit_folder_watch_event(event: &Event) -> bool {
     matches!(
@@ -11,6 +16,20 @@ fn should_emit_folder_watch_event(event: &Event) -> bool {
     )
 }
 
+fn has_unignored_path(ignore: &ProjectIgnore, event: &Event) -> bool {
+    for path in &event.paths {
+        if is_ignore_file(path) {
+            ignore.invalidate(path);
+        }
+    }
+
+    event.paths.is_empty()
+        || event
+            .paths
+            .iter()
+            .any(|path| !ignore.is_ignored(path, path.is_dir()))
+}
+
 pub(super) fn clear_project_folder_watch_for_label(app: &tauri::AppHandle, label: &str) {
     let registry = app.state::<FolderWatchRegistry>();
     if let Ok(mut watches) = registry.by_window.lock() {
@@ -40,6 +59,8 @@ pub(super) fn install_project_folder_watch(
     app: &tauri::AppHandle,
     label: &str,
     root: PathBuf,
+    show_hidden: bool,
+    show_ignored: bool,
 ) -> Result<(), String> {
     let canonical_root = fs::canonicalize(&root).unwrap_or(root);
     if !canonical_root.is_dir() {
@@ -50,7 +71,10 @@ pub(super) fn install_project_folder_watch(
         let registry = app.state::<FolderWatchRegistry>();
         if let Ok(watches) = registry.by_window.lock() {
             if let Some(existing) = watches.get(label) {
-                if existing.root == canonical_root {
+                if existing.root == canonical_root
+                    && existing.show_hidden == show_hidden
+                    && existing.show_ignored == show_ignored
+                {
                     return Ok(());
                 }
             }
@@ -61,6 +85,9 @@ pub(super) fn install_project_folder_watch(
     let label_string = label.to_string();
     let last_emitted = Arc::new(Mutex::new(Instant::now() - FOLDER_WATCH_DEBOUNCE));
     let throttle = Arc::clone(&last_emitted);
+    let ignore = (!show_ignored).then(|| ProjectIgnore::new(&canonical_root));
+    let index = build_shared_index(&canonical_root, show_hidden, show_ignored);
+    let watched_index = Arc::clone(&index);
 
     let mut watcher = RecommendedWatcher::new(
         move |result: notify::Result<Event>| {
@@ -72,6 +99,14 @@ pub(super) fn install_project_folder_watch(
                 return;
             }
 
+            update_shared_index(&watched_index, &event.paths);
+
+            if let Some(ignore) = &ignore {
+                if !has_unignored_path(ignore, &event) {
+                    return;
+                }
+            }
+
             let now = Instant::now();
             let Ok(mut last) = throttle.lock() else {
                 return;
@@ -100,6 +135,9 @@ pub(super) fn install_project_folder_watch(
         label.to_string(),
         WindowFolderWatch {
             root: canonical_root,
+            show_hidden,
+            show_ignored,
+            index,
             _watcher: watcher,
         },
     );
@@ -156,6 +194,8 @@ pub(super) fn install_project_file_watch(
     let last_emitted_by_path: Arc<Mutex<HashMap<String, Instant>>> =
         Arc::new(Mutex::new(HashMap::new()));
     let throttle = Arc::clone(&last_emitted_by_path);
+    let watched_files: std::collections::HashSet<PathBuf> =
+        normalized_paths.iter().cloned().collect();
 
     let mut watcher = RecommendedWatcher::new(
         move |result: notify::Result<Event>| {
@@ -172,7 +212,7 @@ pub(super) fn install_project_file_watch(
                 return;
             };
 
-            for path in &event.paths {
+            for path in event.paths.iter().filter(|p| watched_files.contains(*p)) {
                 let path_string = path_to_string(path);
                 let should_emit = emitted
                     .get(&path_string)
@@ -182,21 +222,35 @@ pub(super) fn install_project_file_watch(
                     continue;
                 }
                 emitted.insert(path_string.clone(), now);
+                app_handle
+                    .state::<LocalHistory>()
+                    .record_file(path, HistorySource::External);
                 emit_to_window(
                     &app_handle,
                     &label_string,
                     EVENT_PROJECT_FILE_CHANGED,
                     path_string,
                 );
+                emit_outline_for_changed_file(&app_handle, &label_string, path);
+                emit_task_progress(&app_handle, path);
             }
         },
         NotifyConfig::default(),
     )
     .map_err(|e| format!("Unable to start file watcher: {e}"))?;
 
-    for path in &normalized_paths {
+    // Watch parent directories rather than the files themselves: atomic
+    // saves replace the file's inode, which would silently end a per-file
+    // watch after the first save.
+    let mut parents: Vec<&Path> = normalized_paths
+        .iter()
+        .filter_map(|path| path.parent())
+        .collect();
+    parents.sort();
+    parents.dedup();
+    for parent in parents {
         watcher
-            .watch(path, RecursiveMode::NonRecursive)
+            .watch(parent, RecursiveMode::NonRecursive)
             .map_err(|e| format!("Unable to watch file: {e}"))?;
     }
 
@@ -1,7 +1,10 @@
 use crate::constants::{
-    EVENT_CONTEXT_MENU_DELETE, EVENT_TAB_CONTEXT_MENU_CLOSE, EVENT_TAB_CONTEXT_MENU_CLOSE_OTHERS,
+    EVENT_CONTEXT_MENU_DELETE, EVENT_CONTEXT_MENU_DUPLICATE, EVENT_CONTEXT_MENU_NEW_FILE,
+    EVENT_CONTEXT_MENU_NEW_FOLDER, EVENT_CONTEXT_MENU_RENAME, EVENT_TAB_CONTEXT_MENU_CLOSE,
+    EVENT_TAB_CONTEXT_MENU_CLOSE_OTHERS,
 };
 use crate::menu_events::emit_to_window;
+use std::path::Path;
 use tauri::menu::{MenuBuilder, MenuItem, PredefinedMenuItem};
 use tauri::Manager;
 use tauri_plugin_opener::OpenerExt;
@@ -19,18 +22,53 @@ pub(crate) fn show_sidebar_context_menu(window: tauri::Window, path: String) ->
         "Reveal File"
     };
 
+    // New entries go inside a folder, or next to a file.
+    let target_dir = if Path::new(&path).is_dir() {
+        path.clone()
+    } else {
+        Path::new(&path)
+            .parent()
+            .map(|parent| parent.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.clone())
+    };
+
+    let new_file_item =
+        MenuItem::with_id(&app, "context_new_file", "New File…", true, None::<&str>)
+            .map_err(|e| format!("Unable to create menu item: {e}"))?;
+
+    let new_folder_item = MenuItem::with_id(
+        &app,
+        "context_new_folder",
+        "New Folder…",
+        true,
+        None::<&str>,
+    )
+    .map_err(|e| format!("Unable to create menu item: {e}"))?;
+
     let reveal_item = MenuItem::with_id(&app, "context_reveal", reveal_label, true, None::<&str>)
         .map_err(|e| format!("Unable to create menu item: {e}"))?;
 
-    let separator = PredefinedMenuItem::separator(&app)
-        .map_err(|e| format!("Unable to create separator: {e}"))?;
+    let rename_item = MenuItem::with_id(&app, "context_rename", "Rename…", true, None::<&str>)
+        .map_err(|e| format!("Unable to create menu item: {e}"))?;
+
+    let duplicate_item =
+        MenuItem::with_id(&app, "context_duplicate", "Duplicate", true, None::<&str>)
+            .map_err(|e| format!("Unable to create menu item: {e}"))?;
 
     let delete_item = MenuItem::with_id(&app, "context_delete", "Delete", true, None::<&str>)
         .map_err(|e| format!("Unable to create menu item: {e}"))?;
 
+    let separator = PredefinedMenuItem::separator(&app)
+        .map_err(|e| format!("Unable to create separator: {e}"))?;
+
     let menu = MenuBuilder::new(&app)
+        .item(&new_file_item)
+        .item(&new_folder_item)
+        .item(&separator)
         .item(&reveal_item)
         .item(&separator)
+        .item(&rename_item)
+        .item(&duplicate_item)
         .item(&delete_item)
         .build()
         .map_err(|e| format!("Unable to build context menu: {e}"))?;
@@ -39,6 +77,28 @@ pub(crate) fn show_sidebar_context_menu(window: tauri::Window, path: String) ->
         "context_reveal" => {
             let _ = app.opener().reveal_item_in_dir(&path);
         }
+        "context_new_file" => {
+            emit_to_window(
+                &app,
+                &label,
+                EVENT_CONTEXT_MENU_NEW_FILE,
+                target_dir.clone(),
+            );
+        }
+        "context_new_folder" => {
+            emit_to_window(
+                &app,
+                &label,
+                EVENT_CONTEXT_MENU_NEW_FOLDER,
+                target_dir.clone(),
+            );
+        }
+        "context_rename" => {
+            emit_to_window(&app, &label, EVENT_CONTEXT_MENU_RENAME, path.clone());
+        }
+        "context_duplicate" => {
+            emit_to_window(&app, &label, EVENT_CONTEXT_MENU_DUPLICATE, path.clone());
+        }
         "context_delete" => {
             emit_to_window(&app, &label, EVENT_CONTEXT_MENU_DELETE, path.clone());
         }
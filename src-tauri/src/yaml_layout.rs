@@ -0,0 +1,325 @@
+/// Indentation step used for every nesting level.
+const INDENT: usize = 2;
+
+/// A literal (`|`) or folded (`>`) block scalar whose lines are being
+/// copied. Lines deeper than `parent_column` belong to it and keep their
+/// indentation relative to the first content line.
+struct BlockScalar {
+    parent_column: usize,
+    parent_indent: usize,
+    base: Option<usize>,
+}
+
+fn leading_spaces(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+/// The value after a `key:` or `-` marker opens a block scalar. Explicit
+/// indentation indicators return `Err` since re-indenting would change
+/// their meaning.
+fn opens_block_scalar(value: &str) -> Result<bool, ()> {
+    let value = match value.find(" #") {
+        Some(comment) => &value[..comment],
+        None => value,
+    }
+    .trim();
+    let Some(rest) = value.strip_prefix(['|', '>']) else {
+        return Ok(false);
+    };
+    if rest.chars().any(|c| c.is_ascii_digit()) {
+        return Err(());
+    }
+    Ok(rest.chars().all(|c| c == '+' || c == '-'))
+}
+
+/// Split a plain `key: value` line, returning the key and the value with
+/// the spacing after the colon removed.
+fn split_plain_key(text: &str) -> Option<(&str, &str)> {
+    let colon = text.find(':')?;
+    let key = &text[..colon];
+    let after = &text[colon + 1..];
+    let plain = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/'));
+    if !plain || !(after.is_empty() || after.starts_with(' ')) {
+        return None;
+    }
+    Some((key, after.trim_start_matches(' ')))
+}
+
+/// Re-indent block-style YAML to two spaces per nesting level, collapse
+/// the spacing after `-` markers and plain `key:` colons, and strip
+/// trailing whitespace. Comments, quoting, flow collections and block
+/// scalar content are kept as written. Returns `None` for layouts this
+/// cannot handle safely, such as tab indentation.
+pub(crate) fn normalize_yaml_layout(content: &str) -> Option<String> {
+    // (original column, new column) for each open nesting level.
+    let mut levels: Vec<(usize, usize)> = Vec::new();
+    let mut block: Option<BlockScalar> = None;
+    let mut out: Vec<String> = Vec::new();
+
+    for raw_line in content.split('\n') {
+        let line = raw_line.strip_suffix('\r').unwrap_or(raw_line);
+        let indent = leading_spaces(line);
+        let body = line[indent..].trim_end();
+
+        if let Some(scalar) = block.as_mut() {
+            if body.is_empty() {
+                out.push(String::new());
+                continue;
+            }
+            if indent > scalar.parent_column {
+                let base = *scalar.base.get_or_insert(indent);
+                if indent < base {
+                    return None;
+                }
+                let column = scalar.parent_indent + INDENT + (indent - base);
+                out.push(format!("{}{}", " ".repeat(column), &line[indent..]));
+                continue;
+            }
+            block = None;
+        }
+
+        if body.is_empty() {
+            out.push(String::new());
+            continue;
+        }
+        if body.starts_with('\t') {
+            return None;
+        }
+        if indent == 0 && (body == "---" || body.starts_with("--- ") || body == "...") {
+            levels.clear();
+            out.push(body.to_string());
+            continue;
+        }
+        if body.starts_with('#') {
+            let column = levels
+                .iter()
+                .rev()
+                .find(|(original, _)| *original <= indent)
+                .map(|&(original, new)| {
+                    if original == indent {
+                        new
+                    } else {
+                        new + INDENT
+                    }
+                })
+                .unwrap_or(0);
+            out.push(format!("{}{body}", " ".repeat(column)));
+            continue;
+        }
+
+        while levels
+            .last()
+            .is_some_and(|&(original, _)| original > indent)
+        {
+            levels.pop();
+        }
+        let mut column = match levels.last() {
+            Some(&(original, new)) if original == indent => new,
+            Some(&(_, new)) => new + INDENT,
+            None => 0,
+        };
+        if levels
+            .last()
+            .is_none_or(|&(original, _)| original != indent)
+        {
+            levels.push((indent, column));
+        }
+
+        let mut text = " ".repeat(column);
+        let mut rest = body;
+        let mut original_column = indent;
+        // The node a block scalar on this line would belong to.
+        let mut parent = (indent, column);
+        while let Some(after) = rest
+            .strip_prefix('-')
+            .filter(|after| after.is_empty() || after.starts_with(' '))
+        {
+            let dash_column = original_column;
+            let trimmed = after.trim_start_matches(' ');
+            original_column += 1 + (after.len() - trimmed.len());
+            rest = trimmed;
+            if rest.is_empty() {
+                text.push('-');
+                break;
+            }
+            text.push_str("- ");
+            parent = (dash_column, column);
+            column += INDENT;
+            levels.push((original_column, column));
+        }
+
+        let value = match split_plain_key(rest) {
+            Some((key, value)) => {
+                parent = (original_column, column);
+                text.push_str(key);
+                text.push(':');
+                if !value.is_empty() {
+                    text.push(' ');
+                    text.push_str(value);
+                }
+                value
+            }
+            None => {
+                text.push_str(rest);
+                rest
+            }
+        };
+        if opens_block_scalar(value).ok()? {
+            block = Some(BlockScalar {
+                parent_column: parent.0,
+                parent_indent: parent.1,
+                base: None,
+            });
+        }
+        out.push(text);
+    }
+
+    Some(out.join("\n"))
+}
+
+/// A node that starts at `column` on `line`; `text` runs to the end of the
+/// line. `- ` markers produce one entry for the item and another for its
+/// content.
+struct LineEntry<'a> {
+    line: usize,
+    column: usize,
+    text: &'a str,
+}
+
+fn line_entries(content: &str) -> Vec<LineEntry<'_>> {
+    let mut entries = Vec::new();
+    for (line, raw_line) in content.lines().enumerate() {
+        let indent = leading_spaces(raw_line);
+        let mut text = raw_line[indent..].trim_end();
+        if text.is_empty() || text.starts_with('#') || text == "---" || text == "..." {
+            continue;
+        }
+        let mut column = indent;
+        loop {
+            entries.push(LineEntry { line, column, text });
+            let Some(after) = text
+                .strip_prefix('-')
+                .filter(|after| after.starts_with(' '))
+            else {
+                break;
+            };
+            let trimmed = after.trim_start_matches(' ');
+            column += text.len() - trimmed.len();
+            text = trimmed;
+        }
+    }
+    entries
+}
+
+fn is_item(entry: &LineEntry) -> bool {
+    entry.text == "-" || entry.text.starts_with("- ")
+}
+
+fn entry_is_key(text: &str, key: &str) -> bool {
+    [
+        format!("{key}:"),
+        format!("\"{key}\":"),
+        format!("'{key}':"),
+    ]
+    .iter()
+    .any(|prefix| {
+        text.strip_prefix(prefix.as_str())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+    })
+}
+
+/// 0-based line each document of a YAML stream starts on. A `---` marker
+/// starts a new document unless only comments and blank lines precede it.
+pub(crate) fn yaml_document_start_lines(content: &str) -> Vec<usize> {
+    let mut starts = vec![0];
+    // Whether the current document has content, and whether `...` ended
+    // it so that the next content starts another one.
+    let mut has_content = false;
+    let mut ended = false;
+    for (line, raw_line) in content.lines().enumerate() {
+        let text = raw_line.trim_end();
+        if text == "---" || text.starts_with("--- ") {
+            if has_content || ended {
+                starts.push(line);
+            }
+            has_content = text != "---";
+            ended = false;
+        } else if text == "..." {
+            ended |= has_content;
+            has_content = false;
+        } else if !text.trim_start().is_empty() && !text.trim_start().starts_with('#') {
+            if ended {
+                starts.push(line);
+                ended = false;
+            }
+            has_content = true;
+        }
+    }
+    starts
+}
+
+/// 1-based line and column of the node at `pointer`, given as unescaped
+/// JSON Pointer tokens, in block-style YAML. Nodes are found by
+/// indentation; when the pointer does not resolve fully, the deepest node
+/// found is used.
+pub(crate) fn yaml_pointer_position(content: &str, pointer: &[String]) -> (usize, usize) {
+    let entries = line_entries(content);
+    let Some(first) = entries.first() else {
+        return (1, 1);
+    };
+    // Entries that belong to the current node, and the column its
+    // children start at.
+    let mut scope = &entries[..];
+    let mut column = first.column;
+    let mut found = None;
+
+    for token in pointer {
+        // A node's children are either all sequence items or all keys; a
+        // sequence sharing its parent key's column is nested under that key.
+        let is_sequence = scope
+            .iter()
+            .find(|entry| entry.column == column)
+            .is_some_and(is_item);
+        let position = if is_sequence {
+            token.parse::<usize>().ok().and_then(|index| {
+                scope
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, entry)| entry.column == column && is_item(entry))
+                    .nth(index)
+                    .map(|(position, _)| position)
+            })
+        } else {
+            scope
+                .iter()
+                .position(|entry| entry.column == column && entry_is_key(entry.text, token))
+        };
+        let Some(position) = position else {
+            break;
+        };
+        let node = &scope[position];
+        found = Some((node.line + 1, node.column + 1));
+
+        // The node's children follow it until a line indented no deeper;
+        // a sequence may sit at the same column as its key.
+        let item = is_item(node);
+        let rest = &scope[position + 1..];
+        let end = rest
+            .iter()
+            .position(|entry| {
+                entry.line != node.line
+                    && (entry.column < node.column
+                        || (entry.column == node.column && (item || !is_item(entry))))
+            })
+            .unwrap_or(rest.len());
+        scope = &rest[..end];
+        match scope.first() {
+            Some(child) => column = child.column,
+            None => break,
+        }
+    }
+    found.unwrap_or((1, 1))
+}
@@ -83,6 +83,7 @@ export function setupControllers({
     updateMenuState: callbacks.updateMenuState,
     invoke,
     crossWindowDrag: crossWindowDragController,
+    movePathToFolder: callbacks.movePathToFolder,
   });
 
   const tabTransferController = createTabTransferController({
@@ -169,6 +170,7 @@ export function setupControllers({
     handleOsOpenFiles: callbacks.handleOsOpenFiles,
     handleProjectFolderChanged: callbacks.handleProjectFolderChanged,
     handleProjectFileChanged: callbacks.handleProjectFileChanged,
+    handleFileAppended: callbacks.handleFileAppended,
     toggleSidebarVisibility: callbacks.toggleSidebarVisibility,
     toggleMarkdownMode: callbacks.toggleMarkdownMode,
     closeActiveFileOrWindow: callbacks.closeActiveFileOrWindow,
@@ -182,13 +184,22 @@ export function setupControllers({
     handleCrossWindowDragLeave: () =>
       crossWindowDragController.handleDragLeave(),
     handleContextMenuDelete: callbacks.handleContextMenuDelete,
+    handleContextMenuNewFile: callbacks.handleContextMenuNewFile,
+    handleContextMenuNewFolder: callbacks.handleContextMenuNewFolder,
+    handleContextMenuRename: callbacks.handleContextMenuRename,
+    handleContextMenuDuplicate: callbacks.handleContextMenuDuplicate,
+    handlePathRenamed: callbacks.handlePathRenamed,
+    undoFileOperation: callbacks.undoFileOperation,
     handleTabContextMenuClose: callbacks.handleTabContextMenuClose,
     handleTabContextMenuCloseOthers: callbacks.handleTabContextMenuCloseOthers,
     openRecentFolder: callbacks.openFolder,
     toggleStatusBar: callbacks.toggleStatusBar,
     toggleCollapseAllFolders: callbacks.toggleCollapseAllFolders,
     openFind: callbacks.openFind,
+    reopenWithEncoding: callbacks.reopenWithEncoding,
     formatActiveFile: callbacks.formatActiveFile,
+    toggleTableView: callbacks.toggleTableView,
+    copyTableAs: callbacks.copyTableAs,
     navigateBack: callbacks.navigateBack,
     navigateForward: callbacks.navigateForward,
     bindWindowDragDropEvents: () =>
@@ -8,6 +8,7 @@ export function createAppEventsController({
   handleOsOpenFiles,
   handleProjectFolderChanged,
   handleProjectFileChanged,
+  handleFileAppended,
   toggleSidebarVisibility,
   toggleMarkdownMode,
   closeActiveFileOrWindow,
@@ -20,13 +21,22 @@ export function createAppEventsController({
   handleCrossWindowDragEnter,
   handleCrossWindowDragLeave,
   handleContextMenuDelete,
+  handleContextMenuNewFile,
+  handleContextMenuNewFolder,
+  handleContextMenuRename,
+  handleContextMenuDuplicate,
+  handlePathRenamed,
+  undoFileOperation,
   handleTabContextMenuClose,
   handleTabContextMenuCloseOthers,
   openRecentFolder,
   toggleStatusBar,
   toggleCollapseAllFolders,
   openFind,
+  reopenWithEncoding,
   formatActiveFile,
+  toggleTableView,
+  copyTableAs,
   navigateBack,
   navigateForward,
 }) {
@@ -49,6 +59,9 @@ export function createAppEventsController({
       listen(`${events.projectFileChanged}/${label}`, async (event) => {
         await handleProjectFileChanged(event.payload);
       }),
+      listen(`${events.fileAppended}/${label}`, async (event) => {
+        await handleFileAppended(event.payload);
+      }),
       listen(`${events.toggleSidebar}/${label}`, () => {
         toggleSidebarVisibility();
       }),
@@ -82,6 +95,24 @@ export function createAppEventsController({
       listen(`${events.contextMenuDelete}/${label}`, async (event) => {
         await handleContextMenuDelete(event.payload);
       }),
+      listen(`${events.contextMenuNewFile}/${label}`, async (event) => {
+        await handleContextMenuNewFile(event.payload);
+      }),
+      listen(`${events.contextMenuNewFolder}/${label}`, async (event) => {
+        await handleContextMenuNewFolder(event.payload);
+      }),
+      listen(`${events.contextMenuRename}/${label}`, async (event) => {
+        await handleContextMenuRename(event.payload);
+      }),
+      listen(`${events.contextMenuDuplicate}/${label}`, async (event) => {
+        await handleContextMenuDuplicate(event.payload);
+      }),
+      listen(`${events.pathRenamed}/${label}`, (event) => {
+        handlePathRenamed(event.payload);
+      }),
+      listen(`${events.undoFileOperation}/${label}`, async () => {
+        await undoFileOperation();
+      }),
       listen(`${events.tabContextMenuClose}/${label}`, async (event) => {
         await handleTabContextMenuClose(event.payload);
       }),
@@ -103,8 +134,17 @@ export function createAppEventsController({
       listen(`${events.find}/${label}`, () => {
         openFind();
       }),
-      listen(`${events.formatFile}/${label}`, () => {
-        formatActiveFile();
+      listen(`${events.reopenWithEncoding}/${label}`, async () => {
+        await reopenWithEncoding();
+      }),
+      listen(`${events.formatFile}/${label}`, (event) => {
+        formatActiveFile(event?.payload ?? undefined);
+      }),
+      listen(`${events.toggleTableView}/${label}`, () => {
+        toggleTableView();
+      }),
+      listen(`${events.copyTable}/${label}`, (event) => {
+        copyTableAs(event?.payload);
       }),
       listen(`${events.mouseNavBack}/${label}`, () => {
         navigateBack();
@@ -1,3 +1,5 @@
+import { recordFileVersion } from "../files/versions.js";
+
 export function collectWatchedProjectFilePaths(state) {
   const paths = new Set();
 
@@ -14,6 +16,35 @@ export function collectWatchedProjectFilePaths(state) {
   return [...paths].sort();
 }
 
+// Log tabs are followed: appended output arrives as `file-appended` events
+// instead of the whole file being re-read on every change. Large files are
+// paged from disk and are not followed.
+export function collectFollowedLogPaths(state) {
+  const tabs =
+    Array.isArray(state.openFiles) && state.openFiles.length > 0
+      ? state.openFiles
+      : [
+          {
+            path: state.activePath,
+            language: state.activeLanguage,
+            largeFile: state.activeLargeFile,
+          },
+        ];
+  const paths = new Set();
+  for (const tab of tabs) {
+    if (
+      tab &&
+      typeof tab.path === "string" &&
+      tab.path &&
+      tab.language === "log" &&
+      !tab.largeFile
+    ) {
+      paths.add(tab.path);
+    }
+  }
+  return [...paths].sort();
+}
+
 export function buildWatchedProjectFileSignature(paths) {
   return paths.join("\n");
 }
@@ -35,6 +66,8 @@ export function createExternalFileWatchController({
     pendingSync: false,
     recentlySavedAtByPath: new Map(),
     handlingChangedPaths: new Set(),
+    // Tab path -> path reported by `follow_file`, which is canonical.
+    followedPaths: new Map(),
   };
 
   function findOpenTabIndexByPath(path) {
@@ -91,9 +124,16 @@ export function createExternalFileWatchController({
         if (!tab) {
           return;
         }
+        recordFileVersion(payload.path, payload.version);
         tab.content = payload.content;
         tab.kind = payload.kind;
+        tab.language = payload.language ?? null;
         tab.writable = payload.writable;
+        tab.largeFile = payload.largeFile ?? null;
+        tab.encoding = payload.encoding ?? null;
+        tab.lineEnding = payload.lineEnding ?? null;
+        tab.hasBom = payload.hasBom ?? null;
+        tab.largeFileStartLine = 1;
         tab.isDirty = false;
         if (payload.kind !== "markdown") {
           tab.markdownViewMode = "edit";
@@ -122,15 +162,58 @@ export function createExternalFileWatchController({
     }
   }
 
+  async function syncFollowedLogFiles() {
+    const wanted = new Set(collectFollowedLogPaths(state));
+    for (const [path, followedPath] of watchState.followedPaths) {
+      if (wanted.has(path)) {
+        continue;
+      }
+      watchState.followedPaths.delete(path);
+      try {
+        await invoke("stop_following_file", { path: followedPath });
+      } catch {
+        // no-op: the follow ends with the window anyway.
+      }
+    }
+    for (const path of wanted) {
+      if (watchState.followedPaths.has(path)) {
+        continue;
+      }
+      watchState.followedPaths.set(path, path);
+      try {
+        const started = await invoke("follow_file", { path, fromEnd: true });
+        if (watchState.followedPaths.has(path) && started?.path) {
+          watchState.followedPaths.set(path, started.path);
+        }
+      } catch (error) {
+        watchState.followedPaths.delete(path);
+        setStatus(String(error), true);
+      }
+    }
+  }
+
+  function findFollowedTabPath(followedPath) {
+    for (const [path, reported] of watchState.followedPaths) {
+      if (path === followedPath || reported === followedPath) {
+        return path;
+      }
+    }
+    return null;
+  }
+
   function syncWatchedProjectFiles() {
     const paths = collectWatchedProjectFilePaths(state);
-    const signature = buildWatchedProjectFileSignature(paths);
+    const signature = [
+      buildWatchedProjectFileSignature(paths),
+      ...collectFollowedLogPaths(state),
+    ].join("\n");
 
     if (watchState.signature === signature && !watchState.pendingSync) {
       return;
     }
 
     watchState.signature = signature;
+    syncFollowedLogFiles();
 
     if (watchState.syncPromise) {
       watchState.pendingSync = true;
@@ -169,7 +252,14 @@ export function createExternalFileWatchController({
     if (typeof path !== "string" || !path) {
       return;
     }
+    // Followed logs are updated by `handleFileAppended`.
+    if (watchState.followedPaths.has(path)) {
+      return;
+    }
+    await reloadChangedPath(path);
+  }
 
+  async function reloadChangedPath(path) {
     const savedAt = watchState.recentlySavedAtByPath.get(path);
     if (savedAt && Date.now() - savedAt < 1200) {
       return;
@@ -187,7 +277,45 @@ export function createExternalFileWatchController({
     }
   }
 
+  // Append output written to a followed log. A truncated or rotated log,
+  // or one with unsaved edits, is reloaded as any outside change would be.
+  async function handleFileAppended(payload) {
+    const path = findFollowedTabPath(payload?.path);
+    if (!path) {
+      return;
+    }
+    const hasTabs = hasTabSession();
+    const isDirty = hasTabs
+      ? isOpenTabDirtyForPath(path)
+      : isActiveFileDirtyForPath(path);
+    if (payload.truncated || payload.rotated || isDirty) {
+      await reloadChangedPath(path);
+      return;
+    }
+    if (!payload.text) {
+      return;
+    }
+
+    // The appended content is not hashed, so the next save skips the
+    // on-disk version check instead of reporting a false conflict.
+    recordFileVersion(path, null);
+    if (hasTabs) {
+      const tab = state.openFiles[findOpenTabIndexByPath(path)];
+      if (!tab) {
+        return;
+      }
+      tab.content += payload.text;
+      tab.savedContent = tab.content;
+    }
+    if (state.activePath === path) {
+      state.content += payload.text;
+      state.savedContent = state.content;
+      render();
+    }
+  }
+
   return {
+    handleFileAppended,
     handleProjectFileChanged,
     onFileSaved,
     syncWatchedProjectFiles,
@@ -22,6 +22,11 @@ export function applySavedShowHiddenFiles(state) {
   state.showHiddenFiles = saved === null ? true : saved === "true";
 }
 
+export function applySavedShowIgnoredFiles(state) {
+  state.showIgnoredFiles =
+    localStorage.getItem("teex-show-ignored-files") === "true";
+}
+
 export function applySavedModifiedOnly(state) {
   state.filterModifiedOnly =
     localStorage.getItem("teex-filter-modified-only") === "true";
@@ -48,6 +53,18 @@ export function toggleHiddenFiles(state, invoke, refreshEntries) {
   refreshEntries();
 }
 
+export function toggleIgnoredFiles(state, invoke, refreshEntries) {
+  state.showIgnoredFiles = !state.showIgnoredFiles;
+  localStorage.setItem(
+    "teex-show-ignored-files",
+    state.showIgnoredFiles ? "true" : "false",
+  );
+  invoke("set_show_ignored_files_checked", {
+    checked: state.showIgnoredFiles,
+  }).catch(() => {});
+  refreshEntries();
+}
+
 export function toggleModifiedOnly(
   state,
   invoke,
@@ -81,6 +98,9 @@ export function syncSavedPreferencesToBackend(state, invoke) {
   invoke("set_show_hidden_files_checked", {
     checked: state.showHiddenFiles,
   }).catch(() => {});
+  invoke("set_show_ignored_files_checked", {
+    checked: state.showIgnoredFiles,
+  }).catch(() => {});
   invoke("set_show_modified_only_checked", {
     checked: state.filterModifiedOnly,
   }).catch(() => {});
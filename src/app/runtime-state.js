@@ -4,6 +4,7 @@ export const EVENTS = {
   osOpenPaths: "teex://os-open-paths",
   projectFolderChanged: "teex://project-folder-changed",
   projectFileChanged: "teex://project-file-changed",
+  fileAppended: "teex://file-appended",
   toggleSidebar: "teex://toggle-sidebar",
   toggleMarkdownMode: "teex://toggle-markdown-mode",
   closeActiveFile: "teex://close-active-file",
@@ -15,17 +16,28 @@ export const EVENTS = {
   crossWindowDragEnter: "teex://cross-window-drag-enter",
   crossWindowDragLeave: "teex://cross-window-drag-leave",
   contextMenuDelete: "teex://context-menu-delete",
+  contextMenuNewFile: "teex://context-menu-new-file",
+  contextMenuNewFolder: "teex://context-menu-new-folder",
+  contextMenuRename: "teex://context-menu-rename",
+  contextMenuDuplicate: "teex://context-menu-duplicate",
+  pathRenamed: "teex://path-renamed",
+  undoFileOperation: "teex://undo-file-operation",
   tabContextMenuClose: "teex://tab-context-menu-close",
   tabContextMenuCloseOthers: "teex://tab-context-menu-close-others",
   openRecentFile: "teex://open-recent-file",
   openRecentFolder: "teex://open-recent-folder",
   toggleStatusBar: "teex://toggle-status-bar",
   toggleHiddenFiles: "teex://toggle-hidden-files",
+  toggleIgnoredFiles: "teex://toggle-ignored-files",
   toggleModifiedOnly: "teex://toggle-modified-only",
   toggleCollapseAllFolders: "teex://toggle-collapse-all-folders",
   toggleUnifiedDiff: "teex://toggle-unified-diff",
   find: "teex://find",
+  reopenWithEncoding: "teex://reopen-with-encoding",
   formatFile: "teex://format-file",
+  toggleTableView: "teex://toggle-table-view",
+  copyTable: "teex://copy-table",
+  taskProgress: "teex://task-progress",
   mouseNavBack: "teex://mouse-nav-back",
   mouseNavForward: "teex://mouse-nav-forward",
 };
@@ -45,9 +57,17 @@ export function createRuntimeState() {
       activeTabIndex: 0,
       activePath: null,
       activeKind: null,
+      activeLanguage: null,
       content: "",
       savedContent: "",
+      activeWritable: true,
+      activeLargeFile: null,
+      activeEncoding: null,
+      activeLineEnding: null,
+      activeHasBom: null,
+      largeFileStartLine: 1,
       markdownViewMode: "preview",
+      tableView: false,
       activeEditorScrollTop: 0,
       activePreviewScrollTop: 0,
       activeMarkdownScrollAnchor: null,
@@ -61,7 +81,9 @@ export function createRuntimeState() {
       windowRepresentedPath: null,
       windowLabel: "",
       statusBarVisible: false,
+      taskProgress: null,
       showHiddenFiles: true,
+      showIgnoredFiles: false,
       filterModifiedOnly: false,
       gitStatusMap: {},
       folderIconUrl: null,
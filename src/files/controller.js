@@ -59,7 +59,11 @@ export function createFileController({
     }
 
     try {
-      await invoke("watch_project_folder", { root });
+      await invoke("watch_project_folder", {
+        root,
+        showHidden: state.showHiddenFiles,
+        showIgnored: state.showIgnoredFiles,
+      });
     } catch {
       // Watch setup is best-effort; keep file operations working.
     }
@@ -81,6 +85,7 @@ export function createFileController({
         const entries = await invoke("list_project_entries", {
           root: state.rootPath,
           showHidden: state.showHiddenFiles,
+          showIgnored: state.showIgnoredFiles,
         });
         const nextGitStatus = await fetchGitStatus(invoke, state.rootPath);
         const entriesChanged = didProjectEntriesChange(state.entries, entries);
@@ -187,6 +192,7 @@ export function createFileController({
       const entries = await invoke("list_project_entries", {
         root: path,
         showHidden: state.showHiddenFiles,
+        showIgnored: state.showIgnoredFiles,
       });
       state.mode = "folder";
       state.rootPath = path;
@@ -262,6 +268,7 @@ export function createFileController({
     openEntry,
     openFolderEntryInTabs,
     refreshOpenFolderEntries,
+    watchProjectFolder,
     clearProjectFolderWatch,
   };
 }
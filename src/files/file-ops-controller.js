@@ -0,0 +1,184 @@
+import { renameFileVersions } from "./versions.js";
+
+function renamedPath(path, from, to) {
+  if (typeof path !== "string") {
+    return path;
+  }
+  if (path === from) {
+    return to;
+  }
+  if (path.startsWith(`${from}/`)) {
+    return `${to}${path.slice(from.length)}`;
+  }
+  return path;
+}
+
+// Point tabs, the active file, history and recorded file versions at a
+// moved file or folder. Returns true when anything in `state` changed.
+export function applyPathRename(state, from, to) {
+  let changed = false;
+
+  for (const tab of state.openFiles) {
+    const next = renamedPath(tab.path, from, to);
+    if (next !== tab.path) {
+      tab.path = next;
+      changed = true;
+    }
+  }
+
+  const nextActive = renamedPath(state.activePath, from, to);
+  if (nextActive !== state.activePath) {
+    state.activePath = nextActive;
+    changed = true;
+  }
+
+  state.navHistory = state.navHistory.map((p) => renamedPath(p, from, to));
+  renameFileVersions((path) => renamedPath(path, from, to));
+
+  for (const [key, value] of [...state.fileScrollMemory]) {
+    const next = renamedPath(key, from, to);
+    if (next !== key) {
+      state.fileScrollMemory.delete(key);
+      state.fileScrollMemory.set(next, value);
+    }
+  }
+
+  return changed;
+}
+
+export function createFileOpsController({
+  state,
+  invoke,
+  baseName,
+  promptForName,
+  openFolderEntryInTabs,
+  markSidebarTreeDirty,
+  render,
+  updateMenuState,
+  setStatus,
+}) {
+  async function run(action, message) {
+    try {
+      return await action();
+    } catch (err) {
+      setStatus(`${message}: ${err}`, true);
+      return null;
+    }
+  }
+
+  async function createFile(dir) {
+    if (!state.rootPath) {
+      return;
+    }
+    const name = await promptForName({
+      title: "New File",
+      confirmLabel: "Create",
+    });
+    if (!name) {
+      return;
+    }
+    const path = await run(
+      () => invoke("create_file", { root: state.rootPath, dir, name }),
+      "Unable to create file",
+    );
+    if (path) {
+      await openFolderEntryInTabs(path);
+    }
+  }
+
+  async function createFolder(dir) {
+    if (!state.rootPath) {
+      return;
+    }
+    const name = await promptForName({
+      title: "New Folder",
+      confirmLabel: "Create",
+    });
+    if (!name) {
+      return;
+    }
+    await run(
+      () => invoke("create_folder", { root: state.rootPath, dir, name }),
+      "Unable to create folder",
+    );
+  }
+
+  async function renamePath(path) {
+    if (!state.rootPath) {
+      return;
+    }
+    const current = baseName(path);
+    const newName = await promptForName({
+      title: `Rename "${current}"`,
+      initialValue: current,
+      confirmLabel: "Rename",
+    });
+    if (!newName || newName === current) {
+      return;
+    }
+    await run(
+      () => invoke("rename_path", { root: state.rootPath, path, newName }),
+      "Unable to rename",
+    );
+  }
+
+  async function movePathToFolder(path, targetDir) {
+    if (!state.rootPath) {
+      return;
+    }
+    await run(
+      () => invoke("move_path", { root: state.rootPath, path, targetDir }),
+      "Unable to move",
+    );
+  }
+
+  async function duplicatePath(path) {
+    if (!state.rootPath) {
+      return;
+    }
+    await run(
+      () => invoke("duplicate_path", { root: state.rootPath, path }),
+      "Unable to duplicate",
+    );
+  }
+
+  async function undoLastOperation() {
+    let undone;
+    try {
+      undone = await invoke("undo_file_operation");
+    } catch (err) {
+      setStatus(`Unable to undo: ${err}`, true);
+      return;
+    }
+    if (!undone) {
+      setStatus("Nothing to undo");
+      return;
+    }
+    const name = baseName(undone.path);
+    setStatus(
+      undone.kind === "restore"
+        ? `Restored "${name}" from the trash`
+        : `Moved "${name}" back`,
+    );
+  }
+
+  // Sent to every window by the backend after a rename or move.
+  function handlePathRenamed({ from, to }) {
+    if (!applyPathRename(state, from, to)) {
+      return;
+    }
+    markSidebarTreeDirty();
+    render();
+    updateMenuState();
+  }
+
+  return {
+    createFile,
+    createFolder,
+    renamePath,
+    movePathToFolder,
+    duplicatePath,
+    undoLastOperation,
+    handlePathRenamed,
+  };
+}
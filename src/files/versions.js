@@ -0,0 +1,48 @@
+// Last known on-disk version token per file path, as returned by
+// `read_text_file` and `write_text_file`. Saves pass it back so the backend
+// can refuse to overwrite changes made outside Teex.
+const versionsByPath = new Map();
+
+export function recordFileVersion(path, version) {
+  if (typeof path !== "string" || !path) {
+    return;
+  }
+  if (typeof version === "string" && version) {
+    versionsByPath.set(path, version);
+  } else {
+    versionsByPath.delete(path);
+  }
+}
+
+export function fileVersionFor(path) {
+  return versionsByPath.get(path) ?? null;
+}
+
+// Re-key versions after a rename or move. `rename` maps an old path to its
+// new one and returns every other path unchanged.
+export function renameFileVersions(rename) {
+  const moved = [];
+  for (const [path, version] of versionsByPath) {
+    const next = rename(path);
+    if (next !== path) {
+      moved.push([path, next, version]);
+    }
+  }
+  for (const [path] of moved) {
+    versionsByPath.delete(path);
+  }
+  for (const [, next, version] of moved) {
+    versionsByPath.set(next, version);
+  }
+}
+
+export function describeWriteError(error) {
+  if (error && typeof error === "object" && typeof error.message === "string") {
+    return error.message;
+  }
+  return String(error);
+}
+
+export function isWriteConflict(error) {
+  return Boolean(error && typeof error === "object" && error.kind === "conflict");
+}
@@ -3,12 +3,14 @@ import { createExternalFileWatchController } from "./app/external-file-watch-con
 import {
   applySavedModifiedOnly,
   applySavedShowHiddenFiles,
+  applySavedShowIgnoredFiles,
   applySavedSidebarWidth,
   applySavedStatusBar,
   applySavedTheme,
   listenForThemeEvents,
   syncSavedPreferencesToBackend,
   toggleHiddenFiles as toggleHiddenFilesPref,
+  toggleIgnoredFiles as toggleIgnoredFilesPref,
   toggleModifiedOnly as toggleModifiedOnlyPref,
   toggleStatusBar as toggleStatusBarPref,
 } from "./app/preferences.js";
@@ -20,6 +22,7 @@ import {
   saveWindowSession,
 } from "./app/session-persistence.js";
 import { createSessionRestoreController } from "./app/session-restore.js";
+import { createFileOpsController } from "./files/file-ops-controller.js";
 import { createFindController } from "./search/find-controller.js";
 import { buildCollapsedFoldersFromExpanded } from "./sidebar/tree.js";
 import { recordNavigation } from "./tabs/navigation.js";
@@ -38,13 +41,18 @@ import {
   bindElements as bindElementsImported,
   bindUiEvents as bindUiEventsImported,
 } from "./ui/bindings-controller.js";
+import { createDiagnosticsController } from "./ui/diagnostics-controller.js";
 import { createDiffController } from "./ui/diff/controller.js";
 import { createDiffMapController } from "./ui/diff/map-controller.js";
 import { createUnifiedDiffController } from "./ui/diff/unified-controller.js";
 import { createCodeMirrorController } from "./ui/editor/codemirror-controller.js";
 import { createFormatController } from "./ui/format-controller.js";
+import { createLargeFileController } from "./ui/large-file-controller.js";
+import { promptForName } from "./ui/name-prompt-dialog.js";
 import { confirmReloadExternalChange } from "./ui/native-dialog.js";
 import { createScrollSyncController } from "./ui/scroll/sync.js";
+import { createTableController, isTableFile } from "./ui/table-controller.js";
+import { createTaskProgressController } from "./ui/task-progress-controller.js";
 import { baseName } from "./utils/app-utils.js";
 
 const { invoke } = window.__TAURI__.core;
@@ -69,19 +77,27 @@ let openPathsController;
 let appEventsController;
 let scrollSyncController;
 let externalFileWatchController;
+let fileOpsController;
 let sessionRestoreController;
 let findController;
 let formatController;
 let diffController;
+let diagnosticsController;
 let diffMapController;
 let unifiedDiffController;
+let tableController;
+let taskProgressController;
+let largeFileController;
 let sessionSaveEnabled = false;
 
 const codeJarController = createCodeMirrorController({
   el,
   state,
   onContentChange: () => renderChrome(),
-  onScroll: () => scrollSyncController?.onEditorScroll(),
+  onScroll: () => {
+    scrollSyncController?.onEditorScroll();
+    largeFileController?.onEditorScroll();
+  },
 });
 
 ({
@@ -134,6 +150,7 @@ const codeJarController = createCodeMirrorController({
     handleOsOpenFiles,
     handleProjectFolderChanged,
     handleProjectFileChanged,
+    handleFileAppended,
     toggleSidebarVisibility,
     toggleMarkdownMode,
     toggleStatusBar,
@@ -150,6 +167,16 @@ const codeJarController = createCodeMirrorController({
       tabController.deleteAndCloseTabs(path, {
         onAllClosed: () => scrollSyncController?.afterContextCleared(),
       }),
+    handleContextMenuNewFile: (dir) => fileOpsController.createFile(dir),
+    handleContextMenuNewFolder: (dir) => fileOpsController.createFolder(dir),
+    handleContextMenuRename: (path) => fileOpsController.renamePath(path),
+    handleContextMenuDuplicate: (path) =>
+      fileOpsController.duplicatePath(path),
+    handlePathRenamed: (payload) =>
+      fileOpsController.handlePathRenamed(payload),
+    undoFileOperation: () => fileOpsController.undoLastOperation(),
+    movePathToFolder: (path, targetDir) =>
+      fileOpsController.movePathToFolder(path, targetDir),
     handleTabContextMenuClose: closeTab,
     handleTabContextMenuCloseOthers: (index) =>
       tabController.closeOtherTabs(index),
@@ -158,7 +185,10 @@ const codeJarController = createCodeMirrorController({
     onAfterToggleMarkdownMode,
     onSavedStateChanged: renderChrome,
     openFind,
+    reopenWithEncoding,
     formatActiveFile,
+    toggleTableView,
+    copyTableAs,
     navigateBack,
     navigateForward,
   },
@@ -193,11 +223,24 @@ externalFileWatchController = createExternalFileWatchController({
   confirmReloadExternalChange,
 });
 
+fileOpsController = createFileOpsController({
+  state,
+  invoke,
+  baseName,
+  promptForName,
+  openFolderEntryInTabs,
+  markSidebarTreeDirty,
+  render,
+  updateMenuState,
+  setStatus,
+});
+
 window.addEventListener("DOMContentLoaded", async () => {
   applySavedTheme();
   applySavedSidebarWidth(state);
   applySavedStatusBar(state);
   applySavedShowHiddenFiles(state);
+  applySavedShowIgnoredFiles(state);
   applySavedModifiedOnly(state);
   bindElementsImported(el);
   bindUiEvents();
@@ -211,10 +254,18 @@ window.addEventListener("DOMContentLoaded", async () => {
       if (url) state.folderIconUrl = url;
     })
     .catch(() => {});
+  largeFileController = createLargeFileController({
+    state,
+    invoke,
+    render,
+    setStatus,
+    codeEditorController: codeJarController,
+  });
   findController = createFindController({
     state,
     el,
     codeEditorController: codeJarController,
+    largeFileController,
   });
   formatController = createFormatController({
     state,
@@ -232,7 +283,23 @@ window.addEventListener("DOMContentLoaded", async () => {
     codeEditorController: codeJarController,
     diffMapController,
   });
+  diagnosticsController = createDiagnosticsController({
+    state,
+    invoke,
+    codeEditorController: codeJarController,
+  });
   unifiedDiffController = createUnifiedDiffController({ state, el, invoke });
+  tableController = createTableController({
+    state,
+    el,
+    invoke,
+    onCellSaved: reloadAfterTableEdit,
+  });
+  taskProgressController = createTaskProgressController({
+    state,
+    invoke,
+    onChange: () => renderChrome(),
+  });
   scrollSyncController = createScrollSyncController({ state, el });
   await openPathsController.bootstrap();
   await appEventsController.bindAppEvents();
@@ -240,8 +307,12 @@ window.addEventListener("DOMContentLoaded", async () => {
   openPathsController.startPendingOpenPathPoller();
 
   listen("teex://toggle-hidden-files", () => toggleHiddenFiles());
+  listen("teex://toggle-ignored-files", () => toggleIgnoredFiles());
   listen("teex://toggle-modified-only", () => toggleModifiedOnly());
   listen("teex://toggle-unified-diff", () => toggleUnifiedDiff());
+  listen(EVENTS.taskProgress, (event) =>
+    taskProgressController.handleProgress(event.payload),
+  );
 });
 
 function bindUiEvents() {
@@ -258,6 +329,7 @@ function bindUiEvents() {
       sidebarController.toggleCollapseAllFolders(),
     toggleUnifiedDiff,
     saveNow,
+    goToLine,
     hasTabSession,
     switchTab,
     navigateBack,
@@ -267,6 +339,7 @@ function bindUiEvents() {
     onDirtyStateChanged: () => renderChrome(),
     openFind,
     formatActiveFile,
+    openLinkedFile: openSingleFileFromUi,
   });
 }
 
@@ -274,8 +347,86 @@ function openFind() {
   findController?.open();
 }
 
-function formatActiveFile() {
-  formatController?.formatActiveFile();
+async function goToLine() {
+  if (!state.activePath || !codeJarController.isAttached()) {
+    return;
+  }
+  const answer = await promptForName({
+    title: "Go to Line",
+    confirmLabel: "Go",
+  });
+  const line = Number.parseInt(answer ?? "", 10);
+  if (!Number.isFinite(line)) {
+    return;
+  }
+  if (largeFileController.isActive()) {
+    await largeFileController.goToLine(line);
+  } else {
+    codeJarController.scrollToLine(
+      Math.min(Math.max(1, line), codeJarController.getLineCount()),
+    );
+  }
+}
+
+// Decode the active file again with an encoding the user names, e.g. when
+// a legacy file was guessed wrong. Saves then write that encoding back.
+async function reopenWithEncoding() {
+  const path = state.activePath;
+  if (!path || state.activeLargeFile) {
+    return;
+  }
+  if (state.isDirty) {
+    setStatus("Save the file before reopening it with another encoding", true);
+    return;
+  }
+  const encoding = await promptForName({
+    title: "Reopen with Encoding",
+    initialValue: state.activeEncoding ?? "UTF-8",
+    confirmLabel: "Reopen",
+  });
+  if (!encoding || state.activePath !== path) {
+    return;
+  }
+  try {
+    const payload = await invoke("reopen_with_encoding", { path, encoding });
+    applyFilePayload(payload, { preserveMarkdownMode: true });
+    flushStateToActiveTab();
+    const tab = hasTabSession() ? state.openFiles[state.activeTabIndex] : null;
+    if (tab) {
+      tab.writable = payload.writable;
+      tab.encoding = payload.encoding ?? null;
+      tab.lineEnding = payload.lineEnding ?? null;
+      tab.hasBom = payload.hasBom ?? null;
+    }
+    render();
+    setStatus(`Reopened as ${payload.encoding}`);
+  } catch (error) {
+    setStatus(String(error), true);
+  }
+}
+
+function formatActiveFile(style) {
+  formatController?.formatActiveFile(style);
+}
+
+function copyTableAs(target) {
+  formatController?.copyTableAs(target);
+}
+
+function toggleTableView() {
+  if (!isTableFile(state.activePath)) {
+    return;
+  }
+  state.tableView = !state.tableView;
+  render();
+}
+
+// Table edits are written straight to disk; pull them into the editor
+// buffer the same way an outside change would be.
+async function reloadAfterTableEdit(path) {
+  await externalFileWatchController.handleProjectFileChanged(path);
+  diffController?.invalidate(path);
+  diffController?.scheduleRefresh();
 }
 
 function markSidebarTreeDirty() {
@@ -294,6 +445,10 @@ async function handleProjectFileChanged(path) {
   await externalFileWatchController.handleProjectFileChanged(path);
 }
 
+async function handleFileAppended(payload) {
+  await externalFileWatchController.handleFileAppended(payload);
+}
+
 async function handleDroppedPaths(paths) {
   await openPathsController.handleDroppedPaths(paths);
 }
@@ -463,9 +618,17 @@ function toggleStatusBar() {
 }
 
 function toggleHiddenFiles() {
-  toggleHiddenFilesPref(state, invoke, () =>
-    fileController.refreshOpenFolderEntries(),
-  );
+  toggleHiddenFilesPref(state, invoke, async () => {
+    await fileController.watchProjectFolder(state.rootPath);
+    await fileController.refreshOpenFolderEntries();
+  });
+}
+
+function toggleIgnoredFiles() {
+  toggleIgnoredFilesPref(state, invoke, async () => {
+    await fileController.watchProjectFolder(state.rootPath);
+    await fileController.refreshOpenFolderEntries();
+  });
 }
 
 function toggleModifiedOnly() {
@@ -520,10 +683,14 @@ function render(options = {}) {
   externalFileWatchController.syncWatchedProjectFiles();
   if (state.activeKind === "diff") {
     diffController?.clear();
+    diagnosticsController?.clear();
     unifiedDiffController?.scheduleRefresh();
   } else {
     diffController?.refreshNow();
+    diagnosticsController?.refresh();
+    tableController?.refresh();
   }
+  taskProgressController?.refresh();
   updateUnifiedDiffButton();
   if (sessionSaveEnabled) {
     flushStateToActiveTab();
@@ -546,4 +713,6 @@ function onFileSaved(path) {
   externalFileWatchController.onFileSaved(path);
   diffController?.invalidate(path);
   diffController?.scheduleRefresh();
+  diagnosticsController?.invalidate();
+  diagnosticsController?.refresh();
 }
@@ -7,10 +7,20 @@ import {
   setActiveHighlight,
 } from "./find-highlights.js";
 
-export function createFindController({ state, el, codeEditorController }) {
+export function createFindController({
+  state,
+  el,
+  codeEditorController,
+  largeFileController = null,
+}) {
   let matches = [];
   let activeIndex = -1;
   let isOpen = false;
+  let largeSearchToken = 0;
+
+  function isLargeFileActive() {
+    return Boolean(largeFileController?.isActive());
+  }
 
   function syncBackdropScroll() {
     if (el.editorBackdrop) {
@@ -44,7 +54,7 @@ export function createFindController({ state, el, codeEditorController }) {
   }
 
   function getActiveView() {
-    if (state.activeKind === "code") {
+    if (state.activeKind === "code" || state.activeLargeFile) {
       return "code";
     }
     if (state.activeKind === "markdown") {
@@ -67,7 +77,39 @@ export function createFindController({ state, el, codeEditorController }) {
     }
   }
 
+  // Large files are searched on disk; only the loaded window is in the
+  // editor, so each match is revealed by moving the window to it.
+  async function applyLargeFileSearch() {
+    const query = el.findInput.value;
+    const token = ++largeSearchToken;
+    codeEditorController?.clearSearch();
+    matches = [];
+    activeIndex = -1;
+    if (!query) {
+      updateCounter();
+      return;
+    }
+    let results;
+    try {
+      results = await largeFileController.search(query);
+    } catch {
+      results = [];
+    }
+    if (token !== largeSearchToken) return;
+    matches = results;
+    activeIndex = matches.length ? 0 : -1;
+    updateCounter();
+    if (matches.length) {
+      await largeFileController.revealMatch(matches[0]);
+    }
+  }
+
   function applyHighlights() {
+    if (isLargeFileActive()) {
+      applyLargeFileSearch();
+      return;
+    }
+
     const query = el.findInput.value;
     const view = getActiveView();
     const domContainer = getDomContainer();
@@ -122,6 +164,13 @@ export function createFindController({ state, el, codeEditorController }) {
     if (activeIndex >= matches.length) activeIndex = 0;
     if (activeIndex < 0) activeIndex = matches.length - 1;
 
+    if (isLargeFileActive()) {
+      largeFileController.revealMatch(matches[activeIndex]);
+      el.findInput.focus();
+      updateCounter();
+      return;
+    }
+
     const view = getActiveView();
 
     if (view === "editor") {
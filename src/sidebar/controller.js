@@ -30,6 +30,7 @@ export function createSidebarController({
   updateMenuState,
   invoke,
   crossWindowDrag,
+  movePathToFolder,
 }) {
   let eventsBound = false;
 
@@ -252,6 +253,7 @@ export function createSidebarController({
             updateMenuState,
             invoke,
             crossWindowDrag,
+            movePathToFolder,
             markTreeDirty,
             renderSidebar,
             applyFolderCollapsedStateToDom,
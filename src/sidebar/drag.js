@@ -34,6 +34,27 @@ export function isOverTabBar(tabBar, clientX, clientY) {
   );
 }
 
+export function folderAtPoint(projectList, clientX, clientY) {
+  for (const folder of projectList.querySelectorAll(".folder-toggle")) {
+    const rect = folder.getBoundingClientRect();
+    if (
+      clientX >= rect.left &&
+      clientX < rect.right &&
+      clientY >= rect.top &&
+      clientY < rect.bottom
+    ) {
+      return folder;
+    }
+  }
+  return null;
+}
+
+function clearFolderDropIndicators(projectList) {
+  projectList.querySelectorAll(".folder-drop-target").forEach((f) => {
+    f.classList.remove("folder-drop-target");
+  });
+}
+
 function clearTabBarDropIndicators(tabBar) {
   tabBar.querySelectorAll(".tab").forEach((t) => {
     t.classList.remove("tab-drag-over-left", "tab-drag-over-right");
@@ -105,6 +126,7 @@ export function bindSidebarDragEvents({
   openFolderEntryInTabs,
   render,
   updateMenuState,
+  movePathToFolder,
 }) {
   projectList.addEventListener("mousedown", (event) => {
     const button = event.target.closest(".project-item");
@@ -232,7 +254,16 @@ export function bindSidebarDragEvents({
             ghost.style.top = `${rect.top}px`;
           }
         }
-        updateTabBarDropIndicator(el.tabBar, e.clientX, phantomIndex);
+        clearFolderDropIndicators(projectList);
+        const folder = isOverTabBar(el.tabBar, e.clientX, e.clientY)
+          ? null
+          : folderAtPoint(projectList, e.clientX, e.clientY);
+        if (folder) {
+          folder.classList.add("folder-drop-target");
+          clearTabBarDropIndicators(el.tabBar);
+        } else {
+          updateTabBarDropIndicator(el.tabBar, e.clientX, phantomIndex);
+        }
       }
     }
 
@@ -241,6 +272,7 @@ export function bindSidebarDragEvents({
         ghost.remove();
       }
       clearTabBarDropIndicators(el.tabBar);
+      clearFolderDropIndicators(projectList);
       document.documentElement.classList.remove("tab-reordering");
       document.removeEventListener("mousemove", onMouseMove);
       document.removeEventListener("mouseup", onMouseUp);
@@ -254,6 +286,10 @@ export function bindSidebarDragEvents({
       }
 
       const outside = isCursorOutsideWindow(e.clientX, e.clientY);
+      const folder =
+        outside || isOverTabBar(el.tabBar, e.clientX, e.clientY)
+          ? null
+          : folderAtPoint(projectList, e.clientX, e.clientY);
 
       if (crossWindowDrag.currentTargetLabel()) {
         cleanup();
@@ -265,6 +301,15 @@ export function bindSidebarDragEvents({
         restoreState();
         render();
         crossWindowDrag.completeDropAsNewWindow(e.screenX, e.screenY);
+      } else if (folder && movePathToFolder && state.rootPath) {
+        cleanup();
+        restoreState();
+        render();
+        crossWindowDrag.cancel();
+        await movePathToFolder(
+          path,
+          `${state.rootPath}/${folder.dataset.folderPath}`,
+        );
       } else {
         const dropIndex = getDropTargetIndex(
           el.tabBar,
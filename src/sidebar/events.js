@@ -6,6 +6,7 @@ import {
   shouldSidebarSingleClickOpenAsTab,
   sidebarClickModifierAction,
 } from "../ui/behavior.js";
+import { showToast } from "../ui/toast.js";
 import { bindSidebarDragEvents } from "./drag.js";
 import { collectSubfolderPaths } from "./tree.js";
 
@@ -25,10 +26,23 @@ export function bindSidebarItemEvents({
   updateMenuState,
   invoke,
   crossWindowDrag,
+  movePathToFolder,
   markTreeDirty,
   renderSidebar,
   applyFolderCollapsedStateToDom,
 }) {
+  // Binary files have no text to show; say so instead of failing to open.
+  function refuseBinaryItem(button) {
+    const { binaryType } = button.dataset;
+    if (!binaryType) {
+      return false;
+    }
+    const name =
+      button.querySelector(".project-item-label")?.textContent ?? "This file";
+    showToast(`${name} is a binary file (${binaryType})`);
+    return true;
+  }
+
   function rememberSidebarSingleClick(path) {
     sidebarClickState.lastPath = path || null;
     sidebarClickState.previousSingleTab = null;
@@ -108,7 +122,7 @@ export function bindSidebarItemEvents({
       }
 
       const path = button.dataset.path;
-      if (!path) {
+      if (!path || refuseBinaryItem(button)) {
         return;
       }
 
@@ -238,7 +252,7 @@ export function bindSidebarItemEvents({
 
     event.preventDefault();
     const path = button.dataset.path;
-    if (!path) {
+    if (!path || button.dataset.binaryType) {
       return;
     }
 
@@ -290,6 +304,7 @@ export function bindSidebarItemEvents({
       openFolderEntryInTabs,
       render,
       updateMenuState,
+      movePathToFolder,
     });
   }
 }
@@ -41,6 +41,7 @@ export function buildEntryTree(entries) {
         name: parts[parts.length - 1],
         path: entry.path,
         relPath: entry.relPath,
+        binaryType: entry.binaryType ?? null,
       });
     }
   }
@@ -160,7 +161,13 @@ export function renderTreeHtml(
     const badge = fileStatus
       ? `<span class="git-badge">${escapeHtml(fileStatus)}</span>`
       : "";
-    html += `<button class="project-item${fileGitClass}" style="--indent:${depth};" data-path="${escapeAttr(file.path)}"><span class="project-item-label">${escapeHtml(file.name)}</span>${badge}</button>`;
+    const typeBadge = file.binaryType
+      ? `<span class="type-badge">${escapeHtml(file.binaryType)}</span>`
+      : "";
+    const binaryAttr = file.binaryType
+      ? ` data-binary-type="${escapeAttr(file.binaryType)}"`
+      : "";
+    html += `<button class="project-item${fileGitClass}" style="--indent:${depth};" data-path="${escapeAttr(file.path)}"${binaryAttr}><span class="project-item-label">${escapeHtml(file.name)}</span>${typeBadge}${badge}</button>`;
   }
 
   return html;
@@ -1,3 +1,5 @@
+import { recordFileVersion } from "../files/versions.js";
+
 export function hasTabSession(state) {
   return state.openFiles.length > 0;
 }
@@ -10,8 +12,16 @@ export function applyFilePayloadToState(state, payload, options) {
   const previousKind = state.activeKind;
   const previousMarkdownMode = state.markdownViewMode;
 
+  recordFileVersion(payload.path, payload.version);
   state.activePath = payload.path;
   state.activeKind = payload.kind;
+  state.activeLanguage = payload.language ?? null;
+  state.activeWritable = payload.writable !== false;
+  state.activeLargeFile = payload.largeFile ?? null;
+  state.activeEncoding = payload.encoding ?? null;
+  state.activeLineEnding = payload.lineEnding ?? null;
+  state.activeHasBom = payload.hasBom ?? null;
+  state.largeFileStartLine = 1;
   state.content = payload.content;
   state.savedContent = payload.content;
   state.isDirty = false;
@@ -37,6 +47,12 @@ export function applyFilePayloadToState(state, payload, options) {
 export function clearActiveFileInState(state) {
   state.activePath = null;
   state.activeKind = null;
+  state.activeWritable = true;
+  state.activeLargeFile = null;
+  state.activeEncoding = null;
+  state.activeLineEnding = null;
+  state.activeHasBom = null;
+  state.largeFileStartLine = 1;
   state.content = "";
   state.savedContent = "";
   state.isDirty = false;
@@ -60,6 +76,9 @@ export function flushStateToActiveTabInState(state) {
   tab.savedContent = state.savedContent;
   tab.isDirty = state.isDirty;
   tab.markdownViewMode = state.markdownViewMode;
+  if (tab.largeFile) {
+    tab.largeFileStartLine = state.largeFileStartLine;
+  }
   tab.scrollState = {
     editorScrollTop: Number.isFinite(state.activeEditorScrollTop)
       ? state.activeEditorScrollTop
@@ -80,6 +99,13 @@ export function syncActiveTabToStateFromTabs(state) {
   }
   state.activePath = tab.path;
   state.activeKind = tab.kind;
+  state.activeLanguage = tab.language ?? null;
+  state.activeWritable = tab.writable !== false;
+  state.activeLargeFile = tab.largeFile ?? null;
+  state.activeEncoding = tab.encoding ?? null;
+  state.activeLineEnding = tab.lineEnding ?? null;
+  state.activeHasBom = tab.hasBom ?? null;
+  state.largeFileStartLine = tab.largeFileStartLine ?? 1;
   state.content = tab.content;
   state.savedContent = tab.savedContent ?? tab.content;
   state.isDirty = tab.isDirty;
@@ -120,7 +146,12 @@ export function normalizeTransferTab(rawTab) {
           ? rawTab.content
           : "",
     kind,
+    language: typeof rawTab.language === "string" ? rawTab.language : null,
     writable: rawTab.writable !== false,
+    encoding: typeof rawTab.encoding === "string" ? rawTab.encoding : null,
+    lineEnding:
+      typeof rawTab.lineEnding === "string" ? rawTab.lineEnding : null,
+    hasBom: typeof rawTab.hasBom === "boolean" ? rawTab.hasBom : null,
     isDirty: Boolean(rawTab.isDirty),
     markdownViewMode:
       kind === "markdown" && rawTab.markdownViewMode === "edit"
@@ -149,7 +180,11 @@ export function snapshotActiveFileAsTransferTab(state) {
     content: state.content,
     savedContent: state.savedContent ?? state.content,
     kind: state.activeKind,
-    writable: true,
+    language: state.activeLanguage ?? null,
+    writable: state.activeWritable !== false,
+    encoding: state.activeEncoding ?? null,
+    lineEnding: state.activeLineEnding ?? null,
+    hasBom: state.activeHasBom ?? null,
     isDirty: state.isDirty,
     markdownViewMode: state.markdownViewMode,
     scrollState: {
@@ -1,3 +1,8 @@
+import {
+  describeWriteError,
+  fileVersionFor,
+  recordFileVersion,
+} from "../files/versions.js";
 import { promptToSaveBeforeClose } from "../ui/close-dirty-dialog.js";
 
 export function createTabCloseController({
@@ -41,14 +46,19 @@ export function createTabCloseController({
 
     if (!isActive && tab.path && tab.writable) {
       try {
-        await invoke("write_text_file", {
+        const result = await invoke("write_text_file", {
           path: tab.path,
           content: tab.content,
+          lineEnding: tab.lineEnding ?? null,
+          hasBom: tab.hasBom ?? null,
+          encoding: tab.encoding ?? null,
+          expectedVersion: fileVersionFor(tab.path),
         });
+        recordFileVersion(tab.path, result?.version);
         tab.isDirty = false;
         return true;
       } catch (error) {
-        setStatus(String(error), true);
+        setStatus(describeWriteError(error), true);
         return false;
       }
     }
@@ -202,7 +202,13 @@ export function createTabOpenController({
       if (tab) {
         tab.path = payload.path;
         tab.kind = payload.kind;
+        tab.language = payload.language ?? null;
         tab.writable = payload.writable;
+        tab.largeFile = payload.largeFile ?? null;
+        tab.encoding = payload.encoding ?? null;
+        tab.lineEnding = payload.lineEnding ?? null;
+        tab.hasBom = payload.hasBom ?? null;
+        tab.largeFileStartLine = 1;
       }
       render();
       updateMenuState();
@@ -1,10 +1,18 @@
+import { recordFileVersion } from "../files/versions.js";
+
 export function buildTabFromPayload(payload) {
+  recordFileVersion(payload.path, payload.version);
   return {
     path: payload.path,
     content: payload.content,
     savedContent: payload.content,
     kind: payload.kind,
+    language: payload.language ?? null,
     writable: payload.writable,
+    largeFile: payload.largeFile ?? null,
+    encoding: payload.encoding ?? null,
+    lineEnding: payload.lineEnding ?? null,
+    hasBom: payload.hasBom ?? null,
     isDirty: false,
     markdownViewMode: payload.kind === "markdown" ? "preview" : "edit",
     scrollState: {
@@ -56,7 +64,11 @@ export function snapshotActiveStateAsTab(state) {
     content: state.content,
     savedContent: state.savedContent ?? state.content,
     kind: state.activeKind,
-    writable: true,
+    language: state.activeLanguage ?? null,
+    writable: state.activeWritable !== false,
+    encoding: state.activeEncoding ?? null,
+    lineEnding: state.activeLineEnding ?? null,
+    hasBom: state.activeHasBom ?? null,
     isDirty: Boolean(state.isDirty),
     markdownViewMode: state.markdownViewMode,
     scrollState: {
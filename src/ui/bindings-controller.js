@@ -1,9 +1,12 @@
 import { bindSidebarResizer } from "../sidebar/resizer.js";
+import { dirName } from "../utils/app-utils.js";
 import { hasFileDragData } from "../utils/path-input.js";
 import {
   buildKeyboardShortcuts,
   handleKeyboardShortcut,
 } from "./keyboard-shortcuts.js";
+import { formatKindLabel } from "./format-controller.js";
+import { resolveLinkPath } from "./image-paths.js";
 import { addCopyButtons, renderMarkdown } from "./markdown-renderer.js";
 import {
   detectStructuredPasteKind,
@@ -31,6 +34,7 @@ export function bindElements(el) {
   el.codeEditor = document.querySelector("#code-editor");
   el.preview = document.querySelector("#preview");
   el.statusBar = document.querySelector("#status-bar");
+  el.statusBarTasks = document.querySelector("#status-bar-tasks");
   el.statusBarLines = document.querySelector("#status-bar-lines");
   el.findBar = document.querySelector("#find-bar");
   el.findInput = document.querySelector("#find-input");
@@ -40,6 +44,7 @@ export function bindElements(el) {
   el.findClose = document.querySelector("#find-close");
   el.unifiedDiff = document.querySelector("#unified-diff");
   el.unifiedDiffBtn = document.querySelector("#unified-diff-btn");
+  el.tableView = document.querySelector("#table-view");
 }
 
 export function bindUiEvents({
@@ -54,6 +59,7 @@ export function bindUiEvents({
   toggleCollapseAllFolders,
   toggleUnifiedDiff,
   saveNow,
+  goToLine,
   hasTabSession,
   switchTab,
   navigateBack,
@@ -63,6 +69,7 @@ export function bindUiEvents({
   onDirtyStateChanged,
   openFind,
   formatActiveFile,
+  openLinkedFile,
 }) {
   window.addEventListener("dragover", (event) => {
     if (hasFileDragData(event)) {
@@ -109,9 +116,9 @@ export function bindUiEvents({
       );
 
       if (inserted === "formatted") {
-        const kindLabel = (
-          formatResult.detectedKind || detectedKind
-        ).toUpperCase();
+        const kindLabel = formatKindLabel(
+          formatResult.detectedKind || detectedKind,
+        );
         showToast(`Reformatted as ${kindLabel}`);
       } else if (!formatResult?.detectedKind) {
         setStatus(
@@ -154,6 +161,7 @@ export function bindUiEvents({
     toggleCollapseAllFolders,
     toggleSidebarVisibility,
     saveNow,
+    goToLine,
     navigateBack,
     navigateForward,
   });
@@ -175,6 +183,22 @@ export function bindUiEvents({
     toggleUnifiedDiff?.();
   });
 
+  // Headings carry their source line, and the outline maps slugs to lines.
+  async function scrollPreviewToAnchor(anchor) {
+    try {
+      const slug = decodeURIComponent(anchor).toLowerCase();
+      const outline = await invoke("markdown_outline", {
+        content: state.content,
+      });
+      const heading = outline.headings.find((h) => h.slug === slug);
+      el.preview
+        .querySelector(`[data-src-line-start="${heading?.line}"]`)
+        ?.scrollIntoView({ block: "start" });
+    } catch {
+      // Leave the preview where it is.
+    }
+  }
+
   el.preview.addEventListener("click", async (event) => {
     const copyButton = event.target.closest(".copy-btn");
     if (copyButton) {
@@ -191,6 +215,10 @@ export function bindUiEvents({
     const checkbox = event.target.closest(
       'input[type="checkbox"], .task-list-item-checkbox',
     );
+    if (checkbox && state.activeWritable === false) {
+      event.preventDefault();
+      return;
+    }
     if (checkbox) {
       const listItem = checkbox.closest("li[data-src-line]");
       const srcLine = parseInt(listItem?.dataset?.srcLine, 10);
@@ -221,6 +249,19 @@ export function bindUiEvents({
     if (!link) {
       return;
     }
+    const href = link.getAttribute("href");
+    if (href.startsWith("#")) {
+      event.preventDefault();
+      await scrollPreviewToAnchor(href.slice(1));
+      return;
+    }
+    const linkedPath =
+      state.activePath && resolveLinkPath(href, dirName(state.activePath));
+    if (linkedPath) {
+      event.preventDefault();
+      await openLinkedFile?.(linkedPath);
+      return;
+    }
     link.setAttribute("target", "_blank");
     link.setAttribute("rel", "noreferrer noopener");
   });
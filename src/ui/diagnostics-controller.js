@@ -0,0 +1,77 @@
+import { detectFormatKind } from "./format-controller.js";
+
+// Broken links and images of a Markdown file, as editor warnings.
+export function brokenLinkDiagnostics(report) {
+  return (report?.links ?? [])
+    .filter((link) => link.status === "broken")
+    .map((link) => ({
+      message: link.message,
+      line: link.line,
+      column: link.column,
+      severity: "warning",
+    }));
+}
+
+function checkDiagnostics({ invoke, kind, path, content }) {
+  if (kind === "markdown") {
+    return invoke("check_markdown_links", { path, content }).then(
+      brokenLinkDiagnostics,
+    );
+  }
+  return invoke("validate_structured_text", { content, kind, path });
+}
+
+export function createDiagnosticsController({
+  state,
+  invoke,
+  codeEditorController,
+}) {
+  let lastPath = null;
+  let lastContent = null;
+  let lastDiagnostics = [];
+
+  function clear() {
+    lastPath = null;
+    lastContent = null;
+    lastDiagnostics = [];
+    codeEditorController.clearDiagnostics();
+  }
+
+  async function refresh() {
+    const path = state.activePath;
+    const content = state.content ?? "";
+    const kind = detectFormatKind(path);
+
+    if (!kind || !codeEditorController.isAttached()) {
+      clear();
+      return;
+    }
+    if (path === lastPath && content === lastContent) {
+      codeEditorController.setDiagnostics(lastDiagnostics);
+      return;
+    }
+
+    try {
+      const diagnostics = await checkDiagnostics({
+        invoke,
+        kind,
+        path,
+        content,
+      });
+      if (state.activePath !== path) return;
+      lastPath = path;
+      lastContent = content;
+      lastDiagnostics = diagnostics ?? [];
+      codeEditorController.setDiagnostics(lastDiagnostics);
+    } catch {
+      clear();
+    }
+  }
+
+  // Schemas and `.teex.toml` can change without the file changing.
+  function invalidate() {
+    lastContent = null;
+  }
+
+  return { refresh, invalidate, clear };
+}
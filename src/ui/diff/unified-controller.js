@@ -1,5 +1,8 @@
 import { isTextInputActive } from "../behavior.js";
-import { buildUnifiedDiffHtml } from "./unified-renderer.js";
+import {
+  buildSemanticChangesHtml,
+  buildUnifiedDiffHtml,
+} from "./unified-renderer.js";
 
 export function getAdjacentTocId(tocItems, activeId, direction) {
   if (!tocItems.length) return activeId;
@@ -97,6 +100,46 @@ function bindScrollspy(container) {
   };
 }
 
+/**
+ * Let the "Structure" button of each JSON, YAML or TOML file switch its
+ * body between the line diff and a key-by-key diff against HEAD.
+ */
+export function bindStructureToggles(container, rootPath, invoke) {
+  if (!container.addEventListener) return null;
+  const lineViews = new Map();
+
+  async function onClick(e) {
+    const button = e.target.closest?.(".udiff-view-toggle");
+    if (!button) return;
+    const fileEl = button.closest(".udiff-file");
+    const body = fileEl?.querySelector(".udiff-file-body");
+    const relPath = fileEl?.dataset.path;
+    if (!body || !relPath) return;
+
+    if (lineViews.has(relPath)) {
+      body.innerHTML = lineViews.get(relPath);
+      lineViews.delete(relPath);
+      button.setAttribute("aria-pressed", "false");
+      return;
+    }
+    try {
+      const changes = await invoke("git_semantic_diff", {
+        root: rootPath,
+        relPath,
+      });
+      lineViews.set(relPath, body.innerHTML);
+      body.innerHTML = buildSemanticChangesHtml(changes);
+      button.setAttribute("aria-pressed", "true");
+    } catch (err) {
+      button.title = String(err);
+      button.disabled = true;
+    }
+  }
+
+  container.addEventListener("click", onClick);
+  return () => container.removeEventListener("click", onClick);
+}
+
 export function createUnifiedDiffController({ state, el, invoke }) {
   let debounceTimer = null;
   let cleanupScrollspy = null;
@@ -113,7 +156,13 @@ export function createUnifiedDiffController({ state, el, invoke }) {
       const fileDiffs = await invoke("git_diff_all", { root: rootPath });
       if (state.activeKind === "diff" && state.rootPath === rootPath) {
         el.unifiedDiff.innerHTML = buildUnifiedDiffHtml(fileDiffs);
-        cleanupScrollspy = bindScrollspy(el.unifiedDiff);
+        const cleanups = [
+          bindScrollspy(el.unifiedDiff),
+          bindStructureToggles(el.unifiedDiff, rootPath, invoke),
+        ];
+        cleanupScrollspy = () => {
+          for (const cleanup of cleanups) cleanup?.();
+        };
       }
     } catch (err) {
       console.error("Failed to fetch unified diff:", err);
@@ -10,6 +10,16 @@ function fileBaseName(relPath) {
   return relPath.split("/").at(-1) || relPath;
 }
 
+const STRUCTURED_PATH_RE = /\.(json|ya?ml|toml)$/i;
+
+/**
+ * Whether the file can also be shown as a structural (key-by-key) diff.
+ * @param {string} relPath
+ */
+export function isStructuredPath(relPath) {
+  return STRUCTURED_PATH_RE.test(relPath);
+}
+
 function countLines(file) {
   let added = 0;
   let removed = 0;
@@ -50,7 +60,12 @@ export function buildUnifiedDiffHtml(fileDiffs) {
     toc += `</span></a>`;
 
     content += `<div class="udiff-file" id="${id}" data-path="${escapeAttr(file.rel_path)}">`;
-    content += `<div class="udiff-file-header">${escapeHtml(file.rel_path)}</div>`;
+    content += `<div class="udiff-file-header">`;
+    content += `<span>${escapeHtml(file.rel_path)}</span>`;
+    if (isStructuredPath(file.rel_path)) {
+      content += `<button type="button" class="udiff-view-toggle" aria-pressed="false" title="Compare keys instead of lines">Structure</button>`;
+    }
+    content += `</div><div class="udiff-file-body">`;
 
     for (const hunk of file.hunks) {
       content += `<div class="udiff-hunk">`;
@@ -70,7 +85,7 @@ export function buildUnifiedDiffHtml(fileDiffs) {
       content += `</div>`;
     }
 
-    content += `</div>`;
+    content += `</div></div>`;
   }
 
   toc += `</nav>`;
@@ -78,3 +93,31 @@ export function buildUnifiedDiffHtml(fileDiffs) {
 
   return `<div class="udiff-layout">${toc}${content}</div>`;
 }
+
+function formatValue(value) {
+  return value === undefined ? "" : JSON.stringify(value);
+}
+
+/**
+ * Build HTML for the structural diff of one file. Changed keys are shown
+ * as a removed line followed by an added line.
+ * @param {Array<{path: string, change: string, oldValue: unknown, newValue: unknown}>} changes
+ * @returns {string} HTML string
+ */
+export function buildSemanticChangesHtml(changes) {
+  if (!changes || changes.length === 0) {
+    return '<div class="udiff-semantic-empty">No structural changes. Only formatting or key order differs.</div>';
+  }
+  let html = '<div class="udiff-hunk udiff-semantic">';
+  for (const { path, change, oldValue, newValue } of changes) {
+    const label = escapeHtml(path || "(document)");
+    if (change !== "added") {
+      html += `<div class="udiff-line udiff-removed">-${label}: ${escapeHtml(formatValue(oldValue))}</div>`;
+    }
+    if (change !== "removed") {
+      html += `<div class="udiff-line udiff-added">+${label}: ${escapeHtml(formatValue(newValue))}</div>`;
+    }
+  }
+  html += "</div>";
+  return html;
+}
@@ -24,6 +24,7 @@ import { languageForExtension } from "./codemirror-languages.js";
 
 const setDiffEffect = StateEffect.define();
 const setSearchDecorationsEffect = StateEffect.define();
+const setDiagnosticsEffect = StateEffect.define();
 
 const diffField = StateField.define({
   create() {
@@ -39,6 +40,39 @@ const diffField = StateField.define({
   provide: (f) => EditorView.decorations.from(f),
 });
 
+const diagnosticsField = StateField.define({
+  create() {
+    return Decoration.none;
+  },
+  update(decos, tr) {
+    for (const effect of tr.effects) {
+      if (effect.is(setDiagnosticsEffect)) return effect.value;
+    }
+    if (tr.docChanged) decos = decos.map(tr.changes);
+    return decos;
+  },
+  provide: (f) => EditorView.decorations.from(f),
+});
+
+// The bundle has no custom gutters, so diagnostics are marked in the
+// line-number gutter.
+const DIAGNOSTIC_GUTTER_MARKS = { error: "\u25cf", warning: "\u25b2" };
+
+function diagnosticSeverityAt(state, lineNumber) {
+  const diagnostics = state.field(diagnosticsField, false);
+  // Gutter sizing formats 9, 99, 999… which can lie past the last line.
+  if (!diagnostics || diagnostics.size === 0) return null;
+  if (lineNumber > state.doc.lines) return null;
+  const from = state.doc.line(lineNumber).from;
+  let severity = null;
+  diagnostics.between(from, from, (start, _end, deco) => {
+    if (start !== from) return;
+    severity = deco.spec.severity;
+    return false;
+  });
+  return severity;
+}
+
 const diffAdded = Decoration.line({ class: "cm-diff-added" });
 const searchMatchMark = Decoration.mark({ class: "cm-custom-search-match" });
 const activeSearchMatchMark = Decoration.mark({
@@ -160,12 +194,37 @@ export function createCodeMirrorController({
   onScroll,
 }) {
   let view = null;
+  // Lines and severities the line-number gutter currently marks.
+  let gutterMarksKey = "";
   let currentLanguage = null;
   let isSyncing = false;
   let searchMatches = [];
   let activeSearchIndex = -1;
+  let readOnly = false;
+  let firstLineNumber = 1;
   const langCompartment = new Compartment();
   const highlightCompartment = new Compartment();
+  const readOnlyCompartment = new Compartment();
+  const lineNumbersCompartment = new Compartment();
+
+  function readOnlyExt() {
+    return EditorState.readOnly.of(readOnly);
+  }
+
+  // Large files show a window of lines; number them as in the file. Lines
+  // with diagnostics get a mark in front of their number.
+  function lineNumbersExt() {
+    const offset = firstLineNumber - 1;
+    return lineNumbers({
+      formatNumber: (n, state) => {
+        const label = String(n + offset);
+        const severity = diagnosticSeverityAt(state, n);
+        return severity
+          ? `${DIAGNOSTIC_GUTTER_MARKS[severity]} ${label}`
+          : label;
+      },
+    });
+  }
 
   function isDark() {
     const explicit = document.documentElement.getAttribute("data-theme");
@@ -202,12 +261,14 @@ export function createCodeMirrorController({
       },
     });
 
+    gutterMarksKey = "";
     view = new EditorView({
       parent: el.codeEditor,
       state: EditorState.create({
         doc: "",
         extensions: [
-          lineNumbers(),
+          lineNumbersCompartment.of(lineNumbersExt()),
+          readOnlyCompartment.of(readOnlyExt()),
           drawSelection(),
           highlightSpecialChars(),
           indentOnInput(),
@@ -233,6 +294,7 @@ export function createCodeMirrorController({
           }),
           searchField,
           diffField,
+          diagnosticsField,
           updateListener,
           scrollListener,
           EditorView.lineWrapping,
@@ -271,6 +333,49 @@ export function createCodeMirrorController({
     if (view) view.focus();
   }
 
+  function setReadOnly(value) {
+    if (readOnly === value) return;
+    readOnly = value;
+    if (!view) return;
+    view.dispatch({ effects: readOnlyCompartment.reconfigure(readOnlyExt()) });
+  }
+
+  function setFirstLineNumber(value) {
+    if (firstLineNumber === value) return;
+    firstLineNumber = value;
+    if (!view) return;
+    view.dispatch({
+      effects: lineNumbersCompartment.reconfigure(lineNumbersExt()),
+    });
+  }
+
+  // Select `[from, to)` UTF-16 columns of a 1-based line of the buffer.
+  function selectInLine(lineNumber, from, to) {
+    if (!view) return;
+    const doc = view.state.doc;
+    if (lineNumber < 1 || lineNumber > doc.lines) return;
+    const line = doc.line(lineNumber);
+    view.dispatch({
+      selection: {
+        anchor: Math.min(line.from + from, line.to),
+        head: Math.min(line.from + to, line.to),
+      },
+      scrollIntoView: true,
+    });
+  }
+
+  function getVisibleLineRange() {
+    if (!view) return null;
+    const doc = view.state.doc;
+    const { scrollTop, clientHeight } = view.scrollDOM;
+    const top = view.lineBlockAtHeight(scrollTop);
+    const bottom = view.lineBlockAtHeight(scrollTop + clientHeight);
+    return {
+      first: doc.lineAt(top.from).number,
+      last: doc.lineAt(bottom.from).number,
+    };
+  }
+
   function isAttached() {
     return view !== null;
   }
@@ -298,6 +403,61 @@ export function createCodeMirrorController({
     });
   }
 
+  function setDiagnostics(diagnostics) {
+    if (!view) return;
+    const doc = view.state.doc;
+    const messagesByLine = new Map();
+    for (const diagnostic of diagnostics) {
+      if (diagnostic.line < 1 || diagnostic.line > doc.lines) continue;
+      const entry = messagesByLine.get(diagnostic.line) ?? {
+        severity: "warning",
+        messages: [],
+      };
+      if (diagnostic.severity === "error") entry.severity = "error";
+      entry.messages.push(
+        `${diagnostic.message} (column ${diagnostic.column})`,
+      );
+      messagesByLine.set(diagnostic.line, entry);
+    }
+    const decos = [...messagesByLine.entries()]
+      .sort(([a], [b]) => a - b)
+      .map(([line, entry]) =>
+        Decoration.line({
+          class: `cm-diagnostic cm-diagnostic-${entry.severity}`,
+          attributes: { title: entry.messages.join("\n") },
+          severity: entry.severity,
+        }).range(doc.line(line).from),
+      );
+    const marksKey = [...messagesByLine.entries()]
+      .map(([line, entry]) => `${line}:${entry.severity}`)
+      .sort()
+      .join(",");
+    view.dispatch({
+      effects: [
+        setDiagnosticsEffect.of(RangeSet.of(decos, true)),
+        ...gutterMarksEffects(marksKey),
+      ],
+    });
+  }
+
+  function clearDiagnostics() {
+    if (!view) return;
+    view.dispatch({
+      effects: [
+        setDiagnosticsEffect.of(Decoration.none),
+        ...gutterMarksEffects(""),
+      ],
+    });
+  }
+
+  // The line-number gutter only redraws its marks when reconfigured, so do
+  // that when the marked lines change rather than on every render.
+  function gutterMarksEffects(marksKey) {
+    if (marksKey === gutterMarksKey) return [];
+    gutterMarksKey = marksKey;
+    return [lineNumbersCompartment.reconfigure(lineNumbersExt())];
+  }
+
   function refreshTheme() {
     if (!view) return;
     view.dispatch({
@@ -315,7 +475,9 @@ export function createCodeMirrorController({
     .matchMedia("(prefers-color-scheme: dark)")
     .addEventListener("change", () => refreshTheme());
 
-  function scrollToLine(lineNumber) {
+  // `position` "top" puts the line at the top of the view instead of a
+  // third of the way down.
+  function scrollToLine(lineNumber, position = "third") {
     if (!view) return;
     const doc = view.state.doc;
     if (lineNumber < 1 || lineNumber > doc.lines) return;
@@ -327,10 +489,13 @@ export function createCodeMirrorController({
       ? Number.parseFloat(window.getComputedStyle(content).paddingTop) || 0
       : 0;
     const viewportHeight = view.scrollDOM.clientHeight;
-    const targetTop = Math.max(
-      0,
-      block.top - paddingTop - viewportHeight / 3 + block.height / 2,
-    );
+    const targetTop =
+      position === "top"
+        ? Math.max(0, block.top - paddingTop)
+        : Math.max(
+            0,
+            block.top - paddingTop - viewportHeight / 3 + block.height / 2,
+          );
 
     view.scrollDOM.scrollTop = targetTop;
   }
@@ -339,6 +504,11 @@ export function createCodeMirrorController({
     return view ? view.state.doc.lines : 0;
   }
 
+  function getCursorLine() {
+    if (!view) return null;
+    return view.state.doc.lineAt(view.state.selection.main.head).number;
+  }
+
   function applySearchDecorations() {
     if (!view) return;
     const decos = [];
@@ -404,10 +574,17 @@ export function createCodeMirrorController({
     syncContent,
     focus,
     isAttached,
+    setReadOnly,
+    setFirstLineNumber,
+    selectInLine,
+    getVisibleLineRange,
     setDiffDecorations,
     clearDiffDecorations,
+    setDiagnostics,
+    clearDiagnostics,
     scrollToLine,
     getLineCount,
+    getCursorLine,
     search,
     searchNext,
     searchPrev,
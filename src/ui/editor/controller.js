@@ -1,4 +1,12 @@
 import { hasActiveContent } from "../behavior.js";
+import {
+  describeWriteError,
+  fileVersionFor,
+  isWriteConflict,
+  recordFileVersion,
+} from "../../files/versions.js";
+import { baseName } from "../../utils/app-utils.js";
+import { promptToResolveWriteConflict } from "../write-conflict-dialog.js";
 
 const CODE_EXTENSIONS = new Set([
   "json",
@@ -9,6 +17,8 @@ const CODE_EXTENSIONS = new Set([
   "toml",
   "xml",
   "csv",
+  "tsv",
+  "tab",
   "ini",
   "cfg",
   "conf",
@@ -81,6 +91,11 @@ export function isEditableState(state) {
     return false;
   }
 
+  // Read-only files and large-file previews must never be written back.
+  if (state.activeWritable === false) {
+    return false;
+  }
+
   if (state.activeKind === "markdown") {
     return state.markdownViewMode === "edit";
   }
@@ -109,6 +124,7 @@ export function createEditorController({
   onBeforeToggleMarkdownMode = null,
   onAfterToggleMarkdownMode = null,
   onSavedStateChanged = null,
+  promptWriteConflict = promptToResolveWriteConflict,
 }) {
   function updateMenuState() {
     invoke("set_menu_state", {
@@ -173,33 +189,84 @@ export function createEditorController({
       return;
     }
 
+    const path = state.activePath;
     state.isSaving = true;
     try {
-      await invoke("write_text_file", {
-        path: state.activePath,
-        content: state.content,
-      });
-      state.savedContent = state.content;
-      state.isDirty = false;
-      if (hasTabSession() && state.openFiles[state.activeTabIndex]) {
-        state.openFiles[state.activeTabIndex].isDirty = false;
-        state.openFiles[state.activeTabIndex].content = state.content;
-        state.openFiles[state.activeTabIndex].savedContent = state.content;
-      }
-      if (typeof onFileSaved === "function") {
-        onFileSaved(state.activePath);
-      }
-      if (typeof onSavedStateChanged === "function") {
-        onSavedStateChanged();
-      }
-      setStatus("Saved");
+      await writeActiveFile(path, fileVersionFor(path));
     } catch (error) {
-      setStatus(String(error), true);
+      if (isWriteConflict(error)) {
+        await resolveWriteConflict(path, error);
+      } else {
+        setStatus(describeWriteError(error), true);
+      }
     } finally {
       state.isSaving = false;
     }
   }
 
+  function markActiveSaved(content) {
+    state.content = content;
+    state.savedContent = content;
+    state.isDirty = false;
+    if (hasTabSession() && state.openFiles[state.activeTabIndex]) {
+      state.openFiles[state.activeTabIndex].isDirty = false;
+      state.openFiles[state.activeTabIndex].content = content;
+      state.openFiles[state.activeTabIndex].savedContent = content;
+    }
+  }
+
+  async function writeActiveFile(path, expectedVersion) {
+    const content = state.content;
+    const result = await invoke("write_text_file", {
+      path,
+      content,
+      lineEnding: state.activeLineEnding ?? null,
+      hasBom: state.activeHasBom ?? null,
+      encoding: state.activeEncoding ?? null,
+      expectedVersion,
+    });
+    recordFileVersion(path, result?.version);
+    markActiveSaved(content);
+    if (typeof onFileSaved === "function") {
+      onFileSaved(path);
+    }
+    if (typeof onSavedStateChanged === "function") {
+      onSavedStateChanged();
+    }
+    setStatus("Saved");
+  }
+
+  // The file changed on disk since it was read. Overwrite it with the
+  // edits or reload what is on disk; either way the version moves on to
+  // the one the conflict reported.
+  async function resolveWriteConflict(path, conflict) {
+    const choice = await promptWriteConflict(baseName(path));
+    if (state.activePath !== path || choice === "cancel") {
+      setStatus(
+        `${describeWriteError(conflict)} (kept local edits, not saved)`,
+        true,
+      );
+      return;
+    }
+
+    if (choice === "overwrite") {
+      try {
+        await writeActiveFile(path, conflict.currentVersion);
+      } catch (error) {
+        setStatus(describeWriteError(error), true);
+      }
+      return;
+    }
+
+    recordFileVersion(path, conflict.currentVersion);
+    markActiveSaved(conflict.theirs);
+    render();
+    if (typeof onSavedStateChanged === "function") {
+      onSavedStateChanged();
+    }
+    setStatus("Reloaded from disk");
+  }
+
   async function saveAsUntitled() {
     const { save } = window.__TAURI__.dialog;
     let chosenPath;
@@ -214,10 +281,11 @@ export function createEditorController({
 
     state.isSaving = true;
     try {
-      await invoke("write_text_file", {
+      const result = await invoke("write_text_file", {
         path: chosenPath,
         content: state.content,
       });
+      recordFileVersion(chosenPath, result?.version);
       state.activePath = chosenPath;
       state.savedContent = state.content;
       state.isDirty = false;
@@ -225,11 +293,13 @@ export function createEditorController({
       const ext = chosenPath.split(".").pop();
       const kind = fileKindFromExtension(ext);
       state.activeKind = kind;
+      state.activeLanguage = null;
 
       const tab = state.openFiles[state.activeTabIndex];
       if (tab) {
         tab.path = chosenPath;
         tab.kind = kind;
+        tab.language = null;
         tab.isDirty = false;
         tab.content = state.content;
         if (kind === "markdown") {
@@ -244,7 +314,7 @@ export function createEditorController({
       setStatus(`Saved as ${chosenPath.split("/").pop()}`);
       render();
     } catch (error) {
-      setStatus(String(error), true);
+      setStatus(describeWriteError(error), true);
     } finally {
       state.isSaving = false;
     }
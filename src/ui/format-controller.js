@@ -1,25 +1,47 @@
 import { fileExtension } from "../utils/app-utils.js";
 import { showToast } from "./toast.js";
 
-const JSON_EXTENSIONS = new Set(["json", "jsonc", "geojson"]);
+const JSON_EXTENSIONS = new Set(["json", "geojson"]);
+const JSONC_EXTENSIONS = new Set(["jsonc"]);
+const JSON5_EXTENSIONS = new Set(["json5"]);
+const JSONL_EXTENSIONS = new Set(["jsonl", "ndjson"]);
 const YAML_EXTENSIONS = new Set(["yaml", "yml"]);
 const TOML_EXTENSIONS = new Set(["toml"]);
 const XML_EXTENSIONS = new Set(["xml", "svg", "xhtml"]);
 const CSV_EXTENSIONS = new Set(["csv", "tsv"]);
+const MARKDOWN_EXTENSIONS = new Set(["md", "markdown"]);
 
 export function detectFormatKind(path) {
   if (!path) return null;
   const ext = fileExtension(path);
   if (!ext) return null;
   if (JSON_EXTENSIONS.has(ext)) return "json";
+  if (JSONC_EXTENSIONS.has(ext)) return "jsonc";
+  if (JSON5_EXTENSIONS.has(ext)) return "json5";
+  if (JSONL_EXTENSIONS.has(ext)) return "jsonl";
   if (YAML_EXTENSIONS.has(ext)) return "yaml";
   if (TOML_EXTENSIONS.has(ext)) return "toml";
   if (XML_EXTENSIONS.has(ext)) return "xml";
   if (CSV_EXTENSIONS.has(ext)) return "csv";
+  if (MARKDOWN_EXTENSIONS.has(ext)) return "markdown";
   return null;
 }
 
-export async function formatActiveFileContent({ activePath, content, invoke }) {
+export function formatKindLabel(kind) {
+  return kind === "markdown" ? "Markdown tables" : kind.toUpperCase();
+}
+
+const STYLE_DONE_MESSAGES = {
+  sortKeys: "Sorted keys",
+  minify: "Minified",
+};
+
+export async function formatActiveFileContent({
+  activePath,
+  content,
+  invoke,
+  style = "pretty",
+}) {
   if (!activePath || !content) return null;
 
   const kind = detectFormatKind(activePath);
@@ -29,14 +51,21 @@ export async function formatActiveFileContent({ activePath, content, invoke }) {
     const result = await invoke("format_structured_text", {
       content,
       preferredKind: kind,
+      style,
     });
-    if (!result || !result.changed || typeof result.formatted !== "string") {
+    const invalidLines = result?.invalidLines ?? [];
+    if (
+      !result ||
+      (!result.changed && invalidLines.length === 0) ||
+      typeof result.formatted !== "string"
+    ) {
       return null;
     }
     return {
       formatted: result.formatted,
       kind: result.detectedKind ?? kind,
-      changed: true,
+      changed: result.changed,
+      invalidLines,
     };
   } catch {
     return null;
@@ -49,13 +78,14 @@ export function createFormatController({
   codeEditorController,
   onDirtyStateChanged,
 }) {
-  async function formatActiveFile() {
+  async function formatActiveFile(style = "pretty") {
     if (!state.activePath || !state.content) return;
 
     const result = await formatActiveFileContent({
       activePath: state.activePath,
       content: state.content,
       invoke,
+      style,
     });
 
     if (!result) {
@@ -63,6 +93,15 @@ export function createFormatController({
       return;
     }
 
+    if (result.invalidLines.length > 0) {
+      const { invalidLines } = result;
+      const lines = invalidLines.slice(0, 5).join(", ");
+      const more = invalidLines.length > 5 ? ", …" : "";
+      const noun = invalidLines.length === 1 ? "line" : "lines";
+      showToast(`Invalid JSON on ${noun} ${lines}${more}`);
+      if (!result.changed) return;
+    }
+
     state.content = result.formatted;
     state.isDirty = state.content !== state.savedContent;
 
@@ -74,8 +113,50 @@ export function createFormatController({
       onDirtyStateChanged();
     }
 
-    showToast(`Formatted as ${result.kind.toUpperCase()}`);
+    if (result.invalidLines.length === 0) {
+      const kindLabel = formatKindLabel(result.kind);
+      showToast(STYLE_DONE_MESSAGES[style] ?? `Formatted as ${kindLabel}`);
+    }
+  }
+
+  // Copy the active CSV/TSV file as a Markdown table, or the Markdown table
+  // under the cursor as CSV.
+  async function copyTableAs(target) {
+    const path = state.activePath;
+    const kind = detectFormatKind(path);
+    if (!state.content) return;
+
+    let text;
+    try {
+      if (target === "markdown" && kind === "csv") {
+        text = await invoke("csv_to_markdown_table", {
+          content: state.content,
+          path,
+        });
+      } else if (target === "csv" && kind === "markdown") {
+        text = await invoke("markdown_table_to_csv", {
+          content: state.content,
+          line: codeEditorController?.isAttached()
+            ? codeEditorController.getCursorLine()
+            : null,
+        });
+      } else {
+        showToast(
+          target === "markdown"
+            ? "Open a CSV or TSV file to copy it as a Markdown table"
+            : "Open a Markdown file to copy one of its tables as CSV",
+        );
+        return;
+      }
+      await globalThis.navigator?.clipboard?.writeText?.(text);
+    } catch (error) {
+      showToast(String(error));
+      return;
+    }
+    showToast(
+      target === "markdown" ? "Copied as Markdown table" : "Copied table as CSV",
+    );
   }
 
-  return { formatActiveFile };
+  return { formatActiveFile, copyTableAs };
 }
@@ -29,6 +29,21 @@ export function resolveImagePath(src, fileDir) {
   return `/${resolved.join("/")}`;
 }
 
+/**
+ * The file a relative or absolute preview link points at, or null for
+ * URLs and in-page `#anchor` links.
+ */
+export function resolveLinkPath(href, fileDir) {
+  if (!href || href.startsWith("#") || href.startsWith("//")) return null;
+  if (/^[a-z][a-z0-9+.-]+:/i.test(href)) return null;
+  const resolved = resolveImagePath(href, fileDir);
+  try {
+    return decodeURIComponent(resolved);
+  } catch {
+    return resolved;
+  }
+}
+
 export function toLocalImageUrl(absolutePath) {
   return `localimage://localhost${absolutePath}`;
 }
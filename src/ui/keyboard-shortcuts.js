@@ -11,6 +11,7 @@ export function buildKeyboardShortcuts({
   toggleCollapseAllFolders,
   toggleSidebarVisibility,
   saveNow,
+  goToLine,
   navigateBack,
   navigateForward,
 }) {
@@ -63,6 +64,11 @@ export function buildKeyboardShortcuts({
       meta: true,
       handler: () => saveNow?.(),
     },
+    {
+      key: "l",
+      meta: true,
+      handler: () => goToLine?.(),
+    },
     {
       key: "[",
       meta: true,
@@ -0,0 +1,130 @@
+// Lines of a large file held in the editor at a time.
+export const LARGE_FILE_WINDOW_LINES = 2000;
+// Load the next window once the view is this close to either edge.
+const EDGE_LINES = 50;
+const MAX_SEARCH_RESULTS = 1000;
+
+export function largeFileWindowStart(
+  line,
+  totalLines,
+  windowLines = LARGE_FILE_WINDOW_LINES,
+) {
+  const latest = Math.max(1, totalLines - windowLines + 1);
+  return Math.min(Math.max(1, line - Math.floor(windowLines / 2)), latest);
+}
+
+export function describeLargeFileWindow(state) {
+  const info = state.activeLargeFile;
+  if (!info) return "";
+  const first = state.largeFileStartLine;
+  const last = Math.min(
+    info.lineCount,
+    first + state.content.split("\n").length - 1,
+  );
+  return `Lines ${first}–${last} of ${info.lineCount} (read-only)`;
+}
+
+// Search results count characters; the editor counts UTF-16 units.
+export function utf16Column(text, charIndex) {
+  return Array.from(text).slice(0, charIndex).join("").length;
+}
+
+/**
+ * Files over the large-file threshold open with a preview of their first
+ * lines. This keeps a window of `LARGE_FILE_WINDOW_LINES` lines in the
+ * read-only editor, moves it with `read_text_range` as the view scrolls or
+ * jumps, and searches the whole file with `search_text_file`.
+ */
+export function createLargeFileController({
+  state,
+  invoke,
+  render,
+  setStatus,
+  codeEditorController,
+}) {
+  let loading = false;
+
+  function isActive() {
+    return Boolean(state.activePath && state.activeLargeFile);
+  }
+
+  function windowLineCount() {
+    return state.content.split("\n").length;
+  }
+
+  async function showLines(startLine) {
+    const path = state.activePath;
+    loading = true;
+    try {
+      const range = await invoke("read_text_range", {
+        path,
+        startLine,
+        count: LARGE_FILE_WINDOW_LINES,
+      });
+      if (state.activePath !== path) return false;
+      state.content = range.lines.join("\n");
+      state.savedContent = state.content;
+      state.isDirty = false;
+      state.largeFileStartLine = range.startLine;
+      render({ focusEditor: false });
+      return true;
+    } catch (error) {
+      setStatus(String(error), true);
+      return false;
+    } finally {
+      loading = false;
+    }
+  }
+
+  async function goToLine(line) {
+    if (!isActive()) return;
+    const total = state.activeLargeFile.lineCount;
+    const target = Math.min(Math.max(1, line), total);
+    const start = state.largeFileStartLine;
+    if (target < start || target >= start + windowLineCount()) {
+      const moved = await showLines(largeFileWindowStart(target, total));
+      if (!moved) return;
+    }
+    codeEditorController.scrollToLine(target - state.largeFileStartLine + 1);
+  }
+
+  async function onEditorScroll() {
+    if (!isActive() || loading) return;
+    const visible = codeEditorController.getVisibleLineRange();
+    if (!visible) return;
+    const start = state.largeFileStartLine;
+    const shown = windowLineCount();
+    const total = state.activeLargeFile.lineCount;
+    const nearTop = visible.first <= EDGE_LINES && start > 1;
+    const nearBottom =
+      visible.last >= shown - EDGE_LINES && start + shown - 1 < total;
+    if (!nearTop && !nearBottom) return;
+
+    const anchor = start + visible.first - 1;
+    const nextStart = largeFileWindowStart(anchor, total);
+    if (nextStart === start) return;
+    if (await showLines(nextStart)) {
+      codeEditorController.scrollToLine(anchor - nextStart + 1, "top");
+    }
+  }
+
+  async function search(query) {
+    if (!isActive() || !query) return [];
+    return invoke("search_text_file", {
+      path: state.activePath,
+      options: { query, maxResults: MAX_SEARCH_RESULTS },
+    });
+  }
+
+  async function revealMatch(match) {
+    await goToLine(match.lineNumber);
+    const [from, to] = match.ranges[0] ?? [0, 0];
+    codeEditorController.selectInLine(
+      match.lineNumber - state.largeFileStartLine + 1,
+      utf16Column(match.lineText, from),
+      utf16Column(match.lineText, to),
+    );
+  }
+
+  return { isActive, goToLine, onEditorScroll, search, revealMatch };
+}
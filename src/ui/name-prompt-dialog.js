@@ -0,0 +1,86 @@
+function buildButton(label, value, className = "") {
+  const button = document.createElement("button");
+  button.type = "button";
+  button.dataset.value = value;
+  button.textContent = label;
+  button.className = className;
+  return button;
+}
+
+// Resolves to the trimmed name, or null when cancelled or left empty.
+export function promptForName({ title, initialValue = "", confirmLabel }) {
+  const dialog = document.createElement("dialog");
+  dialog.className = "close-dirty-dialog name-prompt-dialog";
+  dialog.setAttribute("aria-label", title);
+
+  const form = document.createElement("form");
+  form.className = "close-dirty-dialog-body";
+
+  const heading = document.createElement("h3");
+  heading.textContent = title;
+
+  const input = document.createElement("input");
+  input.type = "text";
+  input.className = "name-prompt-input";
+  input.value = initialValue;
+  input.spellcheck = false;
+
+  const actions = document.createElement("div");
+  actions.className = "close-dirty-dialog-actions";
+
+  const cancelButton = buildButton("Cancel", "cancel", "close-dirty-btn");
+  const confirmButton = buildButton(
+    confirmLabel,
+    "confirm",
+    "close-dirty-btn close-dirty-btn-primary",
+  );
+  confirmButton.type = "submit";
+
+  actions.append(cancelButton, confirmButton);
+  form.append(heading, input, actions);
+  dialog.append(form);
+  document.body.appendChild(dialog);
+
+  return new Promise((resolve) => {
+    let settled = false;
+
+    function finish(value) {
+      if (settled) {
+        return;
+      }
+      settled = true;
+      try {
+        dialog.close();
+      } catch {
+        // no-op: dialog may already be closed.
+      }
+      dialog.remove();
+      resolve(value);
+    }
+
+    dialog.addEventListener("cancel", (event) => {
+      event.preventDefault();
+      finish(null);
+    });
+
+    cancelButton.addEventListener("click", () => finish(null));
+
+    form.addEventListener("submit", (event) => {
+      event.preventDefault();
+      finish(input.value.trim() || null);
+    });
+
+    if (typeof dialog.showModal === "function") {
+      dialog.showModal();
+      input.focus();
+      // Select the stem so typing replaces the name but keeps the extension.
+      const dot = initialValue.lastIndexOf(".");
+      input.setSelectionRange(0, dot > 0 ? dot : initialValue.length);
+      return;
+    }
+
+    // Fallback for environments without <dialog>.
+    const value = window.prompt(title, initialValue);
+    finish(value?.trim() || null);
+  });
+}
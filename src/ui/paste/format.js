@@ -14,6 +14,14 @@ const COMPOSE_ROOT_KEYS = new Set([
   "configs",
 ]);
 
+// A pipe table delimiter row such as `| --- | :-: |`.
+const MARKDOWN_TABLE_DELIMITER_ROW =
+  /(^|\n)[ \t>]*\|?[ \t]*:?-+:?[ \t]*(\|[ \t]*:?-+:?[ \t]*)+\|?[ \t]*(\r?\n|$)/;
+
+function looksLikeMarkdownTable(text) {
+  return MARKDOWN_TABLE_DELIMITER_ROW.test(text);
+}
+
 function looksLikeJson(text) {
   return /^\s*[[{]/.test(text);
 }
@@ -62,6 +70,10 @@ export function detectStructuredPasteKind({ activePath, text }) {
     return "csv";
   }
 
+  if (looksLikeMarkdownTable(text)) {
+    return "markdown";
+  }
+
   if (looksLikeJson(text)) {
     return "json";
   }
@@ -15,6 +15,9 @@ import {
   renderMarkdown,
   renderMermaidDiagrams,
 } from "./markdown-renderer.js";
+import { describeLargeFileWindow } from "./large-file-controller.js";
+import { isTableViewActive } from "./table-controller.js";
+import { describeTaskProgress } from "./task-progress-controller.js";
 
 export function createUiRenderer({
   state,
@@ -75,6 +78,9 @@ export function createUiRenderer({
     if (el.unifiedDiff) {
       el.unifiedDiff.classList.toggle("hidden", state.activeKind !== "diff");
     }
+    if (el.tableView) {
+      el.tableView.classList.toggle("hidden", !isTableViewActive(state));
+    }
 
     if (state.activeKind === "diff") {
       el.editor.classList.add("hidden");
@@ -90,9 +96,17 @@ export function createUiRenderer({
       return;
     }
 
+    if (isTableViewActive(state)) {
+      el.editor.classList.add("hidden");
+      el.preview.classList.add("hidden");
+      codeJarController.detach();
+      return;
+    }
+
     if (
       state.activeKind === "markdown" &&
-      state.markdownViewMode === "preview"
+      state.markdownViewMode === "preview" &&
+      !state.activeLargeFile
     ) {
       el.editor.classList.add("hidden");
       el.preview.classList.remove("hidden");
@@ -110,13 +124,18 @@ export function createUiRenderer({
 
     if (
       state.activeKind === "code" ||
+      state.activeLargeFile ||
       (state.activeKind === "markdown" &&
         state.markdownViewMode === "edit" &&
         !shouldUsePlainTextareaEditor(state))
     ) {
       el.editor.classList.add("hidden");
       el.preview.classList.add("hidden");
-      const ext = fileLanguageKey(state.activePath);
+      const ext = fileLanguageKey(state.activePath, state.activeLanguage);
+      codeJarController.setReadOnly(state.activeWritable === false);
+      codeJarController.setFirstLineNumber(
+        state.activeLargeFile ? state.largeFileStartLine : 1,
+      );
       codeJarController.attach(ext);
       codeJarController.syncContent(state.content);
       if (shouldFocusEditor) {
@@ -127,6 +146,7 @@ export function createUiRenderer({
 
     el.preview.classList.add("hidden");
     el.editor.classList.remove("hidden");
+    el.editor.readOnly = state.activeWritable === false;
     codeJarController.detach();
 
     if (el.editor.value !== state.content) {
@@ -141,14 +161,21 @@ export function createUiRenderer({
   }
 
   function renderStatusBar() {
+    // Always shown for large files, which say which lines are loaded.
     const visible =
-      state.statusBarVisible &&
+      (state.statusBarVisible || Boolean(state.activeLargeFile)) &&
       hasActiveContent(state) &&
       state.activeKind !== "diff";
     el.statusBar.classList.toggle("hidden", !visible);
     if (visible) {
       const lines = state.content.split("\n").length;
-      el.statusBarLines.textContent = `${lines} line${lines !== 1 ? "s" : ""}`;
+      el.statusBarLines.textContent = state.activeLargeFile
+        ? describeLargeFileWindow(state)
+        : `${lines} line${lines !== 1 ? "s" : ""}`;
+      el.statusBarTasks.textContent =
+        state.taskProgress?.path === state.activePath
+          ? describeTaskProgress(state.taskProgress)
+          : "";
     }
   }
 
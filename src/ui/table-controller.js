@@ -0,0 +1,279 @@
+import {
+  describeWriteError,
+  fileVersionFor,
+  isWriteConflict,
+  recordFileVersion,
+} from "../files/versions.js";
+import { fileExtension } from "../utils/app-utils.js";
+import { escapeHtml } from "./html-utils.js";
+import { showToast } from "./toast.js";
+
+const TABLE_EXTENSIONS = new Set(["csv", "tsv", "tab"]);
+const FILTER_DEBOUNCE_MS = 200;
+export const TABLE_PAGE_SIZE = 200;
+
+const DELIMITER_NAMES = {
+  ",": "comma",
+  "\t": "tab",
+  ";": "semicolon",
+  "|": "pipe",
+};
+
+export function isTableFile(path) {
+  return TABLE_EXTENSIONS.has(fileExtension(path));
+}
+
+export function isTableViewActive(state) {
+  return (
+    Boolean(state.tableView) &&
+    state.activeKind === "code" &&
+    isTableFile(state.activePath)
+  );
+}
+
+// Clicking a header sorts ascending, then descending, then restores the
+// file order.
+export function nextSort(sort, column) {
+  if (sort.column !== column) return { column, descending: false };
+  if (!sort.descending) return { column, descending: true };
+  return { column: null, descending: false };
+}
+
+export function describeTablePage(page) {
+  const delimiter =
+    DELIMITER_NAMES[page.dialect.delimiter] ?? page.dialect.delimiter;
+  const rows =
+    page.matchedRows === page.totalRows
+      ? `${page.totalRows} rows`
+      : `${page.matchedRows} matches in ${page.totalRows} rows`;
+  if (page.rows.length === 0) return `${rows} · ${delimiter}`;
+  const first = page.offset + 1;
+  const last = page.offset + page.rows.length;
+  return `${first}–${last} of ${rows} · ${delimiter}`;
+}
+
+export function buildTableHtml(page, sort) {
+  const headers = page.columns
+    .map((column, index) => {
+      const arrow =
+        sort.column === index ? (sort.descending ? " ▼" : " ▲") : "";
+      return `<th class="table-col-${column.kind}" data-column="${index}" title="${escapeHtml(column.kind)}">${escapeHtml(column.name)}${arrow}</th>`;
+    })
+    .join("");
+  const rows = page.rows
+    .map((row) => {
+      const cells = row.cells
+        .map(
+          (cell, index) =>
+            `<td class="table-col-${page.columns[index]?.kind ?? "string"}" data-column="${index}">${escapeHtml(cell)}</td>`,
+        )
+        .join("");
+      return `<tr data-row="${row.index}"><th class="table-row-number">${row.index + 1}</th>${cells}</tr>`;
+    })
+    .join("");
+  return `<table class="table-grid"><thead><tr><th class="table-row-number"></th>${headers}</tr></thead><tbody>${rows}</tbody></table>`;
+}
+
+/**
+ * Write one edited cell back to the file. Refused while the tab has unsaved
+ * text edits, since those would be lost when the file is reloaded. Returns
+ * a message to show when nothing was written.
+ */
+export async function saveTableCell({
+  state,
+  invoke,
+  path,
+  version,
+  row,
+  column,
+  value,
+}) {
+  if (state.activePath === path && state.isDirty) {
+    return "Save the file before editing the table";
+  }
+  try {
+    const result = await invoke("update_table_cell", {
+      path,
+      row,
+      column,
+      value,
+      expectedVersion: version,
+    });
+    recordFileVersion(path, result?.version);
+    return null;
+  } catch (error) {
+    return isWriteConflict(error)
+      ? "The file changed on disk; the table was reloaded"
+      : describeWriteError(error);
+  }
+}
+
+export function createTableController({ state, el, invoke, onCellSaved }) {
+  let path = null;
+  let sort = { column: null, descending: false };
+  let filter = "";
+  let offset = 0;
+  let page = null;
+  let loadedKey = null;
+  let filterTimer = null;
+  let toolbar = null;
+
+  function ensureLayout() {
+    if (toolbar) return;
+    el.tableView.innerHTML = `<div class="table-toolbar">
+  <input class="table-filter" type="search" placeholder="Filter rows" autocomplete="off" spellcheck="false">
+  <span class="table-summary"></span>
+  <button class="table-page-btn" type="button" data-page="-1" aria-label="Previous page">&#x2039;</button>
+  <button class="table-page-btn" type="button" data-page="1" aria-label="Next page">&#x203A;</button>
+</div>
+<div class="table-scroll"></div>`;
+    toolbar = {
+      filter: el.tableView.querySelector(".table-filter"),
+      summary: el.tableView.querySelector(".table-summary"),
+      prev: el.tableView.querySelector('[data-page="-1"]'),
+      next: el.tableView.querySelector('[data-page="1"]'),
+      scroll: el.tableView.querySelector(".table-scroll"),
+    };
+    toolbar.filter.addEventListener("input", onFilterInput);
+    toolbar.prev.addEventListener("click", () => turnPage(-1));
+    toolbar.next.addEventListener("click", () => turnPage(1));
+    toolbar.scroll.addEventListener("click", onGridClick);
+    toolbar.scroll.addEventListener("dblclick", onGridDoubleClick);
+  }
+
+  function queryKey() {
+    return JSON.stringify([path, fileVersionFor(path), sort, filter, offset]);
+  }
+
+  async function load() {
+    const requestPath = path;
+    const key = queryKey();
+    try {
+      const result = await invoke("read_table", {
+        path: requestPath,
+        query: {
+          offset,
+          limit: TABLE_PAGE_SIZE,
+          sortColumn: sort.column,
+          sortDescending: sort.descending,
+          filter: filter || null,
+        },
+      });
+      if (requestPath !== path) return;
+      page = result;
+      loadedKey = key;
+      renderPage();
+    } catch (error) {
+      if (requestPath !== path) return;
+      page = null;
+      loadedKey = key;
+      toolbar.summary.textContent = "";
+      toolbar.scroll.innerHTML = `<p class="table-message">${escapeHtml(String(error))}</p>`;
+    }
+  }
+
+  function renderPage() {
+    toolbar.summary.textContent = describeTablePage(page);
+    toolbar.prev.disabled = page.offset === 0;
+    toolbar.next.disabled = page.offset + page.rows.length >= page.matchedRows;
+    toolbar.scroll.innerHTML = buildTableHtml(page, sort);
+  }
+
+  // Reload when the file, its version on disk or the query changed since
+  // the last page was fetched.
+  async function refresh() {
+    if (!isTableViewActive(state)) return;
+    ensureLayout();
+    if (state.activePath !== path) {
+      path = state.activePath;
+      sort = { column: null, descending: false };
+      filter = "";
+      offset = 0;
+      toolbar.filter.value = "";
+    }
+    if (queryKey() === loadedKey) return;
+    await load();
+  }
+
+  function onFilterInput() {
+    clearTimeout(filterTimer);
+    filterTimer = setTimeout(() => {
+      filter = toolbar.filter.value.trim();
+      offset = 0;
+      load();
+    }, FILTER_DEBOUNCE_MS);
+  }
+
+  function turnPage(direction) {
+    if (!page) return;
+    const next = offset + direction * TABLE_PAGE_SIZE;
+    if (next < 0 || next >= page.matchedRows) return;
+    offset = next;
+    toolbar.scroll.scrollTop = 0;
+    load();
+  }
+
+  function onGridClick(e) {
+    const header = e.target.closest?.("thead th[data-column]");
+    if (!header) return;
+    sort = nextSort(sort, Number(header.dataset.column));
+    offset = 0;
+    load();
+  }
+
+  function onGridDoubleClick(e) {
+    const cell = e.target.closest?.("tbody td[data-column]");
+    if (!cell || !page || cell.querySelector("input")) return;
+    const row = Number(cell.closest("tr").dataset.row);
+    const column = Number(cell.dataset.column);
+    const original = cell.textContent;
+    const input = document.createElement("input");
+    input.className = "table-cell-input";
+    input.value = original;
+    cell.textContent = "";
+    cell.appendChild(input);
+    input.focus();
+    input.select();
+
+    let done = false;
+    async function finish(commit) {
+      if (done) return;
+      done = true;
+      const value = input.value;
+      if (!commit || value === original) {
+        cell.textContent = original;
+        return;
+      }
+      cell.textContent = value;
+      const failure = await saveTableCell({
+        state,
+        invoke,
+        path,
+        version: page.version,
+        row,
+        column,
+        value,
+      });
+      if (failure) {
+        showToast(failure);
+      } else {
+        await onCellSaved?.(path);
+      }
+      loadedKey = null;
+      await refresh();
+    }
+
+    input.addEventListener("keydown", (event) => {
+      if (event.key === "Enter") {
+        event.preventDefault();
+        finish(true);
+      } else if (event.key === "Escape") {
+        event.preventDefault();
+        finish(false);
+      }
+    });
+    input.addEventListener("blur", () => finish(true));
+  }
+
+  return { refresh };
+}
@@ -0,0 +1,71 @@
+import { fileVersionFor } from "../files/versions.js";
+
+export function describeTaskProgress(progress) {
+  if (!progress || progress.total === 0) return "";
+  return `${progress.done}/${progress.total} tasks`;
+}
+
+function samePath(a, b) {
+  return (
+    typeof a === "string" &&
+    typeof b === "string" &&
+    a.replaceAll("\\", "/").replace(/\/+$/, "") ===
+      b.replaceAll("\\", "/").replace(/\/+$/, "")
+  );
+}
+
+/**
+ * Keep `state.taskProgress` in step with the active Markdown file. Counts
+ * come from the backend: read when the file or its version changes, and
+ * pushed by the task-progress event after saves and changes on disk.
+ * Pushed paths are canonical, so they are matched against the canonical
+ * path `read_task_list` returned for the active file as well.
+ */
+export function createTaskProgressController({ state, invoke, onChange }) {
+  let loadedKey = null;
+  let loadedPath = null;
+  let canonicalPath = null;
+
+  function setProgress(progress) {
+    state.taskProgress = progress;
+    onChange?.();
+  }
+
+  async function refresh() {
+    const path = state.activeKind === "markdown" ? state.activePath : null;
+    const key = path ? JSON.stringify([path, fileVersionFor(path)]) : null;
+    if (key === loadedKey) return;
+    loadedKey = key;
+    if (!path) {
+      if (state.taskProgress) setProgress(null);
+      return;
+    }
+    try {
+      const list = await invoke("read_task_list", { path });
+      if (state.activePath !== path) return;
+      loadedPath = path;
+      canonicalPath = list.path ?? path;
+      setProgress({ path, done: list.done, total: list.total });
+    } catch {
+      if (state.activePath === path) setProgress(null);
+    }
+  }
+
+  function isActiveFile(path) {
+    return (
+      samePath(path, state.activePath) ||
+      (samePath(loadedPath, state.activePath) && samePath(path, canonicalPath))
+    );
+  }
+
+  function handleProgress(progress) {
+    if (!progress || !isActiveFile(progress.path)) return;
+    setProgress({
+      path: state.activePath,
+      done: progress.done,
+      total: progress.total,
+    });
+  }
+
+  return { refresh, handleProgress };
+}
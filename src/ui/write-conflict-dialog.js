@@ -0,0 +1,89 @@
+function buildButton(label, value, className = "") {
+  const button = document.createElement("button");
+  button.type = "button";
+  button.dataset.value = value;
+  button.textContent = label;
+  button.className = className;
+  return button;
+}
+
+// Ask what to do when a save was refused because the file changed on disk.
+// Resolves to "overwrite", "reload" or "cancel".
+export function promptToResolveWriteConflict(label) {
+  const dialog = document.createElement("dialog");
+  dialog.className = "close-dirty-dialog";
+  dialog.setAttribute("aria-label", "File changed on disk");
+
+  const body = document.createElement("div");
+  body.className = "close-dirty-dialog-body";
+
+  const title = document.createElement("h3");
+  title.textContent = `"${label}" changed outside Teex.`;
+
+  const message = document.createElement("p");
+  message.textContent =
+    "Overwrite it with your edits, or reload it and discard them.";
+
+  const actions = document.createElement("div");
+  actions.className = "close-dirty-dialog-actions";
+
+  const cancelButton = buildButton("Cancel", "cancel", "close-dirty-btn");
+  const reloadButton = buildButton("Reload", "reload", "close-dirty-btn");
+  const overwriteButton = buildButton(
+    "Overwrite",
+    "overwrite",
+    "close-dirty-btn close-dirty-btn-primary",
+  );
+
+  actions.append(cancelButton, reloadButton, overwriteButton);
+  body.append(title, message, actions);
+  dialog.append(body);
+  document.body.appendChild(dialog);
+
+  return new Promise((resolve) => {
+    let settled = false;
+
+    function finish(value) {
+      if (settled) {
+        return;
+      }
+      settled = true;
+      try {
+        dialog.close();
+      } catch {
+        // no-op: dialog may already be closed.
+      }
+      dialog.remove();
+      resolve(value);
+    }
+
+    dialog.addEventListener("cancel", (event) => {
+      event.preventDefault();
+      finish("cancel");
+    });
+
+    actions.addEventListener("click", (event) => {
+      const button = event.target.closest("button[data-value]");
+      if (!button) {
+        return;
+      }
+      finish(button.dataset.value);
+    });
+
+    if (typeof dialog.showModal === "function") {
+      dialog.showModal();
+      cancelButton.focus();
+      return;
+    }
+
+    // Fallback for environments without <dialog>.
+    if (window.confirm(`Overwrite "${label}" with your edits?`)) {
+      finish("overwrite");
+      return;
+    }
+    const shouldReload = window.confirm(
+      `Reload "${label}" and discard your edits?`,
+    );
+    finish(shouldReload ? "reload" : "cancel");
+  });
+}
@@ -30,7 +30,23 @@ function isDockerfileLike(name) {
   );
 }
 
-export function fileLanguageKey(path) {
+// Backend language ids whose highlighter is keyed by a different extension.
+const LANGUAGE_ID_KEYS = new Map([
+  ["javascript", "js"],
+  ["typescript", "ts"],
+  ["python", "py"],
+  ["rust", "rs"],
+  ["ruby", "rb"],
+  ["kotlin", "kt"],
+  ["csharp", "cs"],
+  ["shellscript", "sh"],
+  ["markdown", "md"],
+]);
+
+export function fileLanguageKey(path, language = null) {
+  if (language && language !== "plaintext") {
+    return LANGUAGE_ID_KEYS.get(language) ?? language;
+  }
   if (!path) return null;
   const name = baseName(path);
   if (isDockerfileLike(name)) return "dockerfile";
@@ -3,6 +3,7 @@ import test from "node:test";
 
 import {
   buildWatchedProjectFileSignature,
+  collectFollowedLogPaths,
   collectWatchedProjectFilePaths,
   createExternalFileWatchController,
 } from "../../src/app/external-file-watch-controller.js";
@@ -161,3 +162,91 @@ test("handleProjectFileChanged reloads active clean file and re-renders", async
     { message: "Reloaded a.md (changed outside Teex)", isError: false },
   ]);
 });
+
+test("collectFollowedLogPaths keeps log tabs that are not large files", () => {
+  assert.deepEqual(
+    collectFollowedLogPaths({
+      openFiles: [
+        { path: "/b.log", language: "log" },
+        { path: "/a.md", language: "markdown" },
+        { path: "/huge.log", language: "log", largeFile: { lineCount: 1 } },
+        { path: "/a.log", language: "log" },
+      ],
+    }),
+    ["/a.log", "/b.log"],
+  );
+  assert.deepEqual(
+    collectFollowedLogPaths({
+      openFiles: [],
+      activePath: "/solo.log",
+      activeLanguage: "log",
+      activeLargeFile: null,
+    }),
+    ["/solo.log"],
+  );
+});
+
+test("log tabs are followed and appended output updates the tab", async () => {
+  const state = {
+    openFiles: [
+      { path: "/app.log", language: "log", content: "one\n", isDirty: false },
+    ],
+    activePath: "/app.log",
+    content: "one\n",
+    savedContent: "one\n",
+    isDirty: false,
+  };
+  const calls = [];
+  let renderCalls = 0;
+
+  const controller = createExternalFileWatchController({
+    state,
+    invoke: async (command, args) => {
+      calls.push({ command, args });
+      if (command === "follow_file") {
+        return { path: "/real/app.log", offset: 4 };
+      }
+      if (command === "read_text_file") {
+        throw new Error("followed logs should not be re-read");
+      }
+    },
+    baseName: (value) => value.split("/").pop(),
+    hasTabSession: () => state.openFiles.length > 0,
+    applyFilePayload: () => {},
+    render: () => {
+      renderCalls += 1;
+    },
+    updateMenuState: () => {},
+    setStatus: () => {},
+    confirmReloadExternalChange: async () => true,
+  });
+
+  controller.syncWatchedProjectFiles();
+  await new Promise((resolve) => setTimeout(resolve, 0));
+  assert.deepEqual(calls, [
+    { command: "follow_file", args: { path: "/app.log", fromEnd: true } },
+    { command: "watch_project_files", args: { paths: ["/app.log"] } },
+  ]);
+
+  await controller.handleProjectFileChanged("/app.log");
+  await controller.handleFileAppended({
+    path: "/real/app.log",
+    text: "two\n",
+    offset: 8,
+    truncated: false,
+    rotated: false,
+  });
+  assert.equal(state.openFiles[0].content, "one\ntwo\n");
+  assert.equal(state.content, "one\ntwo\n");
+  assert.equal(state.savedContent, "one\ntwo\n");
+  assert.equal(renderCalls, 1);
+
+  state.openFiles = [];
+  state.activePath = null;
+  controller.syncWatchedProjectFiles();
+  await new Promise((resolve) => setTimeout(resolve, 0));
+  assert.deepEqual(calls.slice(2), [
+    { command: "stop_following_file", args: { path: "/real/app.log" } },
+    { command: "watch_project_files", args: { paths: [] } },
+  ]);
+});
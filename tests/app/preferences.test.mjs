@@ -1,7 +1,10 @@
 import assert from "node:assert/strict";
 import test from "node:test";
 
-import { toggleModifiedOnly } from "../../src/app/preferences.js";
+import {
+  toggleIgnoredFiles,
+  toggleModifiedOnly,
+} from "../../src/app/preferences.js";
 
 const noop = () => {};
 const noopInvoke = () => Promise.resolve();
@@ -75,3 +78,33 @@ test("toggleModifiedOnly leaves collapsedFolders unchanged when turning off with
   assert.equal(state.filterModifiedOnly, false);
   assert.equal(state.collapsedFolders.size, 1);
 });
+
+test("toggleIgnoredFiles persists, syncs the menu and refreshes entries", () => {
+  const state = { showIgnoredFiles: false };
+  const ls = { store: {} };
+  global.localStorage = {
+    setItem: (k, v) => {
+      ls.store[k] = v;
+    },
+  };
+  const invokeCalls = [];
+  const invoke = (command, args) => {
+    invokeCalls.push({ command, args });
+    return Promise.resolve();
+  };
+  let refreshed = 0;
+
+  toggleIgnoredFiles(state, invoke, () => {
+    refreshed += 1;
+  });
+
+  assert.equal(state.showIgnoredFiles, true);
+  assert.equal(ls.store["teex-show-ignored-files"], "true");
+  assert.deepEqual(invokeCalls, [
+    {
+      command: "set_show_ignored_files_checked",
+      args: { checked: true },
+    },
+  ]);
+  assert.equal(refreshed, 1);
+});
@@ -199,7 +199,11 @@ test("openFolderEntryInTabs snapshots active file before opening another tab", a
     content: "# Current",
     savedContent: "# Current",
     kind: "markdown",
+    language: null,
     writable: true,
+    encoding: null,
+    lineEnding: null,
+    hasBom: null,
     isDirty: true,
     markdownViewMode: "preview",
     scrollState: {
@@ -0,0 +1,150 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import {
+  applyPathRename,
+  createFileOpsController,
+} from "../../src/files/file-ops-controller.js";
+import { fileVersionFor, recordFileVersion } from "../../src/files/versions.js";
+
+function createState(overrides = {}) {
+  return {
+    rootPath: "/repo",
+    openFiles: [],
+    activePath: null,
+    navHistory: [],
+    fileScrollMemory: new Map(),
+    ...overrides,
+  };
+}
+
+test("applyPathRename follows a renamed file", () => {
+  const state = createState({
+    openFiles: [{ path: "/repo/a.md" }, { path: "/repo/b.md" }],
+    activePath: "/repo/a.md",
+    navHistory: ["/repo/b.md", "/repo/a.md"],
+    fileScrollMemory: new Map([["/repo/a.md", { editorScrollTop: 4 }]]),
+  });
+
+  assert.equal(applyPathRename(state, "/repo/a.md", "/repo/c.md"), true);
+  assert.deepEqual(
+    state.openFiles.map((t) => t.path),
+    ["/repo/c.md", "/repo/b.md"],
+  );
+  assert.equal(state.activePath, "/repo/c.md");
+  assert.deepEqual(state.navHistory, ["/repo/b.md", "/repo/c.md"]);
+  assert.deepEqual([...state.fileScrollMemory.keys()], ["/repo/c.md"]);
+});
+
+test("applyPathRename moves paths under a renamed folder but not siblings", () => {
+  const state = createState({
+    openFiles: [
+      { path: "/repo/docs/a.md" },
+      { path: "/repo/docs-old/b.md" },
+      { path: null },
+    ],
+  });
+
+  assert.equal(applyPathRename(state, "/repo/docs", "/repo/guide"), true);
+  assert.deepEqual(
+    state.openFiles.map((t) => t.path),
+    ["/repo/guide/a.md", "/repo/docs-old/b.md", null],
+  );
+  assert.equal(applyPathRename(state, "/repo/other", "/repo/x"), false);
+});
+
+test("applyPathRename carries recorded file versions to the new paths", () => {
+  recordFileVersion("/repo/notes.md", "1-aa");
+  recordFileVersion("/repo/src/a.rs", "2-bb");
+  recordFileVersion("/repo/src/nested/b.rs", "3-cc");
+  recordFileVersion("/repo/src-old/c.rs", "4-dd");
+
+  applyPathRename(createState(), "/repo/notes.md", "/repo/docs/notes.md");
+  applyPathRename(createState(), "/repo/src", "/repo/lib");
+
+  assert.equal(fileVersionFor("/repo/docs/notes.md"), "1-aa");
+  assert.equal(fileVersionFor("/repo/notes.md"), null);
+  assert.equal(fileVersionFor("/repo/lib/a.rs"), "2-bb");
+  assert.equal(fileVersionFor("/repo/lib/nested/b.rs"), "3-cc");
+  assert.equal(fileVersionFor("/repo/src/a.rs"), null);
+  assert.equal(fileVersionFor("/repo/src-old/c.rs"), "4-dd");
+});
+
+test("renamePath sends the new name and reports backend errors", async () => {
+  const state = createState();
+  const calls = [];
+  const statuses = [];
+  const controller = createFileOpsController({
+    state,
+    invoke: async (command, args) => {
+      calls.push([command, args]);
+      throw "\"b.md\" already exists";
+    },
+    baseName: (path) => path.split("/").pop(),
+    promptForName: async ({ initialValue }) => {
+      assert.equal(initialValue, "a.md");
+      return "b.md";
+    },
+    openFolderEntryInTabs: async () => {},
+    markSidebarTreeDirty: () => {},
+    render: () => {},
+    updateMenuState: () => {},
+    setStatus: (message, isError) => statuses.push([message, isError]),
+  });
+
+  await controller.renamePath("/repo/a.md");
+
+  assert.deepEqual(calls, [
+    ["rename_path", { root: "/repo", path: "/repo/a.md", newName: "b.md" }],
+  ]);
+  assert.deepEqual(statuses, [
+    ['Unable to rename: "b.md" already exists', true],
+  ]);
+});
+
+test("createFile opens the created file", async () => {
+  const state = createState();
+  const opened = [];
+  const controller = createFileOpsController({
+    state,
+    invoke: async (_command, { dir, name }) => `${dir}/${name}`,
+    baseName: (path) => path.split("/").pop(),
+    promptForName: async () => "notes.md",
+    openFolderEntryInTabs: async (path) => opened.push(path),
+    markSidebarTreeDirty: () => {},
+    render: () => {},
+    updateMenuState: () => {},
+    setStatus: () => {},
+  });
+
+  await controller.createFile("/repo/docs");
+
+  assert.deepEqual(opened, ["/repo/docs/notes.md"]);
+});
+
+test("undoLastOperation reports what was undone", async () => {
+  const results = [
+    { kind: "restore", path: "/repo/a.md", from: null },
+    null,
+  ];
+  const statuses = [];
+  const controller = createFileOpsController({
+    state: createState(),
+    invoke: async () => results.shift(),
+    baseName: (path) => path.split("/").pop(),
+    promptForName: async () => null,
+    openFolderEntryInTabs: async () => {},
+    markSidebarTreeDirty: () => {},
+    render: () => {},
+    updateMenuState: () => {},
+    setStatus: (message) => statuses.push(message),
+  });
+
+  await controller.undoLastOperation();
+  await controller.undoLastOperation();
+
+  assert.deepEqual(statuses, [
+    'Restored "a.md" from the trash',
+    "Nothing to undo",
+  ]);
+});
@@ -0,0 +1,28 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import {
+  describeWriteError,
+  fileVersionFor,
+  isWriteConflict,
+  recordFileVersion,
+} from "../../src/files/versions.js";
+
+test("recordFileVersion stores and clears versions per path", () => {
+  recordFileVersion("/tmp/versions-a.md", "1-abc");
+  assert.equal(fileVersionFor("/tmp/versions-a.md"), "1-abc");
+
+  recordFileVersion("/tmp/versions-a.md", undefined);
+  assert.equal(fileVersionFor("/tmp/versions-a.md"), null);
+  assert.equal(fileVersionFor("/tmp/never-seen.md"), null);
+});
+
+test("describeWriteError handles structured and plain errors", () => {
+  const conflict = { kind: "conflict", message: "File was changed outside Teex" };
+  assert.equal(describeWriteError(conflict), "File was changed outside Teex");
+  assert.equal(isWriteConflict(conflict), true);
+
+  assert.equal(describeWriteError("Unable to write file"), "Unable to write file");
+  assert.equal(isWriteConflict({ kind: "io", message: "nope" }), false);
+  assert.equal(isWriteConflict("nope"), false);
+});
@@ -78,7 +78,10 @@ function mockCodeEditorController() {
   };
 }
 
-function setup(stateOverrides = {}, { codeEditorController = null } = {}) {
+function setup(
+  stateOverrides = {},
+  { codeEditorController = null, largeFileController = null } = {},
+) {
   const state = {
     activePath: null,
     activeKind: "markdown",
@@ -105,6 +108,7 @@ function setup(stateOverrides = {}, { codeEditorController = null } = {}) {
     state,
     el,
     codeEditorController,
+    largeFileController,
   });
   if (origAdd) globalThis.document.addEventListener = origAdd;
   return { state, el, controller, codeEditorController };
@@ -349,3 +353,39 @@ test("clearing query in code view calls clearSearch", () => {
   typeQuery(el, "");
   assert.ok(codeCtr.calls.some((c) => c.method === "clearSearch"));
 });
+
+// --- Large files ---
+
+test("large files are searched on disk and matches revealed in turn", async () => {
+  const matches = [
+    { lineNumber: 10, lineText: "a needle", ranges: [[2, 8]] },
+    { lineNumber: 90_000, lineText: "needle", ranges: [[0, 6]] },
+  ];
+  const revealed = [];
+  const largeFileController = {
+    isActive: () => true,
+    search: async () => matches,
+    revealMatch: async (match) => revealed.push(match.lineNumber),
+  };
+  const { el, controller } = setup(
+    {
+      activePath: "/logs/big.log",
+      activeKind: "text",
+      activeLargeFile: { lineCount: 100_000 },
+      content: "preview",
+    },
+    { codeEditorController: mockCodeEditorController(), largeFileController },
+  );
+  controller.open();
+  assert.equal(el.editorBackdrop.classList.contains("hidden"), true);
+
+  typeQuery(el, "needle");
+  await new Promise((resolve) => setTimeout(resolve, 0));
+  assert.equal(el.findCount.textContent, "1 of 2");
+  assert.deepEqual(revealed, [10]);
+
+  const [next] = el.findNext._listeners.click;
+  next();
+  assert.equal(el.findCount.textContent, "2 of 2");
+  assert.deepEqual(revealed, [10, 90_000]);
+});
@@ -3,6 +3,7 @@ import test from "node:test";
 
 import {
   findOpenFileContent,
+  folderAtPoint,
   isOverTabBar,
   shouldStartSidebarDrag,
 } from "../../src/sidebar/drag.js";
@@ -67,3 +68,21 @@ test("isOverTabBar returns false when tab bar is hidden", () => {
   };
   assert.equal(isOverTabBar(tabBar, 400, 18), false);
 });
+
+test("folderAtPoint returns the folder row under the cursor", () => {
+  const folder = (folderPath, top) => ({
+    dataset: { folderPath },
+    getBoundingClientRect: () => ({
+      left: 0,
+      right: 200,
+      top,
+      bottom: top + 24,
+    }),
+  });
+  const docs = folder("docs", 0);
+  const src = folder("src", 24);
+  const projectList = { querySelectorAll: () => [docs, src] };
+
+  assert.equal(folderAtPoint(projectList, 50, 30), src);
+  assert.equal(folderAtPoint(projectList, 50, 100), null);
+});
@@ -252,6 +252,20 @@ test("renderTreeHtml renders no git attributes when gitStatusMap is empty", () =
   assert.doesNotMatch(html, /git-badge/);
 });
 
+test("renderTreeHtml shows a type badge for binary files", () => {
+  const tree = buildEntryTree([
+    { path: "/root/logo.png", relPath: "logo.png", binaryType: "png" },
+    { path: "/root/a.md", relPath: "a.md", binaryType: null },
+  ]);
+  const html = renderTreeHtml(tree, 0, new Set(), { "logo.png": "?" });
+
+  assert.match(
+    html,
+    /data-binary-type="png"><span class="project-item-label">logo\.png<\/span><span class="type-badge">png<\/span><span class="git-badge">/,
+  );
+  assert.equal(html.match(/type-badge/g).length, 1);
+});
+
 test("renderTreeHtml wraps expanded folder children in folder-children div", () => {
   const tree = buildEntryTree([
     { path: "/root/docs/guide.md", relPath: "docs/guide.md" },
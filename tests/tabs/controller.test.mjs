@@ -360,6 +360,9 @@ test("closeTab saves and closes inactive dirty file when user confirms save", as
           kind: "markdown",
           content: "# b",
           writable: true,
+          lineEnding: "crlf",
+          hasBom: true,
+          encoding: "windows-1252",
           isDirty: true,
           markdownViewMode: "edit",
           scrollState: { editorScrollTop: 0, previewScrollTop: 0 },
@@ -382,7 +385,14 @@ test("closeTab saves and closes inactive dirty file when user confirms save", as
   await controller.closeTab(1);
 
   assert.equal(writeCalls.length, 1);
-  assert.deepEqual(writeCalls[0], { path: "/tmp/b.md", content: "# b" });
+  assert.deepEqual(writeCalls[0], {
+    path: "/tmp/b.md",
+    content: "# b",
+    lineEnding: "crlf",
+    hasBom: true,
+    encoding: "windows-1252",
+    expectedVersion: null,
+  });
   assert.equal(state.openFiles.length, 1);
   assert.equal(state.openFiles[0].path, "/tmp/a.md");
 });
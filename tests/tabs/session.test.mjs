@@ -37,9 +37,13 @@ test("normalizeTransferTab sanitizes invalid and partial tab records", () => {
   assert.deepEqual(normalizeTransferTab({ path: "" }), {
     path: "",
     kind: "text",
+    language: null,
     content: "",
     savedContent: "",
     writable: true,
+    encoding: null,
+    lineEnding: null,
+    hasBom: null,
     isDirty: false,
     markdownViewMode: "edit",
     scrollState: { editorScrollTop: 0, previewScrollTop: 0 },
@@ -51,9 +55,13 @@ test("normalizeTransferTab sanitizes invalid and partial tab records", () => {
     {
       path: null,
       kind: "text",
+      language: null,
       content: "draft",
       savedContent: "draft",
       writable: true,
+      encoding: null,
+      lineEnding: null,
+      hasBom: null,
       isDirty: false,
       markdownViewMode: "edit",
       scrollState: { editorScrollTop: 0, previewScrollTop: 0 },
@@ -64,9 +72,13 @@ test("normalizeTransferTab sanitizes invalid and partial tab records", () => {
   assert.deepEqual(normalizeTransferTab({ kind: "text" }), {
     path: null,
     kind: "text",
+    language: null,
     content: "",
     savedContent: "",
     writable: true,
+    encoding: null,
+    lineEnding: null,
+    hasBom: null,
     isDirty: false,
     markdownViewMode: "edit",
     scrollState: { editorScrollTop: 0, previewScrollTop: 0 },
@@ -84,9 +96,13 @@ test("normalizeTransferTab sanitizes invalid and partial tab records", () => {
     {
       path: "/a.md",
       kind: "markdown",
+      language: null,
       content: "",
       savedContent: "",
       writable: false,
+      encoding: null,
+      lineEnding: null,
+      hasBom: null,
       isDirty: true,
       markdownViewMode: "edit",
       scrollState: {
@@ -99,9 +115,13 @@ test("normalizeTransferTab sanitizes invalid and partial tab records", () => {
   assert.deepEqual(normalizeTransferTab({ path: "/a.bin", kind: "other" }), {
     path: "/a.bin",
     kind: "text",
+    language: null,
     content: "",
     savedContent: "",
     writable: true,
+    encoding: null,
+    lineEnding: null,
+    hasBom: null,
     isDirty: false,
     markdownViewMode: "edit",
     scrollState: {
@@ -120,9 +140,13 @@ test("normalizeTransferTab sanitizes invalid and partial tab records", () => {
     {
       path: "/a.js",
       kind: "code",
+      language: null,
       content: "const x = 1;",
       savedContent: "const x = 1;",
       writable: true,
+      encoding: null,
+      lineEnding: null,
+      hasBom: null,
       isDirty: false,
       markdownViewMode: "edit",
       scrollState: { editorScrollTop: 0, previewScrollTop: 0 },
@@ -256,7 +280,11 @@ test("snapshot helpers return none/single/tabs payloads", () => {
     content: "# One",
     savedContent: "# One",
     kind: "markdown",
+    language: null,
     writable: true,
+    encoding: null,
+    lineEnding: null,
+    hasBom: null,
     isDirty: true,
     markdownViewMode: "edit",
     scrollState: {
@@ -282,7 +310,11 @@ test("snapshot helpers return none/single/tabs payloads", () => {
     content: "draft content",
     savedContent: "draft content",
     kind: "text",
+    language: null,
     writable: true,
+    encoding: null,
+    lineEnding: null,
+    hasBom: null,
     isDirty: true,
     markdownViewMode: "edit",
     scrollState: { editorScrollTop: 0, previewScrollTop: 0 },
@@ -22,7 +22,12 @@ test("buildTabFromPayload initializes clean tab state and markdown mode", () =>
       content: "# a",
       savedContent: "# a",
       kind: "markdown",
+      language: null,
       writable: true,
+      largeFile: null,
+      encoding: null,
+      lineEnding: null,
+      hasBom: null,
       isDirty: false,
       markdownViewMode: "preview",
       scrollState: {
@@ -44,7 +49,12 @@ test("buildTabFromPayload initializes clean tab state and markdown mode", () =>
       content: "a",
       savedContent: "a",
       kind: "text",
+      language: null,
       writable: false,
+      largeFile: null,
+      encoding: null,
+      lineEnding: null,
+      hasBom: null,
       isDirty: false,
       markdownViewMode: "edit",
       scrollState: {
@@ -55,6 +65,19 @@ test("buildTabFromPayload initializes clean tab state and markdown mode", () =>
   );
 });
 
+test("buildTabFromPayload keeps the detected language", () => {
+  const tab = buildTabFromPayload({
+    path: "/bin/deploy",
+    content: "#!/usr/bin/env python3\n",
+    kind: "code",
+    language: "python",
+    writable: true,
+  });
+
+  assert.equal(tab.kind, "code");
+  assert.equal(tab.language, "python");
+});
+
 test("buildUntitledTab creates a clean markdown editor tab", () => {
   assert.deepEqual(buildUntitledTab(), {
     path: null,
@@ -87,7 +110,11 @@ test("snapshotActiveStateAsTab normalizes active file state", () => {
       content: "# Notes",
       savedContent: "# Notes",
       kind: "markdown",
+      language: null,
       writable: true,
+      encoding: null,
+      lineEnding: null,
+      hasBom: null,
       isDirty: true,
       markdownViewMode: "preview",
       scrollState: {
@@ -0,0 +1,150 @@
+import assert from "node:assert/strict";
+import { describe, it, mock } from "node:test";
+
+import {
+  brokenLinkDiagnostics,
+  createDiagnosticsController,
+} from "../../src/ui/diagnostics-controller.js";
+
+function makeMocks(overrides = {}) {
+  const state = {
+    activePath: "/repo/config.yaml",
+    content: "a: [1\n",
+    ...overrides.state,
+  };
+  const diagnostics = [
+    { message: "did not find expected ',' or ']'", line: 2, column: 1 },
+  ];
+  const invoke = mock.fn(async () => overrides.result ?? diagnostics);
+  const codeEditorController = {
+    setDiagnostics: mock.fn(),
+    clearDiagnostics: mock.fn(),
+    isAttached: () => overrides.isAttached ?? true,
+  };
+  return { state, invoke, codeEditorController, diagnostics };
+}
+
+describe("createDiagnosticsController", () => {
+  it("validates structured files with their format kind", async () => {
+    const { state, invoke, codeEditorController, diagnostics } = makeMocks();
+    const ctrl = createDiagnosticsController({
+      state,
+      invoke,
+      codeEditorController,
+    });
+
+    await ctrl.refresh();
+
+    assert.deepEqual(invoke.mock.calls[0].arguments, [
+      "validate_structured_text",
+      { content: "a: [1\n", kind: "yaml", path: "/repo/config.yaml" },
+    ]);
+    assert.deepEqual(
+      codeEditorController.setDiagnostics.mock.calls[0].arguments[0],
+      diagnostics,
+    );
+  });
+
+  it("reuses the last result while the content is unchanged", async () => {
+    const { state, invoke, codeEditorController } = makeMocks();
+    const ctrl = createDiagnosticsController({
+      state,
+      invoke,
+      codeEditorController,
+    });
+
+    await ctrl.refresh();
+    await ctrl.refresh();
+    assert.equal(invoke.mock.callCount(), 1);
+    assert.equal(codeEditorController.setDiagnostics.mock.callCount(), 2);
+
+    state.content = "a: [1]\n";
+    await ctrl.refresh();
+    assert.equal(invoke.mock.callCount(), 2);
+
+    ctrl.invalidate();
+    await ctrl.refresh();
+    assert.equal(invoke.mock.callCount(), 3);
+  });
+
+  it("clears markers for files that are not structured", async () => {
+    const { state, invoke, codeEditorController } = makeMocks({
+      state: { activePath: "/repo/main.rs" },
+    });
+    const ctrl = createDiagnosticsController({
+      state,
+      invoke,
+      codeEditorController,
+    });
+
+    await ctrl.refresh();
+
+    assert.equal(invoke.mock.callCount(), 0);
+    assert.equal(codeEditorController.clearDiagnostics.mock.callCount(), 1);
+  });
+
+  it("ignores results for a file that is no longer active", async () => {
+    const { state, codeEditorController } = makeMocks();
+    const invoke = mock.fn(async () => {
+      state.activePath = "/repo/other.toml";
+      return [];
+    });
+    const ctrl = createDiagnosticsController({
+      state,
+      invoke,
+      codeEditorController,
+    });
+
+    await ctrl.refresh();
+
+    assert.equal(codeEditorController.setDiagnostics.mock.callCount(), 0);
+  });
+});
+
+describe("brokenLinkDiagnostics", () => {
+  it("shows broken Markdown links as warnings", async () => {
+    const report = {
+      links: [
+        { line: 3, column: 5, status: "ok", message: null },
+        { line: 4, column: 1, status: "external", message: null },
+        {
+          line: 7,
+          column: 2,
+          status: "broken",
+          message: "a.md does not exist",
+        },
+      ],
+      broken: 1,
+      external: 1,
+    };
+    const { state, invoke, codeEditorController } = makeMocks({
+      state: { activePath: "/repo/plan.md", content: "[a](a.md)\n" },
+      result: report,
+    });
+    const ctrl = createDiagnosticsController({
+      state,
+      invoke,
+      codeEditorController,
+    });
+
+    await ctrl.refresh();
+
+    assert.deepEqual(invoke.mock.calls[0].arguments, [
+      "check_markdown_links",
+      { path: "/repo/plan.md", content: "[a](a.md)\n" },
+    ]);
+    const expected = [
+      {
+        message: "a.md does not exist",
+        line: 7,
+        column: 2,
+        severity: "warning",
+      },
+    ];
+    assert.deepEqual(brokenLinkDiagnostics(report), expected);
+    assert.deepEqual(
+      codeEditorController.setDiagnostics.mock.calls[0].arguments[0],
+      expected,
+    );
+  });
+});
@@ -2,6 +2,7 @@ import assert from "node:assert/strict";
 import { describe, it, mock } from "node:test";
 
 import {
+  bindStructureToggles,
   createUnifiedDiffController,
   getAdjacentTocId,
 } from "../../../src/ui/diff/unified-controller.js";
@@ -147,3 +148,54 @@ describe("createUnifiedDiffController", () => {
     assert.equal(el.unifiedDiff.innerHTML, "");
   });
 });
+
+describe("bindStructureToggles", () => {
+  function fakeFile() {
+    const attrs = {};
+    const body = { innerHTML: "<div>line diff</div>" };
+    const fileEl = {
+      dataset: { path: "deploy.yaml" },
+      querySelector: () => body,
+    };
+    const button = {
+      closest: (selector) =>
+        selector === ".udiff-view-toggle" ? button : fileEl,
+      setAttribute: (name, value) => {
+        attrs[name] = value;
+      },
+    };
+    let handler = null;
+    const container = {
+      addEventListener: (_type, fn) => {
+        handler = fn;
+      },
+      removeEventListener: () => {
+        handler = null;
+      },
+    };
+    const click = () => handler({ target: button });
+    return { attrs, body, container, click };
+  }
+
+  it("swaps the file body between line and structural views", async () => {
+    const { attrs, body, container, click } = fakeFile();
+    const invoke = mock.fn(async () => [
+      { path: "spec.replicas", change: "changed", oldValue: 2, newValue: 3 },
+    ]);
+    const cleanup = bindStructureToggles(container, "/project", invoke);
+
+    await click();
+    assert.deepEqual(invoke.mock.calls[0].arguments, [
+      "git_semantic_diff",
+      { root: "/project", relPath: "deploy.yaml" },
+    ]);
+    assert.ok(body.innerHTML.includes("+spec.replicas: 3"));
+    assert.equal(attrs["aria-pressed"], "true");
+
+    await click();
+    assert.equal(body.innerHTML, "<div>line diff</div>");
+    assert.equal(attrs["aria-pressed"], "false");
+    assert.equal(invoke.mock.calls.length, 1);
+    cleanup();
+  });
+});
@@ -1,7 +1,11 @@
 import assert from "node:assert/strict";
 import { describe, it } from "node:test";
 
-import { buildUnifiedDiffHtml } from "../../../src/ui/diff/unified-renderer.js";
+import {
+  buildSemanticChangesHtml,
+  buildUnifiedDiffHtml,
+  isStructuredPath,
+} from "../../../src/ui/diff/unified-renderer.js";
 
 describe("buildUnifiedDiffHtml", () => {
   it("returns empty state message for empty array", () => {
@@ -268,3 +272,39 @@ describe("buildUnifiedDiffHtml", () => {
     assert.ok(!html.includes("udiff-toc-removed"));
   });
 });
+
+describe("structural diff view", () => {
+  it("offers a Structure toggle only for JSON, YAML and TOML files", () => {
+    const html = buildUnifiedDiffHtml([
+      { rel_path: "deploy.yaml", hunks: [] },
+      { rel_path: "notes.md", hunks: [] },
+    ]);
+    assert.equal(html.split("udiff-view-toggle").length - 1, 1);
+    assert.ok(isStructuredPath("Cargo.toml"));
+    assert.ok(isStructuredPath("a/b.JSON"));
+    assert.ok(!isStructuredPath("notes.md"));
+  });
+
+  it("renders changes by path with old and new values", () => {
+    const html = buildSemanticChangesHtml([
+      {
+        path: "spec.replicas",
+        change: "changed",
+        oldValue: 2,
+        newValue: 3,
+      },
+      { path: "spec.paused", change: "added", newValue: false },
+      { path: "metadata.labels", change: "removed", oldValue: { a: "<b>" } },
+    ]);
+    assert.ok(html.includes("-spec.replicas: 2"));
+    assert.ok(html.includes("+spec.replicas: 3"));
+    assert.ok(html.includes("+spec.paused: false"));
+    assert.ok(
+      html.includes("-metadata.labels: {&quot;a&quot;:&quot;&lt;b&gt;&quot;}"),
+    );
+  });
+
+  it("explains when only formatting changed", () => {
+    assert.ok(buildSemanticChangesHtml([]).includes("No structural changes"));
+  });
+});
@@ -8,6 +8,7 @@ import {
   isEditableState,
   shouldAutosaveOnToggle,
 } from "../../../src/ui/editor/controller.js";
+import { fileVersionFor } from "../../../src/files/versions.js";
 
 test("isEditableState depends on active file and markdown mode", () => {
   assert.equal(isEditableState({ activePath: null }), false);
@@ -35,6 +36,160 @@ test("isEditableState depends on active file and markdown mode", () => {
     }),
     true,
   );
+  assert.equal(
+    isEditableState({
+      activePath: "/big.log",
+      activeKind: "text",
+      activeWritable: false,
+      markdownViewMode: "edit",
+    }),
+    false,
+  );
+});
+
+test("saveNow never writes a read-only or large-file buffer", async () => {
+  const invokeCalls = [];
+  const state = {
+    activePath: "/big.log",
+    activeKind: "text",
+    activeWritable: false,
+    markdownViewMode: "edit",
+    isDirty: true,
+    isSaving: false,
+    content: "first 1000 lines",
+    mode: "file",
+    openFiles: [],
+    activeTabIndex: 0,
+  };
+  const controller = createEditorController({
+    state,
+    invoke: (cmd, args) => {
+      invokeCalls.push({ cmd, args });
+      return Promise.resolve();
+    },
+    setStatus: () => {},
+    render: () => {},
+    hasTabSession: () => false,
+  });
+
+  await controller.saveNow();
+
+  assert.equal(controller.isEditable(), false);
+  assert.deepEqual(invokeCalls, []);
+});
+
+test("saveNow writes back the encoding, line ending and BOM the file was read with", async () => {
+  const invokeCalls = [];
+  const state = {
+    activePath: "/legacy.txt",
+    activeKind: "text",
+    activeEncoding: "Shift_JIS",
+    activeLineEnding: "crlf",
+    activeHasBom: false,
+    markdownViewMode: "edit",
+    isDirty: true,
+    isSaving: false,
+    content: "edited",
+    mode: "file",
+    openFiles: [],
+    activeTabIndex: 0,
+  };
+  const controller = createEditorController({
+    state,
+    invoke: (cmd, args) => {
+      invokeCalls.push({ cmd, args });
+      return Promise.resolve({ version: "v2" });
+    },
+    setStatus: () => {},
+    render: () => {},
+    hasTabSession: () => false,
+  });
+
+  await controller.saveNow();
+
+  assert.equal(invokeCalls[0].cmd, "write_text_file");
+  assert.equal(invokeCalls[0].args.encoding, "Shift_JIS");
+  assert.equal(invokeCalls[0].args.lineEnding, "crlf");
+  assert.equal(invokeCalls[0].args.hasBom, false);
+});
+
+function conflictingSave(choice) {
+  const invokeCalls = [];
+  const state = {
+    activePath: "/notes.txt",
+    activeKind: "text",
+    markdownViewMode: "edit",
+    isDirty: true,
+    isSaving: false,
+    content: "ours",
+    savedContent: "base",
+    mode: "file",
+    openFiles: [],
+    activeTabIndex: 0,
+  };
+  const prompts = [];
+  const controller = createEditorController({
+    state,
+    invoke: (cmd, args) => {
+      invokeCalls.push({ cmd, args });
+      if (invokeCalls.length === 1) {
+        return Promise.reject({
+          kind: "conflict",
+          message: "File was changed outside Teex since it was opened",
+          path: args.path,
+          expectedVersion: "v1",
+          currentVersion: "v2",
+          ours: args.content,
+          theirs: "theirs",
+        });
+      }
+      return Promise.resolve({ version: "v3" });
+    },
+    setStatus: () => {},
+    render: () => {},
+    hasTabSession: () => false,
+    promptWriteConflict: (label) => {
+      prompts.push(label);
+      return Promise.resolve(choice);
+    },
+  });
+  return { controller, state, invokeCalls, prompts };
+}
+
+test("saveNow overwrites a conflicting file against its current version", async () => {
+  const { controller, state, invokeCalls, prompts } =
+    conflictingSave("overwrite");
+
+  await controller.saveNow();
+
+  assert.deepEqual(prompts, ["notes.txt"]);
+  assert.equal(invokeCalls.length, 2);
+  assert.equal(invokeCalls[1].args.expectedVersion, "v2");
+  assert.equal(invokeCalls[1].args.content, "ours");
+  assert.equal(fileVersionFor("/notes.txt"), "v3");
+  assert.equal(state.isDirty, false);
+  assert.equal(state.savedContent, "ours");
+});
+
+test("saveNow reloads the disk content of a conflicting file", async () => {
+  const { controller, state, invokeCalls } = conflictingSave("reload");
+
+  await controller.saveNow();
+
+  assert.equal(invokeCalls.length, 1);
+  assert.equal(fileVersionFor("/notes.txt"), "v2");
+  assert.equal(state.content, "theirs");
+  assert.equal(state.isDirty, false);
+});
+
+test("saveNow keeps local edits when a conflict is cancelled", async () => {
+  const { controller, state, invokeCalls } = conflictingSave("cancel");
+
+  await controller.saveNow();
+
+  assert.equal(invokeCalls.length, 1);
+  assert.equal(state.content, "ours");
+  assert.equal(state.isDirty, true);
 });
 
 test("shouldAutosaveOnToggle returns true only for saved dirty files", () => {
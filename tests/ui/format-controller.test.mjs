@@ -1,8 +1,10 @@
 import assert from "node:assert/strict";
-import { describe, it } from "node:test";
+import { after, before, describe, it, mock } from "node:test";
 import {
+  createFormatController,
   detectFormatKind,
   formatActiveFileContent,
+  formatKindLabel,
 } from "../../src/ui/format-controller.js";
 
 describe("detectFormatKind", () => {
@@ -10,8 +12,14 @@ describe("detectFormatKind", () => {
     assert.equal(detectFormatKind("/foo/bar.json"), "json");
   });
 
-  it("returns json for .jsonc files", () => {
-    assert.equal(detectFormatKind("/foo/bar.jsonc"), "json");
+  it("returns jsonc for .jsonc files", () => {
+    assert.equal(detectFormatKind("/foo/bar.jsonc"), "jsonc");
+  });
+
+  it("returns json5 and jsonl for their extensions", () => {
+    assert.equal(detectFormatKind("/foo/bar.json5"), "json5");
+    assert.equal(detectFormatKind("/foo/evals.jsonl"), "jsonl");
+    assert.equal(detectFormatKind("/foo/events.ndjson"), "jsonl");
   });
 
   it("returns json for .geojson files", () => {
@@ -57,6 +65,12 @@ describe("detectFormatKind", () => {
   it("returns csv for .tsv files", () => {
     assert.equal(detectFormatKind("/foo/data.tsv"), "csv");
   });
+
+  it("returns markdown for .md files", () => {
+    assert.equal(detectFormatKind("/foo/README.md"), "markdown");
+    assert.equal(formatKindLabel("markdown"), "Markdown tables");
+    assert.equal(formatKindLabel("yaml"), "YAML");
+  });
 });
 
 describe("formatActiveFileContent", () => {
@@ -104,6 +118,7 @@ describe("formatActiveFileContent", () => {
     assert.equal(invokedArgs.cmd, "format_structured_text");
     assert.equal(invokedArgs.args.content, '{"a":1}');
     assert.equal(invokedArgs.args.preferredKind, "json");
+    assert.equal(invokedArgs.args.style, "pretty");
     assert.equal(result.formatted, '{\n  "a": 1\n}');
     assert.equal(result.kind, "json");
     assert.equal(result.changed, true);
@@ -141,4 +156,97 @@ describe("formatActiveFileContent", () => {
     });
     assert.equal(result, null);
   });
+
+  it("passes the style through to the backend", async () => {
+    let invokedArgs = null;
+    await formatActiveFileContent({
+      activePath: "/foo/bar.json",
+      content: '{"b":1,"a":2}',
+      style: "sortKeys",
+      invoke: (_cmd, args) => {
+        invokedArgs = args;
+        return null;
+      },
+    });
+    assert.equal(invokedArgs.style, "sortKeys");
+  });
+
+  it("reports invalid JSONL lines even when nothing changed", async () => {
+    const result = await formatActiveFileContent({
+      activePath: "/foo/evals.jsonl",
+      content: '{"a":1}\nnope',
+      invoke: () => ({
+        formatted: '{"a":1}\nnope',
+        detectedKind: "jsonl",
+        changed: false,
+        invalidLines: [2],
+      }),
+    });
+    assert.equal(result.changed, false);
+    assert.deepEqual(result.invalidLines, [2]);
+  });
+});
+
+describe("copyTableAs", () => {
+  const written = [];
+  const toasts = [];
+
+  before(() => {
+    const toast = {
+      classList: { add() {}, remove() {} },
+      setAttribute() {},
+      set textContent(message) {
+        toasts.push(message);
+      },
+    };
+    globalThis.document = {
+      createElement: () => toast,
+      querySelector: () => ({ appendChild() {} }),
+    };
+    globalThis.requestAnimationFrame = (callback) => callback();
+    Object.defineProperty(globalThis, "navigator", {
+      configurable: true,
+      value: { clipboard: { writeText: async (text) => written.push(text) } },
+    });
+  });
+
+  after(() => {
+    delete globalThis.document;
+    delete globalThis.requestAnimationFrame;
+  });
+
+  it("copies CSV files as Markdown and Markdown tables as CSV", async () => {
+    const invoke = mock.fn(async (command) =>
+      command === "csv_to_markdown_table" ? "| a |\n| - |\n" : "a\n",
+    );
+    const state = { activePath: "/data/t.csv", content: "a\n" };
+    const codeEditorController = {
+      isAttached: () => true,
+      getCursorLine: () => 4,
+    };
+    const ctrl = createFormatController({
+      state,
+      invoke,
+      codeEditorController,
+    });
+
+    await ctrl.copyTableAs("markdown");
+    state.activePath = "/docs/report.md";
+    await ctrl.copyTableAs("csv");
+    await ctrl.copyTableAs("markdown");
+
+    assert.deepEqual(
+      invoke.mock.calls.map((call) => call.arguments),
+      [
+        ["csv_to_markdown_table", { content: "a\n", path: "/data/t.csv" }],
+        ["markdown_table_to_csv", { content: "a\n", line: 4 }],
+      ],
+    );
+    assert.deepEqual(written, ["| a |\n| - |\n", "a\n"]);
+    assert.deepEqual(toasts, [
+      "Copied as Markdown table",
+      "Copied table as CSV",
+      "Open a CSV or TSV file to copy it as a Markdown table",
+    ]);
+  });
 });
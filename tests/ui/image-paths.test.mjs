@@ -1,7 +1,11 @@
 import assert from "node:assert/strict";
 import test from "node:test";
 
-import { resolveImagePath, toLocalImageUrl } from "../../src/ui/image-paths.js";
+import {
+  resolveImagePath,
+  resolveLinkPath,
+  toLocalImageUrl,
+} from "../../src/ui/image-paths.js";
 
 test("resolveImagePath returns null for http URLs", () => {
   assert.equal(resolveImagePath("http://example.com/img.png", "/tmp"), null);
@@ -66,3 +70,18 @@ test("toLocalImageUrl produces localimage protocol URL", () => {
     "localimage://localhost/Users/kel/docs/img.png",
   );
 });
+
+test("resolveLinkPath resolves local links and leaves URLs alone", () => {
+  assert.equal(
+    resolveLinkPath("../src/main%20file.rs#L3", "/repo/docs"),
+    "/repo/src/main file.rs",
+  );
+  assert.equal(
+    resolveLinkPath("guide.md", "/repo/docs"),
+    "/repo/docs/guide.md",
+  );
+  assert.equal(resolveLinkPath("#setup", "/repo/docs"), null);
+  assert.equal(resolveLinkPath("https://example.com/a.md", "/repo"), null);
+  assert.equal(resolveLinkPath("mailto:a@b.c", "/repo"), null);
+  assert.equal(resolveLinkPath("//cdn.example.com/x.png", "/repo"), null);
+});
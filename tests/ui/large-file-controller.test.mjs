@@ -0,0 +1,123 @@
+import assert from "node:assert/strict";
+import { describe, it, mock } from "node:test";
+
+import {
+  createLargeFileController,
+  describeLargeFileWindow,
+  largeFileWindowStart,
+  utf16Column,
+} from "../../src/ui/large-file-controller.js";
+
+function rangeOf(startLine, count, total) {
+  const lines = [];
+  for (let line = startLine; line < startLine + count && line <= total; line++) {
+    lines.push(`line ${line}`);
+  }
+  return {
+    startLine,
+    lines,
+    totalLines: total,
+    hasMore: startLine - 1 + lines.length < total,
+  };
+}
+
+function setup(total = 100_000) {
+  const state = {
+    activePath: "/logs/big.log",
+    activeLargeFile: { lineCount: total },
+    largeFileStartLine: 1,
+    content: rangeOf(1, 1000, total).lines.join("\n"),
+    savedContent: "",
+    isDirty: false,
+  };
+  const invoke = mock.fn(async (cmd, args) => {
+    if (cmd === "read_text_range") {
+      return rangeOf(args.startLine, args.count, total);
+    }
+    return [];
+  });
+  const codeEditorController = {
+    scrollToLine: mock.fn(),
+    selectInLine: mock.fn(),
+    getVisibleLineRange: () => null,
+  };
+  const ctrl = createLargeFileController({
+    state,
+    invoke,
+    render: mock.fn(),
+    setStatus: mock.fn(),
+    codeEditorController,
+  });
+  return { state, invoke, codeEditorController, ctrl };
+}
+
+describe("large file windows", () => {
+  it("centres the window on a line and clamps it to the file", () => {
+    assert.equal(largeFileWindowStart(1, 100_000, 2000), 1);
+    assert.equal(largeFileWindowStart(50_000, 100_000, 2000), 49_000);
+    assert.equal(largeFileWindowStart(99_990, 100_000, 2000), 98_001);
+    assert.equal(largeFileWindowStart(10, 500, 2000), 1);
+  });
+
+  it("describes the loaded lines", () => {
+    assert.equal(
+      describeLargeFileWindow({
+        activeLargeFile: { lineCount: 5000 },
+        largeFileStartLine: 101,
+        content: "a\nb\nc",
+      }),
+      "Lines 101–103 of 5000 (read-only)",
+    );
+    assert.equal(describeLargeFileWindow({ activeLargeFile: null }), "");
+  });
+
+  it("converts character columns to UTF-16 columns", () => {
+    assert.equal(utf16Column("a😀b", 2), 3);
+    assert.equal(utf16Column("abc", 2), 2);
+  });
+
+  it("reads a new window only when jumping outside the loaded one", async () => {
+    const { state, invoke, codeEditorController, ctrl } = setup();
+
+    await ctrl.goToLine(500);
+    assert.equal(invoke.mock.callCount(), 0);
+    assert.deepEqual(codeEditorController.scrollToLine.mock.calls[0].arguments, [
+      500,
+    ]);
+
+    await ctrl.goToLine(60_000);
+    assert.deepEqual(invoke.mock.calls[0].arguments, [
+      "read_text_range",
+      { path: "/logs/big.log", startLine: 59_000, count: 2000 },
+    ]);
+    assert.equal(state.largeFileStartLine, 59_000);
+    assert.equal(state.content.split("\n")[0], "line 59000");
+    assert.equal(state.isDirty, false);
+    assert.deepEqual(codeEditorController.scrollToLine.mock.calls[1].arguments, [
+      1001,
+    ]);
+  });
+
+  it("searches on disk and selects a match inside the window", async () => {
+    const { state, invoke, codeEditorController, ctrl } = setup();
+
+    await ctrl.search("needle");
+    assert.deepEqual(invoke.mock.calls[0].arguments, [
+      "search_text_file",
+      {
+        path: "/logs/big.log",
+        options: { query: "needle", maxResults: 1000 },
+      },
+    ]);
+
+    await ctrl.revealMatch({
+      lineNumber: 70_000,
+      lineText: "a needle",
+      ranges: [[2, 8]],
+    });
+    assert.equal(state.largeFileStartLine, 69_000);
+    assert.deepEqual(codeEditorController.selectInLine.mock.calls[0].arguments, [
+      1001, 2, 8,
+    ]);
+  });
+});
@@ -163,6 +163,30 @@ test("detects XML by content", () => {
   );
 });
 
+test("detects Markdown tables by their delimiter row", () => {
+  assert.equal(
+    detectStructuredPasteKind({
+      activePath: "/notes/report.md",
+      text: "name|qty\n:--|--:\nwidget|3",
+    }),
+    "markdown",
+  );
+  assert.equal(
+    detectStructuredPasteKind({
+      activePath: null,
+      text: "intro\n| a | b |\n| --- | --- |\n",
+    }),
+    "markdown",
+  );
+  assert.equal(
+    detectStructuredPasteKind({
+      activePath: "/notes/report.md",
+      text: "Title\n---\nbody",
+    }),
+    null,
+  );
+});
+
 test("does not detect CSV by content alone", () => {
   assert.equal(
     detectStructuredPasteKind({
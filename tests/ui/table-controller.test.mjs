@@ -0,0 +1,128 @@
+import assert from "node:assert/strict";
+import { describe, it, mock } from "node:test";
+
+import { fileVersionFor, recordFileVersion } from "../../src/files/versions.js";
+import {
+  buildTableHtml,
+  describeTablePage,
+  isTableFile,
+  isTableViewActive,
+  nextSort,
+  saveTableCell,
+} from "../../src/ui/table-controller.js";
+
+const page = {
+  version: "v1",
+  dialect: { delimiter: "\t", quote: '"', hasHeader: true },
+  columns: [
+    { name: "id", kind: "integer" },
+    { name: "<name>", kind: "string" },
+  ],
+  totalRows: 500,
+  matchedRows: 250,
+  offset: 200,
+  rows: [{ index: 7, cells: ["8", "a & b"] }],
+};
+
+describe("table view", () => {
+  it("applies to CSV and TSV files in the code editor", () => {
+    assert.equal(isTableFile("/data/people.csv"), true);
+    assert.equal(isTableFile("/data/people.TSV"), true);
+    assert.equal(isTableFile("/data/people.json"), false);
+
+    const state = {
+      tableView: true,
+      activeKind: "code",
+      activePath: "/data/people.csv",
+    };
+    assert.equal(isTableViewActive(state), true);
+    assert.equal(isTableViewActive({ ...state, tableView: false }), false);
+    assert.equal(isTableViewActive({ ...state, activeKind: "diff" }), false);
+  });
+
+  it("cycles header sorting through ascending, descending and none", () => {
+    let sort = { column: null, descending: false };
+    sort = nextSort(sort, 1);
+    assert.deepEqual(sort, { column: 1, descending: false });
+    sort = nextSort(sort, 1);
+    assert.deepEqual(sort, { column: 1, descending: true });
+    assert.deepEqual(nextSort(sort, 1), { column: null, descending: false });
+    assert.deepEqual(nextSort(sort, 0), { column: 0, descending: false });
+  });
+
+  it("renders escaped cells with their file row numbers", () => {
+    const html = buildTableHtml(page, { column: 0, descending: true });
+    assert.match(html, /data-column="0" title="integer">id ▼<\/th>/);
+    assert.match(html, /&lt;name&gt;<\/th>/);
+    assert.match(html, /<tr data-row="7"><th class="table-row-number">8<\/th>/);
+    assert.match(html, /class="table-col-integer" data-column="0">8<\/td>/);
+    assert.match(html, /a &amp; b<\/td>/);
+    assert.equal(
+      describeTablePage(page),
+      "201–201 of 250 matches in 500 rows · tab",
+    );
+  });
+});
+
+describe("saveTableCell", () => {
+  it("writes the cell against the version the page was read at", async () => {
+    const invoke = mock.fn(async () => ({ version: "v2" }));
+    const failure = await saveTableCell({
+      state: { activePath: "/data/t.csv", isDirty: false },
+      invoke,
+      path: "/data/t.csv",
+      version: "v1",
+      row: 3,
+      column: 1,
+      value: "x",
+    });
+
+    assert.equal(failure, null);
+    assert.deepEqual(invoke.mock.calls[0].arguments, [
+      "update_table_cell",
+      {
+        path: "/data/t.csv",
+        row: 3,
+        column: 1,
+        value: "x",
+        expectedVersion: "v1",
+      },
+    ]);
+    assert.equal(fileVersionFor("/data/t.csv"), "v2");
+    recordFileVersion("/data/t.csv", null);
+  });
+
+  it("reports conflicts instead of overwriting", async () => {
+    const invoke = mock.fn(async () => {
+      throw { kind: "conflict", message: "changed" };
+    });
+    const failure = await saveTableCell({
+      state: { activePath: "/data/t.csv", isDirty: false },
+      invoke,
+      path: "/data/t.csv",
+      version: "v0",
+      row: 0,
+      column: 0,
+      value: "x",
+    });
+
+    assert.match(failure, /changed on disk/);
+    assert.equal(fileVersionFor("/data/t.csv"), null);
+  });
+
+  it("refuses edits while the text has unsaved changes", async () => {
+    const invoke = mock.fn(async () => ({ version: "v2" }));
+    const failure = await saveTableCell({
+      state: { activePath: "/data/t.csv", isDirty: true },
+      invoke,
+      path: "/data/t.csv",
+      version: "v1",
+      row: 0,
+      column: 0,
+      value: "x",
+    });
+
+    assert.equal(failure, "Save the file before editing the table");
+    assert.equal(invoke.mock.callCount(), 0);
+  });
+});
@@ -0,0 +1,83 @@
+import assert from "node:assert/strict";
+import { describe, it, mock } from "node:test";
+
+import { recordFileVersion } from "../../src/files/versions.js";
+import {
+  createTaskProgressController,
+  describeTaskProgress,
+} from "../../src/ui/task-progress-controller.js";
+
+describe("task progress", () => {
+  it("describes documents with tasks only", () => {
+    assert.equal(describeTaskProgress({ done: 2, total: 5 }), "2/5 tasks");
+    assert.equal(describeTaskProgress({ done: 0, total: 0 }), "");
+    assert.equal(describeTaskProgress(null), "");
+  });
+
+  it("reads counts once per file version and takes pushed updates", async () => {
+    const invoke = mock.fn(async () => ({ done: 1, total: 3, unchecked: [] }));
+    const onChange = mock.fn();
+    const state = {
+      activeKind: "markdown",
+      activePath: "/plans/plan.md",
+      taskProgress: null,
+    };
+    const ctrl = createTaskProgressController({ state, invoke, onChange });
+
+    await ctrl.refresh();
+    await ctrl.refresh();
+    assert.equal(invoke.mock.callCount(), 1);
+    assert.deepEqual(invoke.mock.calls[0].arguments, [
+      "read_task_list",
+      { path: "/plans/plan.md" },
+    ]);
+    assert.deepEqual(state.taskProgress, {
+      path: "/plans/plan.md",
+      done: 1,
+      total: 3,
+    });
+
+    ctrl.handleProgress({ path: "/plans/other.md", done: 9, total: 9 });
+    ctrl.handleProgress({ path: "/plans/plan.md", done: 2, total: 3 });
+    assert.equal(state.taskProgress.done, 2);
+    assert.equal(onChange.mock.callCount(), 2);
+
+    recordFileVersion("/plans/plan.md", "v2");
+    await ctrl.refresh();
+    assert.equal(invoke.mock.callCount(), 2);
+    recordFileVersion("/plans/plan.md", null);
+
+    state.activeKind = "code";
+    state.activePath = "/src/main.rs";
+    await ctrl.refresh();
+    assert.equal(state.taskProgress, null);
+    assert.equal(invoke.mock.callCount(), 2);
+  });
+
+  it("matches pushed canonical paths to the active file", async () => {
+    const invoke = mock.fn(async () => ({
+      path: "/private/tmp/plan.md",
+      done: 0,
+      total: 2,
+      unchecked: [],
+    }));
+    const state = {
+      activeKind: "markdown",
+      activePath: "/tmp/plan.md",
+      taskProgress: null,
+    };
+    const ctrl = createTaskProgressController({ state, invoke });
+
+    await ctrl.refresh();
+    ctrl.handleProgress({ path: "/private/tmp/plan.md", done: 1, total: 2 });
+    assert.deepEqual(state.taskProgress, {
+      path: "/tmp/plan.md",
+      done: 1,
+      total: 2,
+    });
+
+    state.activePath = "/tmp/other.md";
+    ctrl.handleProgress({ path: "/private/tmp/plan.md", done: 2, total: 2 });
+    assert.equal(state.taskProgress.done, 1);
+  });
+});